@@ -66,6 +66,8 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
         arrayOf(
             "DisableRequestCompression" to RuntimeType.clientRequestCompression(runtimeConfig).resolve("DisableRequestCompression"),
             "RequestMinCompressionSizeBytes" to RuntimeType.clientRequestCompression(runtimeConfig).resolve("RequestMinCompressionSizeBytes"),
+            "RequestCompressionAlgorithm" to RuntimeType.clientRequestCompression(runtimeConfig).resolve("RequestCompressionAlgorithm"),
+            "CompressionAlgorithm" to RuntimeType.smithyCompression(runtimeConfig).resolve("CompressionAlgorithm"),
             "Storable" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::Storable"),
             "StoreReplace" to RuntimeType.smithyTypes(runtimeConfig).resolve("config_bag::StoreReplace"),
             *preludeScope,
@@ -86,6 +88,11 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                         pub fn request_min_compression_size_bytes(&self) -> #{Option}<u32> {
                             self.config.load::<#{RequestMinCompressionSizeBytes}>().map(|it| it.0)
                         }
+
+                        /// Returns the preferred `request compression algorithm`, if it was provided.
+                        pub fn request_compression_algorithm(&self) -> #{Option}<#{CompressionAlgorithm}> {
+                            self.config.load::<#{RequestCompressionAlgorithm}>().map(|it| it.0)
+                        }
                         """,
                         *codegenScope,
                     )
@@ -105,6 +112,15 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                             self.set_request_min_compression_size_bytes(request_min_compression_size_bytes.into());
                             self
                         }
+
+                        /// Sets the preferred `request compression algorithm` used when making requests.
+                        ///
+                        /// It is only used for operations that support it; other operations use the
+                        /// first compression algorithm they support.
+                        pub fn request_compression_algorithm(mut self, request_compression_algorithm: impl #{Into}<#{Option}<#{CompressionAlgorithm}>>) -> Self {
+                            self.set_request_compression_algorithm(request_compression_algorithm.into());
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
@@ -122,6 +138,15 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                             self.config.store_or_unset::<#{RequestMinCompressionSizeBytes}>(request_min_compression_size_bytes.map(Into::into));
                             self
                         }
+
+                        /// Sets the preferred `request compression algorithm` used when making requests.
+                        ///
+                        /// It is only used for operations that support it; other operations use the
+                        /// first compression algorithm they support.
+                        pub fn set_request_compression_algorithm(&mut self, request_compression_algorithm: #{Option}<#{CompressionAlgorithm}>) -> &mut Self {
+                            self.config.store_or_unset::<#{RequestCompressionAlgorithm}>(request_compression_algorithm.map(Into::into));
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
@@ -134,6 +159,8 @@ class HttpRequestCompressionConfigCustomization(codegenContext: ClientCodegenCon
                             ${section.configBag}.load::<#{DisableRequestCompression}>().cloned().map(|it| it.0));
                         ${section.builder}.set_request_min_compression_size_bytes(
                             ${section.configBag}.load::<#{RequestMinCompressionSizeBytes}>().cloned().map(|it| it.0));
+                        ${section.builder}.set_request_compression_algorithm(
+                            ${section.configBag}.load::<#{RequestCompressionAlgorithm}>().cloned().map(|it| it.0));
                         """,
                         *codegenScope,
                     )
@@ -37,7 +37,7 @@ class HttpRequestCompressionDecoratorTest {
             })
             service TestService {
                 version: "2023-01-01",
-                operations: [SomeOperation, SomeStreamingOperation, NotACompressibleOperation]
+                operations: [SomeOperation, SomeStreamingOperation, SomeZstdOperation, NotACompressibleOperation]
             }
 
             @streaming
@@ -81,6 +81,14 @@ class HttpRequestCompressionDecoratorTest {
             @output
             structure SomeStreamingOutput {}
 
+            @http(uri: "/SomeZstdOperation", method: "POST")
+            @optionalAuth
+            @requestCompression(encodings: ["zstd", "gzip"])
+            operation SomeZstdOperation {
+                input: SomeInput,
+                output: SomeOutput
+            }
+
             @http(uri: "/NotACompressibleOperation", method: "PUT")
             @optionalAuth
             operation NotACompressibleOperation {
@@ -440,6 +448,74 @@ class HttpRequestCompressionDecoratorTest {
                             "expected that no content length header is set because the request is streaming."
                         );
                     }
+
+                    ##[#{tokio}::test]
+                    async fn test_request_compression_uses_first_supported_encoding() {
+                        let (http_client, rx) = #{capture_request}(None);
+                        let config = $moduleName::Config::builder()
+                            .region(Region::from_static("doesntmatter"))
+                            .with_test_defaults()
+                            .http_client(http_client)
+                            .request_min_compression_size_bytes(128)
+                            .build();
+
+                        let client = $moduleName::Client::from_conf(config);
+                        let _ = client
+                            .some_zstd_operation()
+                            .body(Blob::new(UNCOMPRESSED_INPUT))
+                            .send()
+                            .await;
+                        let request = rx.expect_request();
+                        // Check that the content-encoding header is set to "zstd"
+                        assert_eq!(
+                            Some("zstd"),
+                            request.headers().get(#{http_1x}::header::CONTENT_ENCODING)
+                        );
+
+                        let compressed_body = ByteStream::from(request.into_body())
+                            .collect()
+                            .await
+                            .unwrap()
+                            .to_vec();
+                        let mut decompressor = #{CompressionAlgorithm}::Zstd.stream_decompressor();
+                        let mut decompressed_body = decompressor.decompress_chunk(&compressed_body).unwrap().to_vec();
+                        decompressed_body.extend_from_slice(&decompressor.finish().unwrap());
+                        // Assert input body was compressed with zstd
+                        assert_eq!(UNCOMPRESSED_INPUT, decompressed_body.as_slice());
+                    }
+
+                    ##[#{tokio}::test]
+                    async fn test_request_compression_uses_configured_algorithm() {
+                        let (http_client, rx) = #{capture_request}(None);
+                        let config = $moduleName::Config::builder()
+                            .region(Region::from_static("doesntmatter"))
+                            .with_test_defaults()
+                            .http_client(http_client)
+                            .request_min_compression_size_bytes(128)
+                            .request_compression_algorithm(#{CompressionAlgorithm}::Gzip)
+                            .build();
+
+                        let client = $moduleName::Client::from_conf(config);
+                        let _ = client
+                            .some_zstd_operation()
+                            .body(Blob::new(UNCOMPRESSED_INPUT))
+                            .send()
+                            .await;
+                        let request = rx.expect_request();
+                        // Check that the content-encoding header is set to "gzip"
+                        assert_eq!(
+                            Some("gzip"),
+                            request.headers().get(#{http_1x}::header::CONTENT_ENCODING)
+                        );
+
+                        let compressed_body = ByteStream::from(request.into_body())
+                            .collect()
+                            .await
+                            .unwrap()
+                            .to_vec();
+                        // Assert input body was compressed with gzip
+                        assert_eq!(COMPRESSED_OUTPUT, compressed_body.as_slice());
+                    }
                     """,
                     *preludeScope,
                     "ByteStream" to RuntimeType.smithyTypes(rc).resolve("byte_stream::ByteStream"),
@@ -451,6 +527,7 @@ class HttpRequestCompressionDecoratorTest {
                     "pretty_assertions" to CargoDependency.PrettyAssertions.toType(),
                     "tempfile" to CargoDependency.TempFile.toType(),
                     "Length" to RuntimeType.smithyTypes(rc).resolve("byte_stream::Length"),
+                    "CompressionAlgorithm" to RuntimeType.smithyCompression(rc).resolve("CompressionAlgorithm"),
                 )
             }
         }
@@ -12,6 +12,7 @@ use aws_runtime::content_encoding::AwsChunkedBodyOptions;
 use aws_smithy_checksums::body::calculate;
 use aws_smithy_checksums::body::ChecksumCache;
 use aws_smithy_checksums::http::HttpChecksum;
+use aws_smithy_checksums::registry::ChecksumRegistry;
 use aws_smithy_checksums::ChecksumAlgorithm;
 use aws_smithy_runtime::client::sdk_feature::SmithySdkFeature;
 use aws_smithy_runtime_api::box_error::BoxError;
@@ -62,10 +63,12 @@ struct RequestChecksumInterceptorState {
 }
 
 impl RequestChecksumInterceptorState {
-    fn checksum_algorithm(&self) -> Option<ChecksumAlgorithm> {
+    /// Create a checksum calculator for the selected algorithm, checking any user-registered
+    /// algorithms in the `ChecksumRegistry` before the built-in ones.
+    fn checksum(&self, registry: &ChecksumRegistry) -> Option<Box<dyn HttpChecksum>> {
         self.checksum_algorithm
             .as_ref()
-            .and_then(|s| ChecksumAlgorithm::from_str(s.as_str()).ok())
+            .and_then(|s| registry.resolve(s.as_str()).ok())
     }
 
     fn calculate_checksum(&self) -> bool {
@@ -190,27 +193,37 @@ where
             .get_mut_from_interceptor_state::<RequestChecksumInterceptorState>()
             .expect("set in `read_before_serialization`");
 
-        // If the algorithm fails to parse it is not one we support and we error
-        let checksum_algorithm = state
-            .checksum_algorithm
-            .clone()
-            .map(|s| ChecksumAlgorithm::from_str(s.as_str()))
-            .transpose()?;
-
         let mut state = std::mem::take(state);
 
+        // Algorithms registered by the user in a `ChecksumRegistry` are used as-is
+        let custom_checksum_algorithm = state.checksum_algorithm.clone().filter(|s| {
+            cfg.load::<ChecksumRegistry>()
+                .is_some_and(|registry| registry.is_registered(s))
+        });
+        // Otherwise, if the algorithm fails to parse it is not one we support and we error
+        let checksum_algorithm = match custom_checksum_algorithm {
+            Some(_) => None,
+            None => state
+                .checksum_algorithm
+                .clone()
+                .map(|s| ChecksumAlgorithm::from_str(s.as_str()))
+                .transpose()?,
+        };
+
         if calculate_checksum(cfg, &state) {
             state.calculate_checksum.store(true, Ordering::Release);
 
-            // If a checksum override is set in the ConfigBag we use that instead (currently only used by S3Express)
-            // If we have made it this far without a checksum being set we set the default (currently Crc32)
-            let checksum_algorithm =
-                incorporate_custom_default(checksum_algorithm, cfg).unwrap_or_default();
-            state.checksum_algorithm = Some(checksum_algorithm.as_str().to_owned());
+            if custom_checksum_algorithm.is_none() {
+                // If a checksum override is set in the ConfigBag we use that instead (currently only used by S3Express)
+                // If we have made it this far without a checksum being set we set the default (currently Crc32)
+                let checksum_algorithm =
+                    incorporate_custom_default(checksum_algorithm, cfg).unwrap_or_default();
+                state.checksum_algorithm = Some(checksum_algorithm.as_str().to_owned());
 
-            // NOTE: We have to do this in modify_before_retry_loop since UA interceptor also runs
-            // in modify_before_signing but is registered before this interceptor (client level vs operation level).
-            track_metric_for_selected_checksum_algorithm(cfg, &checksum_algorithm);
+                // NOTE: We have to do this in modify_before_retry_loop since UA interceptor also runs
+                // in modify_before_signing but is registered before this interceptor (client level vs operation level).
+                track_metric_for_selected_checksum_algorithm(cfg, &checksum_algorithm);
+            }
         } else {
             // No checksum calculation needed so disable aws-chunked encoding
             cfg.interceptor_state()
@@ -238,14 +251,18 @@ where
             return Ok(());
         }
 
+        let registry = cfg.load::<ChecksumRegistry>().cloned().unwrap_or_default();
         let checksum_algorithm = state
-            .checksum_algorithm()
+            .checksum_algorithm
+            .as_deref()
             .expect("set in `modify_before_retry_loop`");
-        let mut checksum = checksum_algorithm.into_impl();
+        let mut checksum = state
+            .checksum(&registry)
+            .expect("validated in `modify_before_retry_loop`");
 
         match context.request().body().bytes() {
             Some(data) => {
-                tracing::debug!("applying {checksum_algorithm:?} of the request body as a header");
+                tracing::debug!("applying {checksum_algorithm} of the request body as a header");
                 checksum.update(data);
 
                 for (hdr_name, hdr_value) in
@@ -258,7 +275,7 @@ where
                 }
             }
             None => {
-                tracing::debug!("applying {checksum_algorithm:?} of the request body as a trailer");
+                tracing::debug!("applying {checksum_algorithm} of the request body as a trailer");
                 context.request_mut().headers_mut().insert(
                     HeaderName::from_static("x-amz-trailer"),
                     checksum.header_name(),
@@ -300,13 +317,14 @@ where
         let mut body = {
             let body = mem::replace(request.body_mut(), SdkBody::taken());
 
-            let checksum_algorithm = state
-                .checksum_algorithm()
-                .expect("set in `modify_before_retry_loop`");
+            let registry = cfg.load::<ChecksumRegistry>().cloned().unwrap_or_default();
+            let state = state.clone();
             let checksum_cache = state.checksum_cache.clone();
 
             body.map(move |body| {
-                let checksum = checksum_algorithm.into_impl();
+                let checksum = state
+                    .checksum(&registry)
+                    .expect("validated in `modify_before_retry_loop`");
                 let body =
                     calculate::ChecksumBody::new(body, checksum).with_cache(checksum_cache.clone());
 
@@ -407,6 +425,8 @@ fn track_metric_for_selected_checksum_algorithm(
             cfg.interceptor_state()
                 .store_append(SmithySdkFeature::FlexibleChecksumsReqSha256);
         }
+        // There are no user-agent metrics defined for these algorithms
+        ChecksumAlgorithm::Sha512 | ChecksumAlgorithm::XxHash64 | ChecksumAlgorithm::XxHash3 => {}
         unsupported => tracing::warn!(
                 more_info = "Unsupported value of ChecksumAlgorithm detected when setting user-agent metrics",
                 unsupported = ?unsupported),
@@ -416,6 +436,7 @@ fn track_metric_for_selected_checksum_algorithm(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aws_smithy_checksums::test_util::ByteCount;
     use aws_smithy_checksums::ChecksumAlgorithm;
     use aws_smithy_runtime_api::client::interceptors::context::{
         BeforeTransmitInterceptorContextMut, InterceptorContext,
@@ -528,4 +549,50 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_registered_checksum_is_applied_as_header() {
+        fn algo(_: &Input) -> (Option<String>, bool) {
+            (Some("bytecount".to_string()), false)
+        }
+        fn mutator(_: &mut Request, _: &ConfigBag) -> Result<bool, BoxError> {
+            Ok(false)
+        }
+        let interceptor = RequestChecksumInterceptor::new(algo, mutator);
+
+        let mut cfg = ConfigBag::base();
+        let mut layer = aws_smithy_types::config_bag::Layer::new("test");
+        layer.store_put(
+            ChecksumRegistry::new()
+                .with_checksum("bytecount", || Box::<ByteCount>::default())
+                .unwrap(),
+        );
+        cfg.push_layer(layer);
+        let runtime_components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+
+        let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+        interceptor
+            .modify_before_serialization(&mut (&mut ctx).into(), &runtime_components, &mut cfg)
+            .unwrap();
+        ctx.enter_serialization_phase();
+        let _ = ctx.take_input();
+        ctx.set_request(HttpRequest::new(SdkBody::from("Hello world")));
+        ctx.enter_before_transmit_phase();
+        let mut ctx: BeforeTransmitInterceptorContextMut<'_> = (&mut ctx).into();
+        interceptor
+            .modify_before_retry_loop(&mut ctx, &runtime_components, &mut cfg)
+            .unwrap();
+        interceptor
+            .modify_before_signing(&mut ctx, &runtime_components, &mut cfg)
+            .unwrap();
+
+        assert_eq!(
+            Some("AAAACw=="),
+            ctx.request().headers().get("x-amz-checksum-bytecount")
+        );
+        // There's no user-agent metric for custom checksum algorithms
+        assert!(cfg
+            .load::<SmithySdkFeature>()
+            .all(|feature| !matches!(feature, SmithySdkFeature::FlexibleChecksumsReqCrc32)));
+    }
 }
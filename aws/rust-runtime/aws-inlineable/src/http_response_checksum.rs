@@ -7,7 +7,7 @@
 
 //! Interceptor for handling Smithy `@httpChecksum` response checksumming
 
-use aws_smithy_checksums::ChecksumAlgorithm;
+use aws_smithy_checksums::registry::ChecksumRegistry;
 use aws_smithy_runtime::client::sdk_feature::SmithySdkFeature;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::{
@@ -133,10 +133,12 @@ where
         };
 
         if validation_enabled {
+            let registry = cfg.load::<ChecksumRegistry>().cloned().unwrap_or_default();
             let response = context.response_mut();
             let maybe_checksum_headers = check_headers_for_precalculated_checksum(
                 response.headers(),
                 self.response_algorithms,
+                &registry,
             );
 
             if let Some((checksum_algorithm, precalculated_checksum)) = maybe_checksum_headers {
@@ -146,6 +148,7 @@ where
                 let mut body = wrap_body_with_checksum_validator(
                     body,
                     checksum_algorithm,
+                    registry,
                     precalculated_checksum,
                 );
                 mem::swap(&mut body, response.body_mut());
@@ -156,12 +159,13 @@ where
     }
 }
 
-/// Given an `SdkBody`, a `aws_smithy_checksums::ChecksumAlgorithm`, and a pre-calculated checksum,
-/// return an `SdkBody` where the body will processed with the checksum algorithm and checked
-/// against the pre-calculated checksum.
+/// Given an `SdkBody`, the name of a checksum algorithm resolvable by the `ChecksumRegistry`, and a
+/// pre-calculated checksum, return an `SdkBody` where the body will processed with the checksum
+/// algorithm and checked against the pre-calculated checksum.
 pub(crate) fn wrap_body_with_checksum_validator(
     body: SdkBody,
-    checksum_algorithm: ChecksumAlgorithm,
+    checksum_algorithm: String,
+    registry: ChecksumRegistry,
     precalculated_checksum: bytes::Bytes,
 ) -> SdkBody {
     use aws_smithy_checksums::body::validate;
@@ -169,7 +173,9 @@ pub(crate) fn wrap_body_with_checksum_validator(
     body.map(move |body: SdkBody| {
         SdkBody::from_body_1_x(validate::ChecksumBody::new(
             body,
-            checksum_algorithm.into_impl(),
+            registry
+                .resolve(&checksum_algorithm)
+                .expect("checksum algorithm was resolved when checking headers"),
             precalculated_checksum.clone(),
         ))
     })
@@ -177,14 +183,18 @@ pub(crate) fn wrap_body_with_checksum_validator(
 
 /// Given a `HeaderMap`, extract any checksum included in the headers as `Some(Bytes)`.
 /// If no checksum header is set, return `None`. If multiple checksum headers are set, the one that
-/// is fastest to compute will be chosen.
+/// is fastest to compute will be chosen. Built-in algorithms are only checked if they're one of the
+/// `response_algorithms` supported by the model. Algorithms registered in the `ChecksumRegistry`
+/// aren't part of the model, so they're always checked, after all built-in algorithms.
 pub(crate) fn check_headers_for_precalculated_checksum(
     headers: &Headers,
     response_algorithms: &[&str],
-) -> Option<(ChecksumAlgorithm, bytes::Bytes)> {
+    registry: &ChecksumRegistry,
+) -> Option<(String, bytes::Bytes)> {
     let checksum_algorithms_to_check =
-        aws_smithy_checksums::http::CHECKSUM_ALGORITHMS_IN_PRIORITY_ORDER
-            .into_iter()
+        aws_smithy_checksums::http::ALL_CHECKSUM_ALGORITHMS_IN_PRIORITY_ORDER
+            .iter()
+            .copied()
             // Process list of algorithms, from fastest to slowest, that may have been used to checksum
             // the response body, ignoring any that aren't marked as supported algorithms by the model.
             .flat_map(|algo| {
@@ -196,15 +206,14 @@ pub(crate) fn check_headers_for_precalculated_checksum(
                 }
 
                 None
-            });
+            })
+            .chain(registry.registered_names());
 
     for checksum_algorithm in checksum_algorithms_to_check {
-        let checksum_algorithm: ChecksumAlgorithm = checksum_algorithm.parse().expect(
-            "CHECKSUM_ALGORITHMS_IN_PRIORITY_ORDER only contains valid checksum algorithm names",
+        let checksum = registry.resolve(checksum_algorithm).expect(
+            "ALL_CHECKSUM_ALGORITHMS_IN_PRIORITY_ORDER and the registry only contain valid checksum algorithm names",
         );
-        if let Some(base64_encoded_precalculated_checksum) =
-            headers.get(checksum_algorithm.into_impl().header_name())
-        {
+        if let Some(base64_encoded_precalculated_checksum) = headers.get(checksum.header_name()) {
             // S3 needs special handling for checksums of objects uploaded with `MultiPartUpload`.
             if is_part_level_checksum(base64_encoded_precalculated_checksum) {
                 tracing::warn!(
@@ -225,7 +234,7 @@ pub(crate) fn check_headers_for_precalculated_checksum(
                 }
             };
 
-            return Some((checksum_algorithm, precalculated_checksum));
+            return Some((checksum_algorithm.to_owned(), precalculated_checksum));
         }
     }
 
@@ -262,7 +271,13 @@ fn is_part_level_checksum(checksum: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_part_level_checksum, wrap_body_with_checksum_validator};
+    use super::{
+        check_headers_for_precalculated_checksum, is_part_level_checksum,
+        wrap_body_with_checksum_validator,
+    };
+    use aws_smithy_checksums::registry::ChecksumRegistry;
+    use aws_smithy_checksums::test_util::ByteCount;
+    use aws_smithy_runtime_api::http::Headers;
     use aws_smithy_types::body::SdkBody;
     use aws_smithy_types::byte_stream::ByteStream;
     use aws_smithy_types::error::display::DisplayErrorContext;
@@ -270,7 +285,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_build_checksum_validated_body_works() {
-        let checksum_algorithm = "crc32".parse().unwrap();
+        let checksum_algorithm = "crc32".to_owned();
         let input_text = "Hello world";
         let precalculated_checksum = Bytes::from_static(&[0x8b, 0xd6, 0x9e, 0x52]);
         let body = ByteStream::new(SdkBody::from(input_text));
@@ -278,7 +293,8 @@ mod tests {
         let body = body.map(move |sdk_body| {
             wrap_body_with_checksum_validator(
                 sdk_body,
-                checksum_algorithm,
+                checksum_algorithm.clone(),
+                ChecksumRegistry::default(),
                 precalculated_checksum.clone(),
             )
         });
@@ -293,6 +309,66 @@ mod tests {
         assert_eq!(input_text, body);
     }
 
+    #[tokio::test]
+    async fn test_registered_checksum_is_validated() {
+        let registry = ChecksumRegistry::new()
+            .with_checksum("bytecount", || Box::<ByteCount>::default())
+            .unwrap();
+        let input_text = "Hello world";
+        let mut headers = Headers::new();
+        headers.insert("x-amz-checksum-bytecount", "AAAACw==");
+
+        // No built-in checksum header is present, so the registered algorithm is used even though
+        // the model doesn't list it
+        let (checksum_algorithm, precalculated_checksum) =
+            check_headers_for_precalculated_checksum(&headers, &["crc32"], &registry).unwrap();
+        assert_eq!("bytecount", checksum_algorithm);
+        assert_eq!(Bytes::from_static(&[0, 0, 0, 11]), precalculated_checksum);
+
+        let body = ByteStream::new(wrap_body_with_checksum_validator(
+            SdkBody::from(input_text),
+            checksum_algorithm,
+            registry.clone(),
+            precalculated_checksum,
+        ));
+        let body = body.collect().await.expect("checksum matches");
+        assert_eq!(input_text.as_bytes(), body.into_bytes());
+
+        // Without the registry, the custom checksum header is ignored
+        assert!(check_headers_for_precalculated_checksum(
+            &headers,
+            &["crc32", "bytecount"],
+            &ChecksumRegistry::default()
+        )
+        .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_registered_checksum_mismatch_fails_validation() {
+        let registry = ChecksumRegistry::new()
+            .with_checksum("bytecount", || Box::<ByteCount>::default())
+            .unwrap();
+        let mut headers = Headers::new();
+        // The body is 11 bytes long, but the header claims 12
+        headers.insert("x-amz-checksum-bytecount", "AAAADA==");
+
+        // The model only lists crc32
+        let (checksum_algorithm, precalculated_checksum) =
+            check_headers_for_precalculated_checksum(&headers, &["crc32"], &registry).unwrap();
+        let body = ByteStream::new(wrap_body_with_checksum_validator(
+            SdkBody::from("Hello world"),
+            checksum_algorithm,
+            registry,
+            precalculated_checksum,
+        ));
+        let err = body.collect().await.expect_err("checksum doesn't match");
+        assert!(
+            DisplayErrorContext(&err).to_string().contains("checksum"),
+            "{}",
+            DisplayErrorContext(&err)
+        );
+    }
+
     #[test]
     fn test_is_multipart_object_checksum() {
         // These ARE NOT part-level checksums
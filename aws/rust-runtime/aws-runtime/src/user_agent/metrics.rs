@@ -190,6 +190,8 @@ impl ProvideBusinessMetric for SmithySdkFeature {
             Waiter => Some(BusinessMetric::Waiter),
             Paginator => Some(BusinessMetric::Paginator),
             GzipRequestCompression => Some(BusinessMetric::GzipRequestCompression),
+            // There are no business metrics for non-gzip request compression.
+            ZstdRequestCompression | BrotliRequestCompression => None,
             ProtocolRpcV2Cbor => Some(BusinessMetric::ProtocolRpcV2Cbor),
             RetryModeStandard => Some(BusinessMetric::RetryModeStandard),
             RetryModeAdaptive => Some(BusinessMetric::RetryModeAdaptive),
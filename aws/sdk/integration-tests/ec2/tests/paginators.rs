@@ -5,6 +5,7 @@
 
 use aws_runtime::user_agent::test_util::assert_ua_contains_metric_values;
 use aws_sdk_ec2::{config::Credentials, config::Region, types::InstanceType, Client, Config};
+use aws_smithy_async::future::pagination_stream::cursor::PaginationCursor;
 use aws_smithy_http_client::test_util::capture_request;
 use aws_smithy_runtime_api::client::http::HttpClient;
 use aws_smithy_types::body::SdkBody;
@@ -111,3 +112,56 @@ async fn should_emit_business_metric_for_paginator_in_user_agent() {
     let user_agent = expected_req.headers().get("x-amz-user-agent").unwrap();
     assert_ua_contains_metric_values(user_agent, &["C"]);
 }
+
+#[tokio::test]
+async fn paginators_resume_from_cursor() {
+    let response = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <DescribeSpotPriceHistoryResponse xmlns="http://ec2.amazonaws.com/doc/2016-11-15/">
+            <requestId>edf3e86c-4baf-47c1-9228-9a5ea09542e8</requestId>
+            <spotPriceHistorySet/>
+            <nextToken>token-1</nextToken>
+        </DescribeSpotPriceHistoryResponse>"#;
+    let response = http_1x::Response::builder()
+        .status(200)
+        .body(SdkBody::from(response))
+        .unwrap();
+    let (http_client, _) = capture_request(Some(response));
+    let client = Client::from_conf(stub_config(http_client));
+    let mut paginator = client
+        .describe_spot_price_history()
+        .availability_zone("eu-north-1a")
+        .into_paginator()
+        .send();
+    paginator.try_next().await.expect("success");
+    let checkpoint = paginator
+        .cursor()
+        .expect("the first page was yielded")
+        .to_string();
+
+    let (http_client, captured_request) = capture_request(None);
+    let client = Client::from_conf(stub_config(http_client));
+    let mut paginator = client
+        .describe_spot_price_history()
+        .availability_zone("eu-north-1a")
+        .into_paginator()
+        .resume_from(checkpoint.parse().expect("valid cursor"))
+        .send();
+    let _ = paginator.next().await;
+    let req = captured_request.expect_request();
+    let actual_body = std::str::from_utf8(req.body().bytes().unwrap()).unwrap();
+    let expected_body = "Action=DescribeSpotPriceHistory&Version=2016-11-15&AvailabilityZone=eu-north-1a&NextToken=token-1";
+    validate_query_string(expected_body, actual_body);
+}
+
+#[tokio::test]
+async fn paginators_resumed_from_exhausted_cursor_dont_send_requests() {
+    let (http_client, captured_request) = capture_request(None);
+    let client = Client::from_conf(stub_config(http_client));
+    let mut paginator = client
+        .describe_spot_price_history()
+        .into_paginator()
+        .resume_from(PaginationCursor::done())
+        .send();
+    assert!(paginator.next().await.is_none());
+    captured_request.expect_no_request();
+}
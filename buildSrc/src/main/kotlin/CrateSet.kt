@@ -70,6 +70,7 @@ object CrateSet {
             "aws-smithy-mocks",
             "aws-smithy-observability",
             "aws-smithy-observability-otel",
+            "aws-smithy-observability-prometheus",
             "aws-smithy-protocol-test",
             "aws-smithy-query",
             "aws-smithy-runtime",
@@ -14,13 +14,15 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.getTrait
 import java.util.logging.Logger
 
-// Currently, gzip is the only supported encoding.
-fun isSupportedEncoding(encoding: String): Boolean = encoding == "gzip"
+private val supportedRequestEncodings = setOf("gzip", "zstd", "br")
 
-fun firstSupportedEncoding(encodings: List<String>): String? = encodings.firstOrNull { isSupportedEncoding(it) }
+fun isSupportedEncoding(encoding: String): Boolean = encoding in supportedRequestEncodings
+
+fun supportedEncodings(encodings: List<String>): List<String> = encodings.filter { isSupportedEncoding(it) }
 
 // This generator was implemented based on this spec:
 // https://smithy.io/2.0/spec/behavior-traits.html#requestcompression-trait
@@ -35,10 +37,12 @@ class RequestCompressionGenerator(
                 logger.warning { "No encodings were specified for the requestCompressionTrait on ${operationShape.id}" }
                 return emptySection
             }
-            val encoding = firstSupportedEncoding(requestCompressionTrait.encodings) ?: return emptySection
-            // We can remove this once we start supporting other algorithms.
-            // Until then, we shouldn't see anything else coming up here.
-            assert(encoding == "gzip") { "Only gzip is supported but encoding was `$encoding`" }
+            // The runtime plugin picks the configured algorithm if the operation supports it, and otherwise
+            // the first supported encoding, so the trait's order of preference is preserved.
+            val encodings = supportedEncodings(requestCompressionTrait.encodings)
+            if (encodings.isEmpty()) {
+                return emptySection
+            }
             val runtimeConfig = codegenContext.runtimeConfig
             val compression = RuntimeType.clientRequestCompression(runtimeConfig)
 
@@ -46,7 +50,10 @@ class RequestCompressionGenerator(
                 when (section) {
                     is OperationSection.AdditionalRuntimePlugins ->
                         section.addOperationRuntimePlugin(this) {
-                            rust("#T::new()", compression.resolve("RequestCompressionRuntimePlugin"))
+                            rust(
+                                "#T::new(&[${encodings.joinToString(", ") { it.dq() }}])",
+                                compression.resolve("RequestCompressionRuntimePlugin"),
+                            )
                         }
 
                     else -> {}
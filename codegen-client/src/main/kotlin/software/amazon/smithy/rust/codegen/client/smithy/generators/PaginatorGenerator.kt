@@ -75,6 +75,9 @@ class PaginatorGenerator private constructor(
     private val outputType = symbolProvider.toSymbol(outputShape)
     private val errorType = symbolProvider.symbolForOperationError(operation)
 
+    // Paginators can only be resumed from a cursor when their token is a string
+    private val isResumable = model.expectShape(paginationInfo.inputTokenMember.target).isStringShape
+
     private val isTruncatedPaginator =
         codegenContext.model.getShape(outputShape.toShapeId()).orNull().let { shape ->
             shape?.getTrait<SyntheticOutputTrait>()?.originalId.let { shapeId ->
@@ -105,6 +108,9 @@ class PaginatorGenerator private constructor(
                     .resolve("client::orchestrator::HttpResponse"),
             "SdkError" to RuntimeType.sdkError(runtimeConfig),
             "pagination_stream" to RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream"),
+            "PaginationCursor" to
+                RuntimeType.smithyAsync(runtimeConfig)
+                    .resolve("future::pagination_stream::cursor::PaginationCursor"),
             // External Types
             "Stream" to RuntimeType.TokioStream.resolve("Stream"),
         )
@@ -125,6 +131,7 @@ class PaginatorGenerator private constructor(
                     handle: std::sync::Arc<crate::client::Handle>,
                     builder: #{Builder},
                     stop_on_duplicate_token: bool,
+                    cursor: #{Option}<#{PaginationCursor}>,
                 }
 
                 impl $paginatorName {
@@ -134,11 +141,14 @@ class PaginatorGenerator private constructor(
                             handle,
                             builder,
                             stop_on_duplicate_token: true,
+                            cursor: #{None},
                         }
                     }
 
                     #{page_size_setter:W}
 
+                    #{resume_from_fn:W}
+
                     #{items_fn:W}
 
                     /// Stop paginating when the service returns the same pagination token twice in a row.
@@ -161,12 +171,18 @@ class PaginatorGenerator private constructor(
                         // Move individual fields out of self for the borrow checker
                         let builder = self.builder;
                         let handle = self.handle;
+                        let start = self.cursor;
+                        let exhausted = matches!(&start, #{Some}(cursor) if cursor.is_done());
                         #{runtime_plugin_init}
-                        #{pagination_stream}::PaginationStream::new(#{pagination_stream}::fn_stream::FnStream::new(move |tx| #{Box}::pin(async move {
+                        #{pagination_stream}::PaginationStream::resumable(#{pagination_stream}::fn_stream::FnStream::new(move |tx| #{Box}::pin(async move {
+                            // Resuming from the cursor of an exhausted stream yields no pages
+                            if exhausted {
+                                return;
+                            }
                             // Build the input for the first time. If required fields are missing, this is where we'll produce an early error.
                             let mut input = match builder.build().map_err(#{SdkError}::construction_failure) {
                                 #{Ok}(input) => input,
-                                #{Err}(e) => { let _ = tx.send(#{Err}(e)).await; return; }
+                                #{Err}(e) => { let _ = tx.send((#{Err}(e), #{None})).await; return; }
                             };
                             loop {
                                 let resp = #{orchestrate};
@@ -184,7 +200,8 @@ class PaginatorGenerator private constructor(
                                     },
                                     #{Err}(_) => true,
                                 };
-                                if tx.send(resp).await.is_err() {
+                                #{cursor}
+                                if tx.send((resp, cursor)).await.is_err() {
                                     // receiving end was dropped
                                     return
                                 }
@@ -192,12 +209,14 @@ class PaginatorGenerator private constructor(
                                     return
                                 }
                             }
-                        })))
+                        })), start)
                     }
                 }
                 """,
                 *codegenScope,
                 "items_fn" to itemsFn(),
+                "resume_from_fn" to resumeFromFn(),
+                "cursor" to cursor(),
                 "output_token" to outputTokenLens,
                 "item_type" to
                     writable {
@@ -232,6 +251,53 @@ class PaginatorGenerator private constructor(
             )
         }
 
+    /** Generate code to calculate the cursor to resume the stream after the current page. Errors don't
+     * move the cursor, so that resuming the stream retries the page that failed.
+     */
+    private fun cursor() =
+        writable {
+            if (isResumable) {
+                val inputTokenMember = symbolProvider.toMemberName(paginationInfo.inputTokenMember)
+                rustTemplate(
+                    """
+                    let cursor = match resp {
+                        #{Ok}(_) if done => #{Some}(#{PaginationCursor}::done()),
+                        #{Ok}(_) => input.$inputTokenMember.as_deref().map(#{PaginationCursor}::new),
+                        #{Err}(_) => #{None},
+                    };
+                    """,
+                    *codegenScope,
+                )
+            } else {
+                rustTemplate("let cursor = #{None};", *codegenScope)
+            }
+        }
+
+    /** Generate a `resume_from()` function to resume pagination from a cursor, when the token is a string */
+    private fun resumeFromFn() =
+        writable {
+            if (isResumable) {
+                val inputTokenMember = symbolProvider.toMemberName(paginationInfo.inputTokenMember)
+                rustTemplate(
+                    """
+                    /// Resume paginating from `cursor`
+                    ///
+                    /// The cursor is obtained from the [`cursor()`](#{pagination_stream}::PaginationStream::cursor) of the
+                    /// pagination stream of a paginator for the same input, possibly persisted in between with its string
+                    /// representation. This allows long-running scans to checkpoint their progress.
+                    ///
+                    /// _Note: this method will override any previously set value for `$inputTokenMember`_
+                    pub fn resume_from(mut self, cursor: #{PaginationCursor}) -> Self {
+                        self.builder.$inputTokenMember = cursor.token().map(|token| token.to_owned());
+                        self.cursor = #{Some}(cursor);
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+
     /** Generate code to calculate the value of is_empty. For most paginators this
      * is indicated by the next token being the empty string. But for paginators
      * with the isTruncatedPaginator trait the next token is not necessarily empty.
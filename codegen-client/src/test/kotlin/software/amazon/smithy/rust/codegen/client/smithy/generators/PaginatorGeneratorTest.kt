@@ -86,6 +86,26 @@ internal class PaginatorGeneratorTest {
         }
     }
 
+    @Test
+    fun `paginators can be resumed from a cursor`() {
+        clientIntegrationTest(model) { clientCodegenContext, rustCrate ->
+            rustCrate.integrationTest("paginators_resume_from_cursor") {
+                rust(
+                    """
+                    use ${clientCodegenContext.moduleUseName()}::operation::paginated_list::paginator::PaginatedListPaginator;
+
+                    ##[allow(dead_code)]
+                    async fn resume(paginator: PaginatedListPaginator, checkpoint: &str) -> Option<String> {
+                        let mut stream = paginator.resume_from(checkpoint.parse().unwrap()).items().send();
+                        let _ = stream.next().await;
+                        stream.cursor().map(|cursor| cursor.to_string())
+                    }
+                    """,
+                )
+            }
+        }
+    }
+
     // Regression: when a @paginated operation's top-level outputToken targets a @required member,
     // the borrowing lens accessor in src/lens.rs previously moved the owned value instead of
     // borrowing it, producing Option<String> where Option<&String> is expected.
@@ -137,7 +137,10 @@ class InlineDependency(
                 CargoDependency.Tracing,
                 CargoDependency.Flate2,
                 CargoDependency.Tokio.toDevDependency(),
-                CargoDependency.smithyCompression(runtimeConfig),
+                CargoDependency.smithyCompression(runtimeConfig)
+                    .withFeature("brotli")
+                    .withFeature("client")
+                    .withFeature("zstd"),
                 CargoDependency.smithyRuntimeApiClient(runtimeConfig),
                 CargoDependency.smithyTypes(runtimeConfig)
                     .withFeature("http-body-0-4-x")
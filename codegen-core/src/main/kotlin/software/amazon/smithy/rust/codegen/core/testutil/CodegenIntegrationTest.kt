@@ -137,6 +137,11 @@ class ServerAdditionalSettings private constructor(settings: List<AdditionalSett
                 return this
             }
 
+            fun requestBodyMaxBytesPerOperation(maxBytes: Map<String, Long>): Builder {
+                settings.add(RequestBodyMaxBytesPerOperation(maxBytes))
+                return this
+            }
+
             fun allowMissingUnionVariant(enabled: Boolean = true): Builder {
                 settings.add(AllowMissingUnionVariant(enabled))
                 return this
@@ -185,6 +190,18 @@ class ServerAdditionalSettings private constructor(settings: List<AdditionalSett
                     .build()
         }
 
+        private data class RequestBodyMaxBytesPerOperation(val maxBytes: Map<String, Long>) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember(
+                        "requestBodyMaxBytesPerOperation",
+                        ObjectNode.builder().apply {
+                            maxBytes.forEach { (operation, bytes) -> withMember(operation, bytes) }
+                        }.build(),
+                    )
+                    .build()
+        }
+
         private data class AllowMissingUnionVariant(val enabled: Boolean) : AdditionalSettings() {
             override fun toObjectNode(): ObjectNode =
                 ObjectNode.builder()
@@ -17,6 +17,7 @@ import software.amazon.smithy.model.shapes.UnionShape
 import software.amazon.smithy.model.traits.EnumTrait
 import software.amazon.smithy.model.traits.ErrorTrait
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenTarget
+import software.amazon.smithy.rust.codegen.core.smithy.HttpVersion
 import software.amazon.smithy.rust.codegen.core.smithy.RustCrate
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProviderConfig
 import software.amazon.smithy.rust.codegen.core.smithy.generators.error.ErrorImplGenerator
@@ -49,6 +50,14 @@ class TsServerCodegenVisitor(
     private val codegenDecorator: ServerCodegenDecorator,
 ) : ServerCodegenVisitor(context, codegenDecorator) {
     init {
+        // Typescript server bindings are built on top of the legacy HTTP 0.x server.
+        // Force HTTP 0.x regardless of configuration settings.
+        settings =
+            settings.copy(
+                runtimeConfig = settings.runtimeConfig.copy(httpVersion = HttpVersion.Http0x),
+                codegenConfig = settings.codegenConfig.copy(http1x = false),
+            )
+
         val symbolVisitorConfig =
             RustSymbolProviderConfig(
                 runtimeConfig = settings.runtimeConfig,
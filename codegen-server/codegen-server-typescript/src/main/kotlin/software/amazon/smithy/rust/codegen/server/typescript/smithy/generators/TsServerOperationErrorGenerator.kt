@@ -8,14 +8,28 @@ package software.amazon.smithy.rust.codegen.server.typescript.smithy.generators
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.knowledge.OperationIndex
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.rust.codegen.core.rustlang.RustType
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.stripOuter
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RustSymbolProvider
+import software.amazon.smithy.rust.codegen.core.smithy.isOptional
+import software.amazon.smithy.rust.codegen.core.smithy.rustType
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.errorMessageMember
 import software.amazon.smithy.rust.codegen.server.typescript.smithy.TsServerCargoDependency
 
 /**
  * Generates a unified error enum for [operation] and adds the Rust implementation for `napi` error.
+ *
+ * The reason of the `napi` error of a Typescript handler is `String(error)` of the thrown error, which is
+ * `"${error.name}: ${error.message}"` for Javascript errors. The error name is matched against the names of the errors
+ * of the operation, and the matching error is built with the error message. Errors whose other members are required
+ * can't be built from the message alone, and, like unknown errors, are converted into an `InternalServerError`.
  */
 class TsServerOperationErrorGenerator(
     private val model: Model,
@@ -29,13 +43,62 @@ class TsServerOperationErrorGenerator(
         renderFromTsErr(writer)
     }
 
-    // TODO(https://github.com/smithy-lang/smithy-rs/issues/2317): match the Ts error type and return the right one.
+    /** Returns the members of [error] initialized from the `message` variable, or `null` if it can't be built. */
+    private fun errorFields(error: StructureShape): String? {
+        val messageMember = error.errorMessageMember()
+        val fields =
+            error.members().map { member ->
+                val symbol = symbolProvider.toSymbol(member)
+                val value =
+                    when {
+                        member == messageMember && symbol.rustType().stripOuter<RustType.Option>() == RustType.String ->
+                            if (symbol.isOptional()) "Some(message.to_owned())" else "message.to_owned()"
+                        symbol.isOptional() -> "None"
+                        else -> return null
+                    }
+                "${symbolProvider.toMemberName(member)}: $value"
+            }
+        return fields.joinToString(", ")
+    }
+
     private fun renderFromTsErr(writer: RustWriter) {
+        val matchableErrors = errors.mapNotNull { error -> errorFields(error)?.let { error to it } }
+        val conversion =
+            writable {
+                if (matchableErrors.isEmpty()) {
+                    rust("crate::error::InternalServerError { message: variant.to_string() }.into()")
+                    return@writable
+                }
+                val usesMessage = matchableErrors.any { (_, fields) -> fields.contains("message.to_owned()") }
+                val message = if (usesMessage) "message" else "_message"
+                rust(
+                    """
+                    let (name, $message) = variant.reason.split_once(": ").unwrap_or((variant.reason.as_str(), ""));
+                    """,
+                )
+                rustTemplate(
+                    """
+                    match name {
+                        #{Arms:W}
+                        _ => crate::error::InternalServerError { message: variant.to_string() }.into(),
+                    }
+                    """,
+                    "Arms" to
+                        writable {
+                            for ((error, fields) in matchableErrors) {
+                                rustTemplate(
+                                    "${error.id.name.dq()} => #{Error} { $fields }.into(),",
+                                    "Error" to symbolProvider.toSymbol(error),
+                                )
+                            }
+                        },
+                )
+            }
         writer.rustTemplate(
             """
             impl #{From}<#{napi}::Error> for #{Error} {
                 fn from(variant: #{napi}::Error) -> #{Error} {
-                    crate::error::InternalServerError { message: variant.to_string() }.into()
+                    #{Conversion:W}
                 }
             }
 
@@ -43,6 +106,7 @@ class TsServerOperationErrorGenerator(
             "napi" to TsServerCargoDependency.Napi.toType(),
             "Error" to symbolProvider.symbolForOperationError(operation),
             "From" to RuntimeType.From,
+            "Conversion" to conversion,
         )
     }
 }
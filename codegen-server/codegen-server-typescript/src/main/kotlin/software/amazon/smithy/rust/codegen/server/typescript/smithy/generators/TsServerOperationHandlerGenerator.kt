@@ -28,7 +28,6 @@ class TsServerOperationHandlerGenerator(
         arrayOf(
             "SmithyTs" to TsServerCargoDependency.smithyHttpServerTs(runtimeConfig).toType(),
             "SmithyServer" to TsServerCargoDependency.smithyHttpServer(runtimeConfig).toType(),
-            "napi" to TsServerCargoDependency.Napi.toType(),
         )
 
     fun render(writer: RustWriter) {
@@ -48,8 +47,9 @@ class TsServerOperationHandlerGenerator(
             pub(crate) async fn $fnName(
                 input: $input,
                 handlers: #{SmithyServer}::Extension<crate::ts_server_application::Handlers>,
+                context: #{SmithyServer}::Extension<#{SmithyTs}::TsContext>,
             ) -> std::result::Result<$output, $error> {
-                handlers.$fnName.call_async::<#{napi}::bindgen_prelude::Promise<$output>>(input).await?.await.map_err(|e| e.into())
+                handlers.$fnName.call(input, context.0).await.map_err(|e| e.into())
             }
             """,
             *codegenScope,
@@ -0,0 +1,43 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.typescript.smithy.testutil
+
+import software.amazon.smithy.build.PluginContext
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeCrateLocation
+import software.amazon.smithy.rust.codegen.core.testutil.generatePluginContext
+import software.amazon.smithy.rust.codegen.core.util.runCommand
+import software.amazon.smithy.rust.codegen.server.smithy.customizations.CustomValidationExceptionWithReasonDecorator
+import software.amazon.smithy.rust.codegen.server.smithy.customizations.ServerRequiredCustomizations
+import software.amazon.smithy.rust.codegen.server.smithy.customizations.SmithyValidationExceptionDecorator
+import software.amazon.smithy.rust.codegen.server.smithy.customize.CombinedServerCodegenDecorator
+import software.amazon.smithy.rust.codegen.server.typescript.smithy.TsServerCodegenVisitor
+import software.amazon.smithy.rust.codegen.server.typescript.smithy.customizations.DECORATORS
+import java.io.File
+import java.nio.file.Path
+
+val TestRuntimeConfig =
+    RuntimeConfig(runtimeCrateLocation = RuntimeCrateLocation.path(File("../../rust-runtime").absolutePath))
+
+fun generateTsServerPluginContext(model: Model) = generatePluginContext(model, runtimeConfig = TestRuntimeConfig)
+
+fun executeTsServerCodegenVisitor(pluginCtx: PluginContext) {
+    val codegenDecorator =
+        CombinedServerCodegenDecorator.fromClasspath(
+            pluginCtx,
+            ServerRequiredCustomizations(),
+            SmithyValidationExceptionDecorator(),
+            CustomValidationExceptionWithReasonDecorator(),
+            *DECORATORS,
+        )
+    TsServerCodegenVisitor(pluginCtx, codegenDecorator).execute()
+}
+
+fun cargoTest(workdir: Path) =
+    // Without a Node.js process to load the crate into, `napi` has to load the Node-API symbols at runtime rather than
+    // have them resolved by the linker.
+    "cargo test --features napi/dyn-symbols --no-fail-fast".runCommand(workdir)
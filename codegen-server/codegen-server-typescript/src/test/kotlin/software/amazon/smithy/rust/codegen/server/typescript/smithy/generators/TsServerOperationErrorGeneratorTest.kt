@@ -0,0 +1,101 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.typescript.smithy.generators
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+import software.amazon.smithy.rust.codegen.server.typescript.smithy.testutil.cargoTest
+import software.amazon.smithy.rust.codegen.server.typescript.smithy.testutil.executeTsServerCodegenVisitor
+import software.amazon.smithy.rust.codegen.server.typescript.smithy.testutil.generateTsServerPluginContext
+import kotlin.io.path.appendText
+
+internal class TsServerOperationErrorGeneratorTest {
+    @Test
+    fun `errors thrown by handlers are converted into the modeled errors`() {
+        val model =
+            """
+            namespace test
+
+            use aws.protocols#restJson1
+
+            @restJson1
+            service Service {
+                operations: [
+                    GetItem,
+                ],
+            }
+
+            @http(method: "GET", uri: "/items/{id}")
+            operation GetItem {
+                input: GetItemInput,
+                output: GetItemOutput,
+                errors: [ItemNotFound, ItemLocked],
+            }
+
+            structure GetItemInput {
+                @required
+                @httpLabel
+                id: String,
+            }
+
+            structure GetItemOutput {}
+
+            @error("client")
+            @httpError(404)
+            structure ItemNotFound {
+                @required
+                message: String,
+            }
+
+            @error("client")
+            @httpError(409)
+            structure ItemLocked {
+                message: String,
+                @required
+                lockedBy: String,
+            }
+            """.asSmithyModel()
+
+        val (pluginCtx, testDir) = generateTsServerPluginContext(model)
+        executeTsServerCodegenVisitor(pluginCtx)
+
+        val writer = RustWriter.forModule("error")
+        writer.unitTest("errors_thrown_by_handlers_are_converted_into_the_modeled_errors") {
+            rust(
+                """
+                use aws_smithy_legacy_http_server::protocol::rest_json_1::RestJson1;
+                use aws_smithy_legacy_http_server::response::IntoResponse;
+
+                // What a handler throwing `Object.assign(new Error("item 1 doesn't exist"), { name: "ItemNotFound" })`
+                // is converted into.
+                let error = GetItemError::from(napi::Error::from_reason("ItemNotFound: item 1 doesn't exist"));
+                match &error {
+                    GetItemError::ItemNotFound(error) => assert_eq!("item 1 doesn't exist", error.message),
+                    other => panic!("expected `ItemNotFound`, got {other:?}"),
+                }
+                let response = IntoResponse::<RestJson1>::into_response(error);
+                assert_eq!(404, response.status().as_u16());
+                assert_eq!("ItemNotFound", response.headers()["x-amzn-errortype"]);
+
+                // `ItemLocked` can't be built from the message alone, and unknown errors aren't modeled.
+                for reason in ["ItemLocked: item 1 is locked", "TypeError: handler is not a function"] {
+                    let error = GetItemError::from(napi::Error::from_reason(reason));
+                    assert!(matches!(error, GetItemError::InternalServerError(_)), "{error:?}");
+                    let response = IntoResponse::<RestJson1>::into_response(error);
+                    assert_eq!(500, response.status().as_u16());
+                }
+                """,
+            )
+        }
+
+        testDir.resolve("src/error.rs").appendText(writer.toString())
+
+        cargoTest(testDir)
+    }
+}
@@ -237,6 +237,7 @@ open class ServerCodegenVisitor(
         logger.warning(
             "[rust-server-codegen] Generating Rust server for service $service, protocol ${codegenContext.protocol}",
         )
+        codegenContext.settings.codegenConfig.validateRequestBodyMaxBytesPerOperation(model, service)
 
         val validationExceptionShapeId = validationExceptionConversionGenerator.shapeId
         for (validationResult in listOf(
@@ -5,8 +5,11 @@
 
 package software.amazon.smithy.rust.codegen.server.smithy
 
+import software.amazon.smithy.codegen.core.CodegenException
 import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.knowledge.TopDownIndex
 import software.amazon.smithy.model.node.ObjectNode
+import software.amazon.smithy.model.shapes.ServiceShape
 import software.amazon.smithy.model.shapes.ShapeId
 import software.amazon.smithy.rust.codegen.core.smithy.CODEGEN_SETTINGS
 import software.amazon.smithy.rust.codegen.core.smithy.CoreCodegenConfig
@@ -100,6 +103,8 @@ data class ServerRustSettings(
  *   request body. Set to `0` to disable the limit (the historical behavior; not recommended, as
  *   it allows memory exhaustion via `Transfer-Encoding: chunked` or very large `Content-Length`
  *   values). Default is `0` (no limit) for backwards compatibility.
+ * [requestBodyMaxBytesPerOperation]: Overrides [requestBodyMaxBytes] for the operations it lists, keyed by their absolute
+ *   shape ID (e.g. `com.example#UploadFile`). Set an operation's value to `0` to disable the limit for it.
  * [rpcV2CborAddCapitalizedRoute]: When false (default), the RPCv2 CBOR server router registers
  *   only the spec-compliant verbatim route derived from the Smithy operation shape name
  *   (e.g., `Example.getFoo`). When true, an additional legacy alias with the first character
@@ -131,6 +136,7 @@ data class ServerCodegenConfig(
     val alwaysSendEventStreamInitialResponse: Boolean = DEFAULT_SEND_EVENT_STREAM_INITIAL_RESPONSE,
     val http1x: Boolean = DEFAULT_HTTP_1X,
     val requestBodyMaxBytes: Long = DEFAULT_REQUEST_BODY_MAX_BYTES,
+    val requestBodyMaxBytesPerOperation: Map<ShapeId, Long> = emptyMap(),
     /**
      * When true, a union JSON body whose object did not set any recognized variant
      * (e.g. `{}` or `{"unknownKey": ...}`) parses to `Ok(None)` rather than returning a
@@ -142,6 +148,28 @@ data class ServerCodegenConfig(
 ) : CoreCodegenConfig(
         formatTimeoutSeconds, debugMode,
     ) {
+    /** Returns the maximum number of bytes to buffer when deserializing a request body of [operation]. */
+    fun requestBodyMaxBytes(operation: ShapeId): Long = requestBodyMaxBytesPerOperation[operation] ?: requestBodyMaxBytes
+
+    /**
+     * Throws if [requestBodyMaxBytesPerOperation] lists shapes that aren't operations of [service], so that a mistyped
+     * operation isn't silently left with the default limit.
+     */
+    fun validateRequestBodyMaxBytesPerOperation(
+        model: Model,
+        service: ServiceShape,
+    ) {
+        val operations = TopDownIndex.of(model).getContainedOperations(service).map { it.id }.toSet()
+        val unknownOperations = requestBodyMaxBytesPerOperation.keys.filterNot { it in operations }.sorted()
+        if (unknownOperations.isNotEmpty()) {
+            throw CodegenException(
+                "`$REQUEST_BODY_MAX_BYTES_PER_OPERATION_CONFIG_KEY` lists shapes that aren't operations of service " +
+                    "`${service.id}`: ${unknownOperations.joinToString { "`$it`" }}. Its keys must be the absolute " +
+                    "shape IDs of operations of the service, e.g. `${operations.minOrNull() ?: "com.example#UploadFile"}`.",
+            )
+        }
+    }
+
     companion object {
         private const val DEFAULT_PUBLIC_CONSTRAINED_TYPES = true
         private const val DEFAULT_IGNORE_UNSUPPORTED_CONSTRAINTS = false
@@ -188,6 +216,9 @@ data class ServerCodegenConfig(
         /** Configuration key for the per-request body size limit. */
         const val REQUEST_BODY_MAX_BYTES_CONFIG_KEY = "requestBodyMaxBytes"
 
+        /** Configuration key for the per-operation overrides of the per-request body size limit. */
+        const val REQUEST_BODY_MAX_BYTES_PER_OPERATION_CONFIG_KEY = "requestBodyMaxBytesPerOperation"
+
         /** Configuration key for the RPCv2 CBOR opt-in flag that adds a legacy capitalized route alias. */
         const val RPC_V2_CBOR_ADD_CAPITALIZED_ROUTE_CONFIG_KEY = "rpcV2CborAddCapitalizedRoute"
 
@@ -203,6 +234,7 @@ data class ServerCodegenConfig(
                 "allowMissingUnionVariant",
                 HTTP_1X_CONFIG_KEY,
                 REQUEST_BODY_MAX_BYTES_CONFIG_KEY,
+                REQUEST_BODY_MAX_BYTES_PER_OPERATION_CONFIG_KEY,
                 RPC_V2_CBOR_ADD_CAPITALIZED_ROUTE_CONFIG_KEY,
             )
 
@@ -261,6 +293,12 @@ data class ServerCodegenConfig(
                         REQUEST_BODY_MAX_BYTES_CONFIG_KEY,
                         DEFAULT_REQUEST_BODY_MAX_BYTES,
                     ).toLong(),
+                requestBodyMaxBytesPerOperation =
+                    node.get().getObjectMember(REQUEST_BODY_MAX_BYTES_PER_OPERATION_CONFIG_KEY).map { operations ->
+                        operations.members.entries.associate { (operation, maxBytes) ->
+                            ShapeId.from(operation.value) to maxBytes.expectNumberNode().value.toLong()
+                        }
+                    }.orElse(emptyMap()),
                 rpcV2CborAddCapitalizedRoute =
                     node.get().getBooleanMemberOrDefault(
                         RPC_V2_CBOR_ADD_CAPITALIZED_ROUTE_CONFIG_KEY,
@@ -270,6 +308,11 @@ data class ServerCodegenConfig(
                 require(it.requestBodyMaxBytes >= 0) {
                     "`$REQUEST_BODY_MAX_BYTES_CONFIG_KEY` must be non-negative, got ${it.requestBodyMaxBytes}"
                 }
+                it.requestBodyMaxBytesPerOperation.forEach { (operation, maxBytes) ->
+                    require(maxBytes >= 0) {
+                        "`$REQUEST_BODY_MAX_BYTES_PER_OPERATION_CONFIG_KEY` must be non-negative, got $maxBytes for `$operation`"
+                    }
+                }
             }
         } else {
             ServerCodegenConfig(
@@ -6,6 +6,8 @@
 package software.amazon.smithy.rust.codegen.server.smithy.generators
 
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.traits.HttpHeaderTrait
+import software.amazon.smithy.model.traits.HttpTrait
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.documentShape
@@ -13,7 +15,10 @@ import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.CodegenContext
+import software.amazon.smithy.rust.codegen.core.smithy.HttpVersion
 import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.getTrait
+import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.toPascalCase
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 
@@ -29,6 +34,7 @@ class ServerOperationGenerator(
         )
     private val symbolProvider = codegenContext.symbolProvider
     private val model = codegenContext.model
+    private val serviceShape = codegenContext.serviceShape
 
     private val operationName = symbolProvider.toSymbol(operation).name.toPascalCase()
     private val operationId = operation.id
@@ -44,6 +50,37 @@ class ServerOperationGenerator(
             }
         }
 
+    /**
+     * Returns the lowercase names of the headers bound with `@httpHeader` in the output and errors of the operation,
+     * including the errors bound to the service.
+     * Headers are only bound by the HTTP binding protocols, which require the operation to have the `@http` trait.
+     */
+    private fun exposedHeaders(): List<String> {
+        if (!operation.hasTrait<HttpTrait>()) {
+            return emptyList()
+        }
+        return (listOf(operation.outputShape) + operation.getErrors(serviceShape))
+            .flatMap { model.expectShape(it).members() }
+            .mapNotNull { it.getTrait<HttpHeaderTrait>()?.value?.lowercase() }
+            .distinct()
+            .sorted()
+    }
+
+    /** Only the HTTP 1.x server runtime crate provides the `cors` module. */
+    private fun corsOperation(): Writable =
+        writable {
+            if (runtimeConfig.httpVersion == HttpVersion.Http1x) {
+                rustTemplate(
+                    """
+                    impl #{SmithyHttpServer}::cors::CorsOperation for $operationName {
+                        const EXPOSED_HEADERS: &'static [&'static str] = &[${exposedHeaders().joinToString { it.dq() }}];
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
+
     fun render(writer: RustWriter) {
         writer.documentShape(operation, model)
 
@@ -64,6 +101,8 @@ class ServerOperationGenerator(
                 type Error = #{Error:W};
             }
 
+            #{CorsOperation:W}
+
             impl #{SmithyHttpServer}::instrumentation::sensitivity::Sensitivity for $operationName {
                 type RequestFmt = #{RequestType:W};
                 type ResponseFmt = #{ResponseType:W};
@@ -78,6 +117,7 @@ class ServerOperationGenerator(
             }
             """,
             "Error" to operationError(),
+            "CorsOperation" to corsOperation(),
             "RequestValue" to requestFmt.value,
             "RequestType" to requestFmt.type,
             "ResponseValue" to responseFmt.value,
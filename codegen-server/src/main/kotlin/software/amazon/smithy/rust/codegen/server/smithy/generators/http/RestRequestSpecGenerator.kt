@@ -10,9 +10,12 @@ import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.withBlock
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.HttpVersion
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpBindingResolver
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 
 /**
  * [RestRequestSpecGenerator] generates a restJson1 or restXml specific `RequestSpec`. Both protocols are routed the same.
@@ -71,6 +74,19 @@ class RestRequestSpecGenerator(
                 }
             }
 
+        // The operation is used to answer CORS preflight requests, which only the HTTP 1.x server runtime crate supports.
+        val withOperation =
+            writable {
+                if (runtimeConfig.httpVersion == HttpVersion.Http1x) {
+                    val operationId = operationShape.id
+                    val operationIdAbsolute = operationId.toString().replace("#", "##")
+                    rustTemplate(
+                        ".with_operation(#{ShapeId}::new(${operationIdAbsolute.dq()}, ${operationId.namespace.dq()}, ${operationId.name.dq()}))",
+                        "ShapeId" to ServerCargoDependency.smithyHttpServer(runtimeConfig).toType().resolve("shape_id::ShapeId"),
+                    )
+                }
+            }
+
         return writable {
             rustTemplate(
                 """
@@ -82,9 +98,10 @@ class RestRequestSpecGenerator(
                             #{QuerySpec}::from_vector_unchecked(#{QuerySegmentsVec:W})
                         )
                     ),
-                )
+                )#{WithOperation:W}
                 """,
                 *extraCodegenScope,
+                "WithOperation" to withOperation,
                 "PathSegmentsVec" to pathSegmentsVec,
                 "QuerySegmentsVec" to querySegmentsVec,
                 "Method" to RuntimeType.http(runtimeConfig).resolve("Method"),
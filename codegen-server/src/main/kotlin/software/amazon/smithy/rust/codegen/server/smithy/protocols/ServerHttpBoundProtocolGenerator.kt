@@ -804,22 +804,21 @@ class ServerHttpBoundProtocolTraitImplGenerator(
             // attacks in which a client streams an unbounded `Transfer-Encoding: chunked` body.
             //
             // If the limit is exceeded the helper returns a `BodyLimitExceeded` error that bubbles
-            // up as `RequestRejection::BufferHttpBodyBytes` and surfaces to the client as
-            // `400 Bad Request` (plus the server drops the connection).
+            // up as `RequestRejection::RequestBodyTooLarge` and surfaces to the client as
+            // `413 Payload Too Large` in every protocol (plus the server drops the connection).
             //
             // Services that legitimately need to accept larger payloads can raise the limit via
-            // the `requestBodyMaxBytes` codegen setting. Setting it to `0` disables the check
-            // entirely (not recommended).
-            val requestBodyMaxBytes = codegenContext.settings.codegenConfig.requestBodyMaxBytes
+            // the `requestBodyMaxBytes` codegen setting, or for some operations only via the
+            // `requestBodyMaxBytesPerOperation` codegen setting. Setting it to `0` disables the
+            // check entirely (not recommended).
+            val requestBodyMaxBytes = codegenContext.settings.codegenConfig.requestBodyMaxBytes(operationShape.id)
             if (requestBodyMaxBytes > 0L) {
                 rustTemplate(
                     """
                     let bytes = match #{SmithyHttpServer}::body::collect_body_limited(body, ${requestBodyMaxBytes}usize).await {
                         #{Ok}(bytes) => bytes,
                         #{Err}(#{SmithyHttpServer}::body::CollectBodyError::Body(err)) => return #{Err}(#{RequestRejection}::from(err)),
-                        #{Err}(#{SmithyHttpServer}::body::CollectBodyError::TooLarge(err)) => {
-                            return #{Err}(#{RequestRejection}::BufferHttpBodyBytes(#{SmithyHttpServer}::error::Error::new(err)));
-                        }
+                        #{Err}(#{SmithyHttpServer}::body::CollectBodyError::TooLarge(err)) => return #{Err}(#{RequestRejection}::RequestBodyTooLarge(err)),
                     };
                     """,
                     *codegenScope,
@@ -1072,7 +1071,8 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                         // Generate body collection code. See the long comment in
                         // `serverRenderShapeParser` for why we wrap the body in a size-limited
                         // reader (same reasoning applies here).
-                        val payloadRequestBodyMaxBytes = codegenContext.settings.codegenConfig.requestBodyMaxBytes
+                        val payloadRequestBodyMaxBytes =
+                            codegenContext.settings.codegenConfig.requestBodyMaxBytes(operationShape.id)
                         if (payloadRequestBodyMaxBytes > 0L) {
                             rustTemplate(
                                 """
@@ -1080,9 +1080,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                                     let bytes = match #{SmithyHttpServer}::body::collect_body_limited(body, ${payloadRequestBodyMaxBytes}usize).await {
                                         #{Ok}(bytes) => bytes,
                                         #{Err}(#{SmithyHttpServer}::body::CollectBodyError::Body(err)) => return #{Err}(#{RequestRejection}::from(err)),
-                                        #{Err}(#{SmithyHttpServer}::body::CollectBodyError::TooLarge(err)) => {
-                                            return #{Err}(#{RequestRejection}::BufferHttpBodyBytes(#{SmithyHttpServer}::error::Error::new(err)));
-                                        }
+                                        #{Err}(#{SmithyHttpServer}::body::CollectBodyError::TooLarge(err)) => return #{Err}(#{RequestRejection}::RequestBodyTooLarge(err)),
                                     };
                                     #{VerifyRequestContentTypeHeader:W}
                                     #{Deserializer}(&bytes)?
@@ -5,7 +5,15 @@
 
 package software.amazon.smithy.rust.codegen.server.smithy
 
+import io.kotest.matchers.string.shouldContain
 import org.junit.jupiter.api.Test
+import org.junit.jupiter.api.assertThrows
+import org.junit.jupiter.api.extension.ExtensionContext
+import org.junit.jupiter.params.ParameterizedTest
+import org.junit.jupiter.params.provider.Arguments
+import org.junit.jupiter.params.provider.ArgumentsProvider
+import org.junit.jupiter.params.provider.ArgumentsSource
+import software.amazon.smithy.codegen.core.CodegenException
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
@@ -15,6 +23,7 @@ import software.amazon.smithy.rust.codegen.core.testutil.testModule
 import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.server.smithy.testutil.ServerHttpTestHelpers
 import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+import java.util.stream.Stream
 
 internal class RequestBodyMaxBytesTest {
     private val model =
@@ -132,7 +141,9 @@ internal class RequestBodyMaxBytesTest {
                             .body(#{Body})
                             .unwrap();
                         let result = EchoInput::from_request(request).await;
-                        result.expect_err("request exceeding limit should be rejected");
+                        let rejection = result.expect_err("request exceeding limit should be rejected");
+                        assert_eq!(413, rejection.status_code().as_u16());
+                        assert_eq!("PayloadTooLargeException", rejection.name());
                         """,
                         "SmithyHttpServer" to
                             ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
@@ -170,7 +181,8 @@ internal class RequestBodyMaxBytesTest {
                             .body(#{Body})
                             .unwrap();
                         let result = UploadInput::from_request(request).await;
-                        result.expect_err("httpPayload request exceeding limit should be rejected");
+                        let rejection = result.expect_err("httpPayload request exceeding limit should be rejected");
+                        assert_eq!(413, rejection.status_code().as_u16());
                         """,
                         "SmithyHttpServer" to
                             ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
@@ -181,4 +193,241 @@ internal class RequestBodyMaxBytesTest {
             }
         }
     }
+
+    @Test
+    fun `per-operation body size limit overrides the global limit`() {
+        serverIntegrationTest(
+            model,
+            IntegrationTestParams(
+                additionalSettings =
+                    ServerAdditionalSettings.builder()
+                        .requestBodyMaxBytes(16)
+                        .requestBodyMaxBytesPerOperation(mapOf("test#Echo" to 1024L))
+                        .toObjectNode(),
+            ),
+        ) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("per_operation_limit_overrides_global_limit") {
+                    rustTemplate(
+                        """
+                        use crate::input::EchoInput;
+                        use #{SmithyHttpServer}::request::FromRequest;
+
+                        let body_bytes = b"{\"message\": \"this body is definitely longer than sixteen bytes\"}".to_vec();
+                        let request = #{Http}::Request::builder()
+                            .uri("/echo")
+                            .method("POST")
+                            .header("Content-Type", "application/json")
+                            .body(#{Body})
+                            .unwrap();
+                        let result = EchoInput::from_request(request).await;
+                        result.expect("request within the operation's limit should succeed");
+                        """,
+                        "SmithyHttpServer" to
+                            ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
+                        "Http" to RuntimeType.http(codegenContext.runtimeConfig),
+                        "Body" to ServerHttpTestHelpers.createBodyFromBytes(codegenContext, "body_bytes"),
+                    )
+                }
+            }
+        }
+    }
+
+    @Test
+    fun `per-operation body size limit for an unknown operation is rejected`() {
+        val exception =
+            assertThrows<CodegenException> {
+                serverIntegrationTest(
+                    model,
+                    IntegrationTestParams(
+                        additionalSettings =
+                            ServerAdditionalSettings.builder()
+                                .requestBodyMaxBytesPerOperation(mapOf("test#Ecoh" to 1024L))
+                                .toObjectNode(),
+                    ),
+                )
+            }
+        exception.message shouldContain "`test#Ecoh`"
+        exception.message shouldContain "`test#TestService`"
+    }
+
+    /**
+     * A protocol whose requests to the `Echo` and `Ping` operations of [protocolModel] are built by [request], with a
+     * body setting `message` to [LONG_MESSAGE] built by [body].
+     */
+    data class ProtocolTestCase(
+        val protocol: String,
+        val httpTraits: Map<String, String>,
+        val request: (operation: String) -> String,
+        val body: (operation: String) -> String,
+    ) {
+        override fun toString() = protocol
+    }
+
+    class ProtocolTestCaseProvider : ArgumentsProvider {
+        private val testCases =
+            listOf(
+                ProtocolTestCase(
+                    "aws.protocols#awsJson1_0",
+                    emptyMap(),
+                    { operation ->
+                        """
+                        .uri("/")
+                        .method("POST")
+                        .header("Content-Type", "application/x-amz-json-1.0")
+                        .header("X-Amz-Target", "TestService.$operation")
+                        """
+                    },
+                    { _ -> """b"{\"message\": \"$LONG_MESSAGE\"}".to_vec()""" },
+                ),
+                ProtocolTestCase(
+                    "aws.protocols#restXml",
+                    mapOf(
+                        "Echo" to """@http(uri: "/echo", method: "POST")""",
+                        "Ping" to """@http(uri: "/ping", method: "POST")""",
+                    ),
+                    { operation ->
+                        """
+                        .uri("/${operation.lowercase()}")
+                        .method("POST")
+                        .header("Content-Type", "application/xml")
+                        """
+                    },
+                    { operation ->
+                        """b"<${operation}Input><message>$LONG_MESSAGE</message></${operation}Input>".to_vec()"""
+                    },
+                ),
+                ProtocolTestCase(
+                    "smithy.protocols#rpcv2Cbor",
+                    emptyMap(),
+                    { operation ->
+                        """
+                        .uri("/service/TestService/operation/$operation")
+                        .method("POST")
+                        .header("Content-Type", "application/cbor")
+                        .header("smithy-protocol", "rpc-v2-cbor")
+                        """
+                    },
+                    { _ ->
+                        // A map with one entry, whose key is a text string of length 7 and whose value is a text
+                        // string whose length is given by the following byte.
+                        """
+                        {
+                            let message = b"$LONG_MESSAGE";
+                            let mut body = vec![0xa1, 0x67];
+                            body.extend_from_slice(b"message");
+                            body.extend_from_slice(&[0x78, message.len() as u8]);
+                            body.extend_from_slice(message);
+                            body
+                        }
+                        """
+                    },
+                ),
+            )
+
+        override fun provideArguments(context: ExtensionContext?): Stream<out Arguments> =
+            testCases.map { Arguments.of(it) }.stream()
+    }
+
+    private fun protocolModel(testCase: ProtocolTestCase) =
+        """
+        ${'$'}version: "2.0"
+        namespace test
+
+        use ${testCase.protocol}
+
+        @${testCase.protocol.substringAfter("#")}
+        service TestService {
+            operations: [Echo, Ping]
+        }
+
+        ${testCase.httpTraits["Echo"] ?: ""}
+        operation Echo {
+            input := {
+                @required
+                message: String
+            }
+            output := {
+                @required
+                message: String
+            }
+        }
+
+        ${testCase.httpTraits["Ping"] ?: ""}
+        operation Ping {
+            input := {
+                @required
+                message: String
+            }
+            output := {
+                @required
+                message: String
+            }
+        }
+        """.asSmithyModel()
+
+    @ParameterizedTest
+    @ArgumentsSource(ProtocolTestCaseProvider::class)
+    fun `body size limits apply to every protocol`(testCase: ProtocolTestCase) {
+        serverIntegrationTest(
+            protocolModel(testCase),
+            IntegrationTestParams(
+                additionalSettings =
+                    ServerAdditionalSettings.builder()
+                        .requestBodyMaxBytes(16)
+                        .requestBodyMaxBytesPerOperation(mapOf("test#Echo" to 1024L))
+                        .toObjectNode(),
+            ),
+        ) { codegenContext, rustCrate ->
+            val codegenScope =
+                arrayOf(
+                    "SmithyHttpServer" to
+                        ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
+                    "Http" to RuntimeType.http(codegenContext.runtimeConfig),
+                    "Body" to ServerHttpTestHelpers.createBodyFromBytes(codegenContext, "body_bytes"),
+                )
+            rustCrate.testModule {
+                tokioTest("request_exceeding_limit_is_rejected") {
+                    rustTemplate(
+                        """
+                        use crate::input::PingInput;
+                        use #{SmithyHttpServer}::request::FromRequest;
+
+                        let body_bytes = ${testCase.body("Ping")};
+                        let request = #{Http}::Request::builder()
+                            ${testCase.request("Ping")}
+                            .body(#{Body})
+                            .unwrap();
+                        let result = PingInput::from_request(request).await;
+                        let rejection = result.expect_err("request exceeding limit should be rejected");
+                        assert_eq!(413, rejection.status_code().as_u16());
+                        """,
+                        *codegenScope,
+                    )
+                }
+                tokioTest("per_operation_limit_overrides_global_limit") {
+                    rustTemplate(
+                        """
+                        use crate::input::EchoInput;
+                        use #{SmithyHttpServer}::request::FromRequest;
+
+                        let body_bytes = ${testCase.body("Echo")};
+                        let request = #{Http}::Request::builder()
+                            ${testCase.request("Echo")}
+                            .body(#{Body})
+                            .unwrap();
+                        let result = EchoInput::from_request(request).await;
+                        let input = result.expect("request within the operation's limit should succeed");
+                        assert_eq!("$LONG_MESSAGE", input.message);
+                        """,
+                        *codegenScope,
+                    )
+                }
+            }
+        }
+    }
+
+    companion object {
+        private const val LONG_MESSAGE = "this body is definitely longer than sixteen bytes"
+    }
 }
@@ -0,0 +1,96 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.server.smithy.generators
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.HttpTestType
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.HttpTestVersion
+import software.amazon.smithy.rust.codegen.server.smithy.testutil.serverIntegrationTest
+
+internal class ServerOperationGeneratorTest {
+    private val model =
+        """
+        ${'$'}version: "2.0"
+        namespace test
+
+        use aws.protocols#restJson1
+
+        @restJson1
+        service TestService {
+            operations: [GetItem]
+            errors: [ServiceUnavailable]
+        }
+
+        @http(uri: "/items/{id}", method: "GET")
+        operation GetItem {
+            input := {
+                @required
+                @httpLabel
+                id: String
+            }
+            output := {
+                @httpHeader("X-Item-Version")
+                version: String
+
+                @httpHeader("ETag")
+                etag: String
+            }
+            errors: [ItemNotFound]
+        }
+
+        @error("client")
+        @httpError(404)
+        structure ItemNotFound {
+            @httpHeader("X-Item-Version")
+            version: String
+
+            @httpHeader("Retry-After")
+            retryAfter: String
+        }
+
+        @error("server")
+        @httpError(503)
+        structure ServiceUnavailable {
+            @httpHeader("X-Unavailable-Reason")
+            reason: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `operations compile with the default HTTP version`() {
+        serverIntegrationTest(model, testCoverage = HttpTestType.Default)
+    }
+
+    @Test
+    fun `operations expose the headers bound in their output, errors and service errors`() {
+        serverIntegrationTest(
+            model,
+            testCoverage = HttpTestType.Only(HttpTestVersion.HTTP_1_X),
+        ) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                unitTest("exposed_headers") {
+                    rustTemplate(
+                        """
+                        use #{SmithyHttpServer}::cors::CorsOperation;
+
+                        assert_eq!(
+                            &["etag", "retry-after", "x-item-version", "x-unavailable-reason"],
+                            crate::operation_shape::GetItem::EXPOSED_HEADERS
+                        );
+                        """,
+                        "SmithyHttpServer" to
+                            ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -6,12 +6,14 @@
 //! Provides types to support stream-like operations for paginators.
 
 use crate::future::pagination_stream::collect::sealed::Collectable;
-use std::future::Future;
+use std::future::{poll_fn, Future};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 pub mod collect;
+pub mod cursor;
 pub mod fn_stream;
+use cursor::PaginationCursor;
 use fn_stream::FnStream;
 
 /// Stream specifically made to support paginators.
@@ -46,30 +48,244 @@ use fn_stream::FnStream;
 /// # }
 /// ```
 ///
+/// Streams can be limited with [`.take_items()`](PaginationStream::take_items). With the `rt-tokio`
+/// feature, they can fetch items ahead of the caller with `.prefetch()`, and with the
+/// `futures-stream-0-3-x` feature, they can be converted into a `futures_util::Stream` with
+/// `.into_futures_stream()`.
+///
+/// Streams created with [`PaginationStream::resumable`] keep track of their position with a
+/// [`PaginationCursor`], which is available with [`.cursor()`](PaginationStream::cursor).
+///
 /// [`PaginationStream`] is implemented in terms of [`FnStream`], but the latter is meant to be
 /// used internally and not by external users.
 #[derive(Debug)]
-pub struct PaginationStream<Item>(FnStream<Item>);
+pub struct PaginationStream<Item> {
+    inner: Inner<Item>,
+    cursor: Option<PaginationCursor>,
+}
+
+#[derive(Debug)]
+enum Inner<Item> {
+    Items(FnStream<Item>),
+    // Items are paired with the cursor after them, if it changed
+    Resumable(FnStream<(Item, Option<PaginationCursor>)>),
+}
 
 impl<Item> PaginationStream<Item> {
     /// Creates a `PaginationStream` from the given [`FnStream`].
     pub fn new(stream: FnStream<Item>) -> Self {
-        Self(stream)
+        Self {
+            inner: Inner::Items(stream),
+            cursor: None,
+        }
+    }
+
+    /// Creates a resumable `PaginationStream` from the given [`FnStream`].
+    ///
+    /// Each item of `stream` is paired with the cursor to resume the stream after it, or `None`
+    /// if resuming the stream after the item would yield it again (e.g. for errors).
+    /// `start` is the cursor the stream was resumed from, if any.
+    pub fn resumable(
+        stream: FnStream<(Item, Option<PaginationCursor>)>,
+        start: Option<PaginationCursor>,
+    ) -> Self {
+        Self {
+            inner: Inner::Resumable(stream),
+            cursor: start,
+        }
+    }
+
+    /// Returns the cursor to resume this stream after the last item it yielded.
+    ///
+    /// The cursor can be persisted, and given back to the paginator that produced this stream to
+    /// resume it later on, possibly in another process. This returns `None` if the stream isn't
+    /// resumable, or if it's at its start.
+    pub fn cursor(&self) -> Option<&PaginationCursor> {
+        self.cursor.as_ref()
     }
 
     /// Consumes and returns the next `Item` from this stream.
     pub async fn next(&mut self) -> Option<Item> {
-        self.0.next().await
+        poll_fn(|cx| self.poll_next(cx)).await
     }
 
     /// Poll an item from the stream
     pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Item>> {
-        Pin::new(&mut self.0).poll_next(cx)
+        self.poll_next_with_cursor(cx)
+            .map(|next| next.map(|(item, _)| item))
+    }
+
+    async fn next_with_cursor(&mut self) -> Option<(Item, Option<PaginationCursor>)> {
+        poll_fn(|cx| self.poll_next_with_cursor(cx)).await
+    }
+
+    fn poll_next_with_cursor(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<(Item, Option<PaginationCursor>)>> {
+        match &mut self.inner {
+            Inner::Items(stream) => Pin::new(stream)
+                .poll_next(cx)
+                .map(|next| next.map(|item| (item, None))),
+            Inner::Resumable(stream) => {
+                let next = std::task::ready!(Pin::new(stream).poll_next(cx));
+                // The cursor is only updated once the item is yielded, since the generator of
+                // the stream may already be working on the next one.
+                if let Some((_, Some(cursor))) = &next {
+                    self.cursor = Some(cursor.clone());
+                }
+                Poll::Ready(next)
+            }
+        }
     }
 
     /// Consumes this stream and gathers elements into a collection.
-    pub async fn collect<T: Collectable<Item>>(self) -> T {
-        self.0.collect().await
+    pub async fn collect<T: Collectable<Item>>(mut self) -> T {
+        let mut collection = T::initialize();
+        while let Some(item) = self.next().await {
+            if !T::extend(&mut collection, item) {
+                break;
+            }
+        }
+        T::finalize(collection)
+    }
+
+    /// Produces a new [`PaginationStream`] that yields at most `limit` items of this stream.
+    ///
+    /// Since items are only fetched on demand, no page is fetched once `limit` items have been
+    /// yielded. On a stream of items (e.g. from a paginator's `.items()`), this limits the number of
+    /// items rather than pages, and only fetches the pages needed to produce them:
+    ///
+    /// ```no_run
+    /// # async fn docs() {
+    /// # use aws_smithy_async::future::pagination_stream::PaginationStream;
+    /// # fn operation_to_yield_items<T>() -> PaginationStream<T> {
+    /// #     todo!()
+    /// # }
+    /// # struct Item;
+    /// let stream: PaginationStream<Result<Item, ()>> = operation_to_yield_items();
+    /// let first_items = stream.take_items(10).try_collect().await;
+    /// # }
+    /// ```
+    pub fn take_items(mut self, limit: usize) -> PaginationStream<Item>
+    where
+        Item: Send + 'static,
+    {
+        let start = self.cursor.clone();
+        PaginationStream::resumable(
+            FnStream::new(move |tx| {
+                Box::pin(async move {
+                    for _ in 0..limit {
+                        match self.next_with_cursor().await {
+                            Some(next) => {
+                                if tx.send(next).await.is_err() {
+                                    return;
+                                }
+                            }
+                            None => return,
+                        }
+                    }
+                }) as Pin<Box<dyn Future<Output = ()> + Send>>
+            }),
+            start,
+        )
+    }
+
+    /// Produces a new [`PaginationStream`] that fetches up to `items` items of this stream ahead of
+    /// the caller.
+    ///
+    /// By default, the next item is only requested once the caller asks for it. With prefetching,
+    /// this stream is driven by a background task as soon as this method is called, so the next
+    /// items are requested while the caller processes the current one. Items are still requested
+    /// one at a time, and at most `items` of them are buffered. The items of a paginator are its
+    /// pages, so prefetching it buffers whole pages, whereas prefetching the stream returned by
+    /// its `.items()` buffers individual items. When the returned stream is dropped, the
+    /// background task is aborted.
+    ///
+    /// If `items` is zero, this stream is returned as is.
+    ///
+    /// # Panics
+    /// This method panics if it isn't called within the context of a Tokio runtime.
+    #[cfg(feature = "rt-tokio")]
+    pub fn prefetch(mut self, items: usize) -> PaginationStream<Item>
+    where
+        Item: Send + 'static,
+    {
+        if items == 0 {
+            return self;
+        }
+        let start = self.cursor.clone();
+        let (buffer_tx, mut buffer_rx) = tokio::sync::mpsc::channel(items);
+        let task = tokio::spawn(async move {
+            // Only fetch the next item once there is room for it in the buffer
+            while let Ok(permit) = buffer_tx.reserve().await {
+                match self.next_with_cursor().await {
+                    Some(next) => permit.send(next),
+                    None => return,
+                }
+            }
+        });
+        PaginationStream::resumable(
+            FnStream::new(move |tx| {
+                Box::pin(async move {
+                    let _task = AbortOnDrop(task);
+                    while let Some(next) = buffer_rx.recv().await {
+                        if tx.send(next).await.is_err() {
+                            return;
+                        }
+                    }
+                }) as Pin<Box<dyn Future<Output = ()> + Send>>
+            }),
+            start,
+        )
+    }
+
+    /// Converts this stream into an implementor of [`futures_util::Stream`].
+    ///
+    /// This makes the combinators of the `futures` crate available, e.g. `StreamExt::buffered`.
+    #[cfg(feature = "futures-stream-0-3-x")]
+    pub fn into_futures_stream(self) -> FuturesStream<Item> {
+        FuturesStream(self)
+    }
+}
+
+/// Aborts the background task of [`PaginationStream::prefetch`] when the stream is dropped.
+#[cfg(feature = "rt-tokio")]
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+#[cfg(feature = "rt-tokio")]
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Implementor of [`futures_util::Stream`] wrapping a [`PaginationStream`].
+///
+/// Created with [`PaginationStream::into_futures_stream`].
+#[cfg(feature = "futures-stream-0-3-x")]
+#[derive(Debug)]
+pub struct FuturesStream<Item>(PaginationStream<Item>);
+
+#[cfg(feature = "futures-stream-0-3-x")]
+impl<Item> FuturesStream<Item> {
+    /// Returns the cursor of the wrapped stream, see [`PaginationStream::cursor`].
+    pub fn cursor(&self) -> Option<&PaginationCursor> {
+        self.0.cursor()
+    }
+
+    /// Returns the wrapped [`PaginationStream`].
+    pub fn into_inner(self) -> PaginationStream<Item> {
+        self.0
+    }
+}
+
+#[cfg(feature = "futures-stream-0-3-x")]
+impl<Item> futures_util::Stream for FuturesStream<Item> {
+    type Item = Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Item>> {
+        self.0.poll_next(cx)
     }
 }
 
@@ -100,6 +316,9 @@ impl<Page, Err> TryFlatMap<Page, Err> {
     }
 
     /// Produces a new [`PaginationStream`] by mapping this stream with `map` then flattening the result.
+    ///
+    /// If this stream is resumable, so is the produced stream, but only at page boundaries: its
+    /// cursor only moves once all the items of a page have been yielded.
     pub fn flat_map<M, Item, Iter>(mut self, map: M) -> PaginationStream<Result<Item, Err>>
     where
         Page: Send + 'static,
@@ -109,30 +328,40 @@ impl<Page, Err> TryFlatMap<Page, Err> {
         Iter: IntoIterator<Item = Item> + Send,
         <Iter as IntoIterator>::IntoIter: Send,
     {
-        PaginationStream::new(FnStream::new(|tx| {
-            Box::pin(async move {
-                while let Some(page) = self.0.next().await {
-                    match page {
-                        Ok(page) => {
-                            let mapped = map(page);
-                            for item in mapped.into_iter() {
-                                let _ = tx.send(Ok(item)).await;
+        let start = self.0.cursor.clone();
+        PaginationStream::resumable(
+            FnStream::new(|tx| {
+                Box::pin(async move {
+                    while let Some((page, mut cursor)) = self.0.next_with_cursor().await {
+                        match page {
+                            Ok(page) => {
+                                let mut mapped = map(page).into_iter().peekable();
+                                while let Some(item) = mapped.next() {
+                                    let cursor = match mapped.peek() {
+                                        Some(_) => None,
+                                        None => cursor.take(),
+                                    };
+                                    let _ = tx.send((Ok(item), cursor)).await;
+                                }
+                            }
+                            Err(e) => {
+                                let _ = tx.send((Err(e), None)).await;
+                                break;
                             }
-                        }
-                        Err(e) => {
-                            let _ = tx.send(Err(e)).await;
-                            break;
                         }
                     }
-                }
-            }) as Pin<Box<dyn Future<Output = ()> + Send>>
-        }))
+                }) as Pin<Box<dyn Future<Output = ()> + Send>>
+            }),
+            start,
+        )
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::future::pagination_stream::{FnStream, PaginationStream, TryFlatMap};
+    use crate::future::pagination_stream::{
+        FnStream, PaginationCursor, PaginationStream, TryFlatMap,
+    };
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
@@ -306,4 +535,180 @@ mod test {
                 .await
         )
     }
+
+    /// Creates a stream of `count` pages of three items, recording the number of pages fetched
+    fn pages(
+        count: u8,
+        fetched: Arc<Mutex<u8>>,
+    ) -> PaginationStream<Result<Vec<u8>, &'static str>> {
+        PaginationStream::new(FnStream::new(move |tx| {
+            Box::pin(async move {
+                for page in 0..count {
+                    *fetched.lock().unwrap() += 1;
+                    let items = (page * 3..page * 3 + 3).collect();
+                    if tx.send(Ok(items)).await.is_err() {
+                        return;
+                    }
+                }
+            })
+        }))
+    }
+
+    #[tokio::test]
+    async fn take_items_stops_fetching_pages() {
+        let fetched = Arc::new(Mutex::new(0));
+        let items = TryFlatMap::new(pages(10, fetched.clone()))
+            .flat_map(|page| page)
+            .take_items(4)
+            .try_collect()
+            .await;
+        assert_eq!(Ok(vec![0, 1, 2, 3]), items);
+        assert_eq!(2, *fetched.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn take_items_of_short_stream() {
+        let fetched = Arc::new(Mutex::new(0));
+        let items = TryFlatMap::new(pages(2, fetched.clone()))
+            .flat_map(|page| page)
+            .take_items(100)
+            .try_collect()
+            .await;
+        assert_eq!(Ok(vec![0, 1, 2, 3, 4, 5]), items);
+
+        let mut stream = pages(2, fetched.clone()).take_items(0);
+        assert_eq!(None, stream.next().await);
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn prefetch_fetches_pages_ahead() {
+        let fetched = Arc::new(Mutex::new(0));
+        let mut stream = pages(10, fetched.clone()).prefetch(2);
+
+        assert_eq!(Some(Ok(vec![0, 1, 2])), stream.next().await);
+        // let the background task fill the buffer while the page is processed
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        // the page that was returned, and the two that are buffered
+        assert_eq!(3, *fetched.lock().unwrap());
+
+        assert_eq!(
+            Ok((0..30).skip(3).collect::<Vec<_>>()),
+            stream
+                .collect::<Result<Vec<_>, _>>()
+                .await
+                .map(|pages| pages.concat())
+        );
+        assert_eq!(10, *fetched.lock().unwrap());
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn prefetch_stops_when_dropped() {
+        let fetched = Arc::new(Mutex::new(0));
+        let mut stream = pages(10, fetched.clone()).prefetch(1);
+        assert!(stream.next().await.is_some());
+        drop(stream);
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(2, *fetched.lock().unwrap());
+    }
+
+    #[cfg(feature = "futures-stream-0-3-x")]
+    #[tokio::test]
+    async fn into_futures_stream() {
+        use futures_util::StreamExt;
+
+        let fetched = Arc::new(Mutex::new(0));
+        let stream = TryFlatMap::new(pages(2, fetched))
+            .flat_map(|page| page)
+            .into_futures_stream();
+        let items: Vec<_> = stream.map(|item| item.unwrap() * 2).collect().await;
+        assert_eq!(vec![0, 2, 4, 6, 8, 10], items);
+    }
+
+    /// Creates a resumable stream of pages of three items, with tokens "page-<n>", starting at `start`
+    fn resumable_pages(
+        count: u8,
+        start: Option<PaginationCursor>,
+    ) -> PaginationStream<Result<Vec<u8>, &'static str>> {
+        let first = match start.as_ref().map(|cursor| cursor.token()) {
+            Some(None) => count,
+            Some(Some(token)) => token.trim_start_matches("page-").parse().unwrap(),
+            None => 0,
+        };
+        PaginationStream::resumable(
+            FnStream::new(move |tx| {
+                Box::pin(async move {
+                    for page in first..count {
+                        let items = (page * 3..page * 3 + 3).collect();
+                        let cursor = match page + 1 {
+                            next if next == count => PaginationCursor::done(),
+                            next => PaginationCursor::new(format!("page-{next}")),
+                        };
+                        if tx.send((Ok(items), Some(cursor))).await.is_err() {
+                            return;
+                        }
+                    }
+                })
+            }),
+            start,
+        )
+    }
+
+    #[tokio::test]
+    async fn cursor_tracks_yielded_pages() {
+        let mut stream = resumable_pages(3, None);
+        assert_eq!(None, stream.cursor());
+        stream.next().await.unwrap().unwrap();
+        assert_eq!(Some(&PaginationCursor::new("page-1")), stream.cursor());
+
+        let checkpoint = stream.cursor().unwrap().to_string();
+        let mut resumed = resumable_pages(3, Some(checkpoint.parse().unwrap()));
+        assert_eq!(Some(&PaginationCursor::new("page-1")), resumed.cursor());
+        assert_eq!(Some(Ok(vec![3, 4, 5])), resumed.next().await);
+        assert_eq!(Some(Ok(vec![6, 7, 8])), resumed.next().await);
+        assert_eq!(Some(&PaginationCursor::done()), resumed.cursor());
+        assert_eq!(None, resumed.next().await);
+
+        let mut done = resumable_pages(3, Some(PaginationCursor::done()));
+        assert_eq!(None, done.next().await);
+    }
+
+    #[tokio::test]
+    async fn flat_map_cursor_moves_at_page_boundaries() {
+        let mut items = TryFlatMap::new(resumable_pages(3, None))
+            .flat_map(|page| page)
+            .take_items(5);
+        assert_eq!(Some(Ok(0)), items.next().await);
+        assert_eq!(Some(Ok(1)), items.next().await);
+        assert_eq!(None, items.cursor());
+        assert_eq!(Some(Ok(2)), items.next().await);
+        assert_eq!(Some(&PaginationCursor::new("page-1")), items.cursor());
+        assert_eq!(Some(Ok(3)), items.next().await);
+        assert_eq!(Some(Ok(4)), items.next().await);
+        assert_eq!(None, items.next().await);
+
+        // resuming yields the items of the interrupted page again
+        let resumed = TryFlatMap::new(resumable_pages(3, items.cursor().cloned()))
+            .flat_map(|page| page)
+            .try_collect()
+            .await;
+        assert_eq!(Ok(vec![3, 4, 5, 6, 7, 8]), resumed);
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn prefetch_cursor_tracks_yielded_pages() {
+        let mut stream = resumable_pages(3, None).prefetch(2);
+        stream.next().await.unwrap().unwrap();
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        // pages that are buffered aren't taken into account
+        assert_eq!(Some(&PaginationCursor::new("page-1")), stream.cursor());
+    }
 }
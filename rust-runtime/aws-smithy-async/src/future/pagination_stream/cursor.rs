@@ -0,0 +1,113 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Module to define the cursor of a resumable [`PaginationStream`](super::PaginationStream).
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const DONE: &str = "done";
+const TOKEN_PREFIX: &str = "token:";
+
+/// Position in a paginated stream, from which the stream can be resumed.
+///
+/// A cursor is obtained from [`PaginationStream::cursor`](super::PaginationStream::cursor), and
+/// given back to the paginator that produced the stream to resume it, e.g. after a crash. Cursors
+/// are opaque, but can be persisted with their string representation:
+///
+/// ```
+/// use aws_smithy_async::future::pagination_stream::cursor::PaginationCursor;
+///
+/// let cursor = PaginationCursor::new("next-token");
+/// let checkpoint = cursor.to_string();
+/// assert_eq!(cursor, checkpoint.parse().unwrap());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PaginationCursor {
+    // `None` once the stream is exhausted
+    token: Option<String>,
+}
+
+impl PaginationCursor {
+    /// Creates a cursor resuming the stream with the page requested with `token`.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: Some(token.into()),
+        }
+    }
+
+    /// Creates a cursor of an exhausted stream.
+    ///
+    /// Streams resumed from this cursor don't yield any item.
+    pub fn done() -> Self {
+        Self { token: None }
+    }
+
+    /// Returns the token to request the next page with, or `None` if the stream is exhausted.
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns true if the stream is exhausted.
+    pub fn is_done(&self) -> bool {
+        self.token.is_none()
+    }
+}
+
+impl fmt::Display for PaginationCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.token {
+            Some(token) => write!(f, "{TOKEN_PREFIX}{token}"),
+            None => f.write_str(DONE),
+        }
+    }
+}
+
+impl FromStr for PaginationCursor {
+    type Err = InvalidPaginationCursor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == DONE {
+            Ok(Self::done())
+        } else if let Some(token) = s.strip_prefix(TOKEN_PREFIX) {
+            Ok(Self::new(token))
+        } else {
+            Err(InvalidPaginationCursor)
+        }
+    }
+}
+
+/// Error returned when parsing a string that isn't a [`PaginationCursor`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InvalidPaginationCursor;
+
+impl fmt::Display for InvalidPaginationCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid pagination cursor")
+    }
+}
+
+impl Error for InvalidPaginationCursor {}
+
+#[cfg(test)]
+mod test {
+    use super::PaginationCursor;
+
+    #[test]
+    fn round_trip() {
+        for cursor in [
+            PaginationCursor::new("abc"),
+            PaginationCursor::new(""),
+            PaginationCursor::new("token:done"),
+            PaginationCursor::done(),
+        ] {
+            assert_eq!(cursor, cursor.to_string().parse().unwrap());
+        }
+        assert!("done".parse::<PaginationCursor>().unwrap().is_done());
+        assert!("abc".parse::<PaginationCursor>().is_err());
+    }
+}
@@ -40,26 +40,91 @@
 //! task.await.unwrap();
 //! # }
 //! ```
+//!
+//! Futures that sleep an unknown amount of time, like retries with jittered backoff, can be
+//! driven to completion without ticking manually with [`TickAdvanceTime::run_to_completion`]:
+//!
+//! ```rust,no_run
+//! use std::time::{Duration, SystemTime};
+//! use aws_smithy_async::test_util::tick_advance_sleep::tick_advance_time_and_sleep;
+//! use aws_smithy_async::time::TimeSource;
+//! use aws_smithy_async::rt::sleep::AsyncSleep;
+//!
+//! # async fn example() {
+//! let (time, sleep) = tick_advance_time_and_sleep();
+//! let output = time
+//!     .run_to_completion(async {
+//!         sleep.sleep(Duration::from_secs(30)).await;
+//!         "done"
+//!     })
+//!     .await;
+//! assert_eq!("done", output);
+//! assert_eq!(SystemTime::UNIX_EPOCH + Duration::from_secs(30), time.now());
+//! # }
+//! ```
 
 use crate::{
     rt::sleep::{AsyncSleep, Sleep},
     time::TimeSource,
 };
 use std::{
-    future::IntoFuture,
+    future::{Future, IntoFuture},
     ops::{Deref, DerefMut},
+    pin::pin,
     sync::{Arc, Mutex},
+    task::Poll,
     time::{Duration, SystemTime},
 };
 use tokio::sync::oneshot::Sender;
 
+/// Number of times the runtime is yielded to before advancing time in [`TickAdvanceTime::run_to_completion`].
+const YIELDS_BEFORE_TICK: usize = 16;
+
+/// Number of consecutive times [`TickAdvanceTime::run_to_completion`] finds no sleep to advance
+/// time to before giving up.
+const MAX_STALLED_ROUNDS: usize = 100;
+
 #[derive(Debug)]
 struct QueuedSleep {
     /// Duration since `UNIX_EPOCH` at which point the sleep is finished.
     presents_at: Duration,
+    /// Duration the sleep was created with.
+    duration: Duration,
     notify: Option<Sender<()>>,
 }
 
+impl QueuedSleep {
+    /// Returns true if the sleep future was dropped before presenting.
+    fn is_abandoned(&self) -> bool {
+        self.notify
+            .as_ref()
+            .map(|notify| notify.is_closed())
+            .unwrap_or(true)
+    }
+}
+
+/// A sleep that hasn't presented yet.
+///
+/// Returned by [`TickAdvanceTime::pending_sleeps`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PendingSleep {
+    presents_at: SystemTime,
+    duration: Duration,
+}
+
+impl PendingSleep {
+    /// The time at which the sleep will present.
+    pub fn presents_at(&self) -> SystemTime {
+        self.presents_at
+    }
+
+    /// The duration the sleep was created with.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
 #[derive(Default, Debug)]
 struct Inner {
     // Need to use a Vec since VecDeque doesn't have sort functions,
@@ -88,6 +153,11 @@ impl Inner {
             None
         }
     }
+
+    /// Removes the sleeps whose futures were dropped, e.g. timeouts that lost a race.
+    fn prune_abandoned(&mut self) {
+        self.sleeps.retain(|sleep| !sleep.is_abandoned());
+    }
 }
 
 #[derive(Clone, Default, Debug)]
@@ -123,6 +193,7 @@ impl AsyncSleep for TickAdvanceSleep {
         // Add the sleep to the queue, which `TickAdvanceTime` will examine when ticking.
         inner.push(QueuedSleep {
             presents_at: now + duration,
+            duration,
             notify: Some(tx),
         });
 
@@ -175,6 +246,70 @@ impl TickAdvanceTime {
         // Set the final time.
         self.inner.get_mut().now = time;
     }
+
+    /// Returns the sleeps that haven't presented yet, in the order they will present.
+    ///
+    /// Sleeps whose futures were dropped are not included.
+    pub fn pending_sleeps(&self) -> Vec<PendingSleep> {
+        let mut inner = self.inner.get_mut();
+        inner.prune_abandoned();
+        inner
+            .sleeps
+            .iter()
+            .map(|sleep| PendingSleep {
+                presents_at: SystemTime::UNIX_EPOCH + sleep.presents_at,
+                duration: sleep.duration,
+            })
+            .collect()
+    }
+
+    /// Advance time to the next pending sleep, and return the new time.
+    ///
+    /// Returns `None`, without advancing time, if there are no pending sleeps.
+    pub async fn tick_to_next_sleep(&self) -> Option<SystemTime> {
+        let duration = {
+            let mut inner = self.inner.get_mut();
+            inner.prune_abandoned();
+            let next = inner.sleeps.first()?.presents_at;
+            next.saturating_sub(inner.now)
+        };
+        self.tick(duration).await;
+        Some(self.now())
+    }
+
+    /// Polls `future` to completion, advancing time to the next pending sleep whenever it's
+    /// pending.
+    ///
+    /// The async runtime is yielded several times before advancing time, so that spawned tasks the
+    /// future depends on can make progress, and time is only advanced as far as the next sleep.
+    /// This makes it possible to run code that sleeps, like retries, timeouts and waiters, without
+    /// knowing ahead of time how long it will sleep for.
+    ///
+    /// # Panics
+    /// This function panics if the future stays pending while there are no pending sleeps, since
+    /// advancing time can't make progress in that case.
+    pub async fn run_to_completion<F: Future>(&self, future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut stalled_rounds = 0;
+        loop {
+            for _ in 0..YIELDS_BEFORE_TICK {
+                let poll = std::future::poll_fn(|cx| Poll::Ready(future.as_mut().poll(cx))).await;
+                if let Poll::Ready(output) = poll {
+                    return output;
+                }
+                tokio::task::yield_now().await;
+            }
+            if self.tick_to_next_sleep().await.is_some() {
+                stalled_rounds = 0;
+            } else {
+                stalled_rounds += 1;
+                assert!(
+                    stalled_rounds < MAX_STALLED_ROUNDS,
+                    "the future is pending, but there are no pending sleeps to advance time to"
+                );
+            }
+        }
+    }
 }
 
 impl TimeSource for TickAdvanceTime {
@@ -187,7 +322,22 @@ impl TimeSource for TickAdvanceTime {
 ///
 /// See [module docs](crate::test_util::tick_advance_sleep) for more information.
 pub fn tick_advance_time_and_sleep() -> (TickAdvanceTime, TickAdvanceSleep) {
+    tick_advance_time_and_sleep_at(SystemTime::UNIX_EPOCH)
+}
+
+/// Creates tick-advancing test time/sleep implementations starting at `start_time`.
+///
+/// # Panics
+/// This function panics if `start_time` is before `UNIX_EPOCH`.
+///
+/// See [module docs](crate::test_util::tick_advance_sleep) for more information.
+pub fn tick_advance_time_and_sleep_at(
+    start_time: SystemTime,
+) -> (TickAdvanceTime, TickAdvanceSleep) {
     let inner = SharedInner::default();
+    inner.get_mut().now = start_time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("start time must be after UNIX_EPOCH");
     (
         TickAdvanceTime {
             inner: inner.clone(),
@@ -261,6 +411,80 @@ mod tests {
         task.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn pending_sleeps() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let (time, sleep) = tick_advance_time_and_sleep_at(start);
+        assert_eq!(start, time.now());
+        assert!(time.pending_sleeps().is_empty());
+        assert_eq!(None, time.tick_to_next_sleep().await);
+
+        let long = tokio::spawn(sleep.sleep(Duration::from_secs(5)));
+        let short = tokio::spawn(sleep.sleep(Duration::from_secs(2)));
+        let dropped = sleep.sleep(Duration::from_secs(1));
+        drop(dropped);
+
+        let pending = time.pending_sleeps();
+        assert_eq!(2, pending.len(), "dropped sleeps are not pending");
+        assert_eq!(start + Duration::from_secs(2), pending[0].presents_at());
+        assert_eq!(Duration::from_secs(2), pending[0].duration());
+        assert_eq!(start + Duration::from_secs(5), pending[1].presents_at());
+
+        assert_eq!(
+            Some(start + Duration::from_secs(2)),
+            time.tick_to_next_sleep().await
+        );
+        short.await.unwrap();
+        assert!(!long.is_finished());
+        assert_eq!(
+            Some(start + Duration::from_secs(5)),
+            time.tick_to_next_sleep().await
+        );
+        long.await.unwrap();
+        assert!(time.pending_sleeps().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_to_completion() {
+        let (time, sleep) = tick_advance_time_and_sleep();
+
+        let spawned = tokio::spawn({
+            let sleep = sleep.clone();
+            async move {
+                sleep.sleep(Duration::from_secs(10)).await;
+                "spawned"
+            }
+        });
+        let output = time
+            .run_to_completion(async {
+                let timeout = sleep.sleep(Duration::from_secs(60));
+                let work = async {
+                    for _ in 0..3 {
+                        sleep.sleep(Duration::from_secs(5)).await;
+                    }
+                    spawned.await.unwrap()
+                };
+                tokio::select! {
+                    output = work => output,
+                    _ = timeout => panic!("the timeout should not present first"),
+                }
+            })
+            .await;
+        assert_eq!("spawned", output);
+        assert_eq!(SystemTime::UNIX_EPOCH + Duration::from_secs(15), time.now());
+        assert!(
+            time.pending_sleeps().is_empty(),
+            "the timeout was dropped when the work completed"
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no pending sleeps")]
+    async fn run_to_completion_stalled() {
+        let (time, _sleep) = tick_advance_time_and_sleep();
+        time.run_to_completion(std::future::pending::<()>()).await;
+    }
+
     #[tokio::test]
     async fn racing_sleeps() {
         let (time, sleep) = tick_advance_time_and_sleep();
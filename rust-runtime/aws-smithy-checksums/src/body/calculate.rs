@@ -132,7 +132,10 @@ impl http_body_1x::Body for ChecksumBody<SdkBody> {
 #[cfg(test)]
 mod tests {
     use super::ChecksumBody;
-    use crate::{http::CRC_32_HEADER_NAME, ChecksumAlgorithm, CRC_32_NAME};
+    use crate::http::{
+        CRC_32_HEADER_NAME, SHA_512_HEADER_NAME, XXHASH_3_HEADER_NAME, XXHASH_64_HEADER_NAME,
+    };
+    use crate::{ChecksumAlgorithm, CRC_32_NAME, SHA_512_NAME, XXHASH_3_NAME, XXHASH_64_NAME};
     use aws_smithy_types::base64;
     use aws_smithy_types::body::SdkBody;
     use bytes::Buf;
@@ -141,6 +144,7 @@ mod tests {
     use http_body_util::BodyExt;
     use std::fmt::Write;
     use std::io::Read;
+    use std::sync::LazyLock;
 
     fn header_value_as_checksum_string(header_value: &http_1x::HeaderValue) -> String {
         let decoded_checksum = base64::decode(header_value.to_str().unwrap()).unwrap();
@@ -154,11 +158,22 @@ mod tests {
         format!("0x{decoded_checksum}")
     }
 
-    #[tokio::test]
-    async fn test_checksum_body() {
-        let input_text = "This is some test text for an SdkBody";
-        let body = SdkBody::from(input_text);
-        let checksum = CRC_32_NAME
+    async fn checksum_trailer_for(algorithm_name: &str, header_name: &str) -> String {
+        checksum_trailer_for_input(
+            algorithm_name,
+            header_name,
+            b"This is some test text for an SdkBody",
+        )
+        .await
+    }
+
+    async fn checksum_trailer_for_input(
+        algorithm_name: &str,
+        header_name: &str,
+        input: &'static [u8],
+    ) -> String {
+        let body = SdkBody::from(input);
+        let checksum = algorithm_name
             .parse::<ChecksumAlgorithm>()
             .unwrap()
             .into_impl();
@@ -178,20 +193,67 @@ mod tests {
             }
         }
 
-        let mut output_text = String::new();
+        let mut output = Vec::new();
         output_data
             .reader()
-            .read_to_string(&mut output_text)
+            .read_to_end(&mut output)
             .expect("Doesn't cause IO errors");
         // Verify data is complete and unaltered
-        assert_eq!(input_text, output_text);
+        assert_eq!(input, output);
 
         let checksum_trailer = trailers
-            .get(CRC_32_HEADER_NAME)
-            .expect("trailers contain crc32 checksum");
-        let checksum_trailer = header_value_as_checksum_string(checksum_trailer);
+            .get(header_name)
+            .unwrap_or_else(|| panic!("trailers contain {algorithm_name} checksum"));
+        header_value_as_checksum_string(checksum_trailer)
+    }
+
+    #[tokio::test]
+    async fn test_checksum_body() {
+        let checksum_trailer = checksum_trailer_for(CRC_32_NAME, CRC_32_HEADER_NAME).await;
 
         // Known correct checksum for the input "This is some test text for an SdkBody"
         assert_eq!("0x99B01F72", checksum_trailer);
     }
+
+    #[tokio::test]
+    async fn test_checksum_body_sha512() {
+        let checksum_trailer = checksum_trailer_for(SHA_512_NAME, SHA_512_HEADER_NAME).await;
+
+        // Known correct checksum for the input "This is some test text for an SdkBody"
+        assert_eq!(
+            "0x4D5728EFFE1A86FFE020CF35FCA377517011E88BC87AC059369DDD3DC710226BEA1957C6844656F7EBF7FFF7441EBD142A80CCA69A1AEA80BEA621FB09D55EF3",
+            checksum_trailer
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checksum_body_xxhash64() {
+        let checksum_trailer = checksum_trailer_for(XXHASH_64_NAME, XXHASH_64_HEADER_NAME).await;
+
+        // Known correct checksum for the input "This is some test text for an SdkBody"
+        assert_eq!("0x6B84F29DEC227497", checksum_trailer);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_body_xxhash3() {
+        // The sanity buffer of the xxHash reference test vectors
+        static INPUT: LazyLock<Vec<u8>> = LazyLock::new(|| {
+            const PRIME32: u64 = 2654435761;
+            const PRIME64: u64 = 11400714785074694797;
+            let mut byte_gen = PRIME32;
+            (0..2367)
+                .map(|_| {
+                    let byte = (byte_gen >> 56) as u8;
+                    byte_gen = byte_gen.wrapping_mul(PRIME64);
+                    byte
+                })
+                .collect()
+        });
+        let checksum_trailer =
+            checksum_trailer_for_input(XXHASH_3_NAME, XXHASH_3_HEADER_NAME, INPUT.as_slice()).await;
+
+        // Known correct XXH3 (64-bit) checksum of the first 2367 bytes of the sanity buffer, from the
+        // xxHash reference test vectors
+        assert_eq!("0xCB37AEB9E5D361ED", checksum_trailer);
+    }
 }
@@ -205,4 +205,49 @@ mod tests {
         // Verify data is complete and unaltered
         assert_eq!(input_text, output_text);
     }
+
+    #[tokio::test]
+    async fn test_checksum_validated_body_supports_xxhash_and_sha512() {
+        let input_text = "This is some test text for an SdkBody";
+        for algorithm in [
+            ChecksumAlgorithm::XxHash64,
+            ChecksumAlgorithm::XxHash3,
+            ChecksumAlgorithm::Sha512,
+        ] {
+            let mut checksum = algorithm.into_impl();
+            checksum.update(input_text.as_bytes());
+            let actual_checksum = checksum.finalize();
+
+            let mut body = ChecksumBody::new(
+                SdkBody::from(input_text),
+                algorithm.into_impl(),
+                actual_checksum.clone(),
+            );
+            while let Some(buf) = body.frame().await {
+                buf.expect("checksum matches");
+            }
+
+            let non_matching_checksum = Bytes::from(vec![0; actual_checksum.len()]);
+            let mut body = ChecksumBody::new(
+                SdkBody::from(input_text),
+                algorithm.into_impl(),
+                non_matching_checksum,
+            );
+            let mut saw_mismatch = false;
+            while let Some(buf) = body.frame().await {
+                if let Err(e) = buf {
+                    assert!(matches!(
+                        e.downcast_ref::<Error>().unwrap(),
+                        Error::ChecksumMismatch { actual, .. } if actual == &actual_checksum
+                    ));
+                    saw_mismatch = true;
+                    break;
+                }
+            }
+            assert!(
+                saw_mismatch,
+                "{algorithm:?} didn't hit expected error condition"
+            );
+        }
+    }
 }
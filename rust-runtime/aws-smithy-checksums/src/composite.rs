@@ -0,0 +1,443 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Whole-object checksums for data that is uploaded in parts.
+//!
+//! When an object is uploaded in parts (e.g. an S3 multipart upload), each part is checksummed
+//! separately. There are two ways to derive a checksum for the whole object from those part
+//! checksums:
+//!
+//! - A **full object** checksum ([`FullObjectChecksum`]) mathematically combines the CRCs of each
+//!   part. The result is identical to the checksum of the whole object calculated in one pass.
+//!   Only CRC algorithms (CRC32, CRC32C and CRC64NVME) can be combined this way.
+//! - A **composite** checksum ([`CompositeChecksum`]) is a "checksum of checksums": the raw part
+//!   checksums are concatenated in part order and checksummed again. It is rendered as the
+//!   base64-encoded checksum followed by `-<part count>` (see [`CompositeChecksumValue`]).
+//!
+//! ```
+//! use aws_smithy_checksums::composite::{CompositeChecksum, CompositeChecksumValue};
+//! use aws_smithy_checksums::ChecksumAlgorithm;
+//!
+//! let parts: [&[u8]; 2] = [b"hello ", b"world"];
+//! let mut composite = CompositeChecksum::new(ChecksumAlgorithm::Crc32).unwrap();
+//! for part in parts {
+//!     let mut checksum = ChecksumAlgorithm::Crc32.into_impl();
+//!     checksum.update(part);
+//!     composite.add_part(&checksum.finalize()).unwrap();
+//! }
+//!
+//! // Compare against the value returned by the service
+//! let returned: CompositeChecksumValue = "1Fu2mQ==-2".parse().unwrap();
+//! assert_eq!(composite.finalize(), returned);
+//! ```
+
+use crate::error::CompositeChecksumError;
+use crate::http::HttpChecksum;
+use crate::ChecksumAlgorithm;
+
+use aws_smithy_types::base64;
+use bytes::Bytes;
+use std::fmt;
+use std::str::FromStr;
+
+/// Return the `crc-fast` algorithm and checksum width in bytes for a CRC `ChecksumAlgorithm`.
+fn crc_params(
+    algorithm: ChecksumAlgorithm,
+) -> Result<(crc_fast::CrcAlgorithm, usize), CompositeChecksumError> {
+    match algorithm {
+        ChecksumAlgorithm::Crc32 => Ok((crc_fast::CrcAlgorithm::Crc32IsoHdlc, 4)),
+        ChecksumAlgorithm::Crc32c => Ok((crc_fast::CrcAlgorithm::Crc32Iscsi, 4)),
+        ChecksumAlgorithm::Crc64Nvme => Ok((crc_fast::CrcAlgorithm::Crc64Nvme, 8)),
+        other => Err(CompositeChecksumError::unsupported_algorithm(
+            other.as_str(),
+        )),
+    }
+}
+
+fn crc_from_bytes(checksum: &[u8], width: usize) -> Result<u64, CompositeChecksumError> {
+    if checksum.len() != width {
+        return Err(CompositeChecksumError::invalid_checksum_length(
+            width,
+            checksum.len(),
+        ));
+    }
+    Ok(checksum
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)))
+}
+
+fn crc_to_bytes(crc: u64, width: usize) -> Bytes {
+    Bytes::copy_from_slice(&crc.to_be_bytes()[8 - width..])
+}
+
+/// Combine the CRCs of two adjacent pieces of data into the CRC of their concatenation.
+///
+/// `first` and `second` are raw (not base64-encoded) big-endian checksums, as returned by
+/// [`Checksum::finalize`](crate::Checksum::finalize). `second_length` is the length in bytes of
+/// the data that `second` was calculated over.
+///
+/// Returns an error if `algorithm` isn't a CRC algorithm or if either checksum has the wrong length.
+pub fn combine_crc(
+    algorithm: ChecksumAlgorithm,
+    first: &[u8],
+    second: &[u8],
+    second_length: u64,
+) -> Result<Bytes, CompositeChecksumError> {
+    let (crc_algorithm, width) = crc_params(algorithm)?;
+    let combined = crc_fast::checksum_combine(
+        crc_algorithm,
+        crc_from_bytes(first, width)?,
+        crc_from_bytes(second, width)?,
+        second_length,
+    );
+    Ok(crc_to_bytes(combined, width))
+}
+
+/// Calculates a full object CRC from the CRCs of each part of an object.
+///
+/// Parts must be added in order. The result is the same checksum that would have been calculated
+/// over the entire object in one pass.
+#[derive(Debug, Clone)]
+pub struct FullObjectChecksum {
+    algorithm: ChecksumAlgorithm,
+    crc_algorithm: crc_fast::CrcAlgorithm,
+    width: usize,
+    crc: u64,
+    part_count: u64,
+    content_length: u64,
+}
+
+impl FullObjectChecksum {
+    /// Create a new `FullObjectChecksum` for the given CRC algorithm.
+    ///
+    /// Returns an error if `algorithm` isn't a CRC algorithm.
+    pub fn new(algorithm: ChecksumAlgorithm) -> Result<Self, CompositeChecksumError> {
+        let (crc_algorithm, width) = crc_params(algorithm)?;
+        Ok(Self {
+            algorithm,
+            crc_algorithm,
+            width,
+            // The CRC of no data, so that combining with the first part yields that part's CRC
+            crc: crc_fast::checksum(crc_algorithm, &[]),
+            part_count: 0,
+            content_length: 0,
+        })
+    }
+
+    /// Add the raw (not base64-encoded) CRC of the next part, along with the part's length in bytes.
+    pub fn add_part(
+        &mut self,
+        part_checksum: &[u8],
+        part_length: u64,
+    ) -> Result<(), CompositeChecksumError> {
+        let part_crc = crc_from_bytes(part_checksum, self.width)?;
+        self.crc = crc_fast::checksum_combine(self.crc_algorithm, self.crc, part_crc, part_length);
+        self.part_count += 1;
+        self.content_length += part_length;
+        Ok(())
+    }
+
+    /// The checksum algorithm used by this `FullObjectChecksum`.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    /// The number of parts added so far.
+    pub fn part_count(&self) -> u64 {
+        self.part_count
+    }
+
+    /// The total length in bytes of all parts added so far.
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// Return the raw checksum of all parts added so far.
+    pub fn finalize(&self) -> Bytes {
+        crc_to_bytes(self.crc, self.width)
+    }
+
+    /// Return the checksum of all parts added so far, base64-encoded as it appears in HTTP headers.
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.finalize())
+    }
+}
+
+/// Calculates a composite "checksum of checksums" from the checksums of each part of an object.
+///
+/// Parts must be added in order. Any checksum algorithm except the deprecated MD5 can be used, but it
+/// must be the same algorithm that was used to calculate the part checksums.
+pub struct CompositeChecksum {
+    algorithm: ChecksumAlgorithm,
+    checksum: Box<dyn HttpChecksum>,
+    part_count: u64,
+}
+
+impl fmt::Debug for CompositeChecksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompositeChecksum")
+            .field("algorithm", &self.algorithm)
+            .field("part_count", &self.part_count)
+            .finish()
+    }
+}
+
+impl CompositeChecksum {
+    /// Create a new `CompositeChecksum` for the given algorithm.
+    ///
+    /// Returns an error for [`ChecksumAlgorithm::Md5`], which has no checksum implementation.
+    pub fn new(algorithm: ChecksumAlgorithm) -> Result<Self, CompositeChecksumError> {
+        #[allow(deprecated)]
+        if algorithm == ChecksumAlgorithm::Md5 {
+            return Err(CompositeChecksumError::unsupported_composite_algorithm(
+                algorithm.as_str(),
+            ));
+        }
+        Ok(Self {
+            algorithm,
+            checksum: algorithm.into_impl(),
+            part_count: 0,
+        })
+    }
+
+    /// Add the raw (not base64-encoded) checksum of the next part.
+    pub fn add_part(&mut self, part_checksum: &[u8]) -> Result<(), CompositeChecksumError> {
+        let expected = crate::Checksum::size(&*self.checksum) as usize;
+        if part_checksum.len() != expected {
+            return Err(CompositeChecksumError::invalid_checksum_length(
+                expected,
+                part_checksum.len(),
+            ));
+        }
+        self.checksum.update(part_checksum);
+        self.part_count += 1;
+        Ok(())
+    }
+
+    /// The checksum algorithm used by this `CompositeChecksum`.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    /// The number of parts added so far.
+    pub fn part_count(&self) -> u64 {
+        self.part_count
+    }
+
+    /// Finish calculating the composite checksum.
+    pub fn finalize(self) -> CompositeChecksumValue {
+        CompositeChecksumValue {
+            checksum: self.checksum.finalize(),
+            part_count: self.part_count,
+        }
+    }
+}
+
+/// A composite checksum value, made up of a checksum of part checksums and the number of parts.
+///
+/// Its string form is `<base64-encoded checksum>-<part count>`, e.g. `1Fu2mQ==-2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositeChecksumValue {
+    checksum: Bytes,
+    part_count: u64,
+}
+
+impl CompositeChecksumValue {
+    /// Create a new `CompositeChecksumValue` from a raw checksum and a part count.
+    pub fn new(checksum: impl Into<Bytes>, part_count: u64) -> Self {
+        Self {
+            checksum: checksum.into(),
+            part_count,
+        }
+    }
+
+    /// The raw (not base64-encoded) checksum of part checksums.
+    pub fn checksum(&self) -> &Bytes {
+        &self.checksum
+    }
+
+    /// The number of parts that make up this checksum.
+    pub fn part_count(&self) -> u64 {
+        self.part_count
+    }
+}
+
+impl fmt::Display for CompositeChecksumValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", base64::encode(&self.checksum), self.part_count)
+    }
+}
+
+impl FromStr for CompositeChecksumValue {
+    type Err = CompositeChecksumError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || CompositeChecksumError::invalid_composite_value(value);
+        let (checksum, part_count) = value.rsplit_once('-').ok_or_else(invalid)?;
+        let part_count = part_count.parse::<u64>().map_err(|_| invalid())?;
+        let checksum = base64::decode(checksum).map_err(|_| invalid())?;
+        if checksum.is_empty() || part_count == 0 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            checksum: checksum.into(),
+            part_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{combine_crc, CompositeChecksum, CompositeChecksumValue, FullObjectChecksum};
+    use crate::ChecksumAlgorithm;
+    use bytes::Bytes;
+
+    const PARTS: [&[u8]; 3] = [
+        b"The quick brown fox ",
+        b"jumps over ",
+        b"the lazy dog and keeps on running",
+    ];
+
+    fn checksum(algorithm: ChecksumAlgorithm, data: &[u8]) -> Bytes {
+        let mut checksum = algorithm.into_impl();
+        checksum.update(data);
+        checksum.finalize()
+    }
+
+    #[test]
+    fn combine_crc_matches_checksum_of_concatenated_data() {
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+        ] {
+            let combined = combine_crc(
+                algorithm,
+                &checksum(algorithm, PARTS[0]),
+                &checksum(algorithm, PARTS[1]),
+                PARTS[1].len() as u64,
+            )
+            .unwrap();
+            assert_eq!(
+                checksum(algorithm, &[PARTS[0], PARTS[1]].concat()),
+                combined,
+                "{algorithm:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn full_object_checksum_matches_checksum_of_whole_object() {
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+        ] {
+            let mut full_object = FullObjectChecksum::new(algorithm).unwrap();
+            for part in PARTS {
+                full_object
+                    .add_part(&checksum(algorithm, part), part.len() as u64)
+                    .unwrap();
+            }
+            assert_eq!(3, full_object.part_count());
+            assert_eq!(PARTS.concat().len() as u64, full_object.content_length());
+            assert_eq!(
+                checksum(algorithm, &PARTS.concat()),
+                full_object.finalize(),
+                "{algorithm:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn full_object_checksum_of_no_parts_is_checksum_of_empty_data() {
+        let full_object = FullObjectChecksum::new(ChecksumAlgorithm::Crc32).unwrap();
+        assert_eq!("AAAAAA==", full_object.to_base64());
+    }
+
+    #[test]
+    fn full_object_checksum_rejects_non_crc_algorithms() {
+        let err = FullObjectChecksum::new(ChecksumAlgorithm::Sha256).unwrap_err();
+        assert!(err.to_string().contains("sha256"), "{err}");
+    }
+
+    #[test]
+    fn full_object_checksum_rejects_wrong_checksum_length() {
+        let mut full_object = FullObjectChecksum::new(ChecksumAlgorithm::Crc64Nvme).unwrap();
+        let err = full_object.add_part(&[0, 0, 0, 0], 4).unwrap_err();
+        assert!(err.to_string().contains("4 bytes"), "{err}");
+    }
+
+    #[test]
+    fn composite_checksum_is_checksum_of_part_checksums() {
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            let part_checksums: Vec<Bytes> =
+                PARTS.iter().map(|part| checksum(algorithm, part)).collect();
+            let mut composite = CompositeChecksum::new(algorithm).unwrap();
+            for part_checksum in &part_checksums {
+                composite.add_part(part_checksum).unwrap();
+            }
+            let value = composite.finalize();
+            assert_eq!(3, value.part_count());
+            assert_eq!(
+                &checksum(algorithm, &part_checksums.concat()),
+                value.checksum()
+            );
+        }
+    }
+
+    #[test]
+    fn composite_checksum_value_round_trips() {
+        // The CRC32 of the CRC32s of "hello " and "world"
+        let mut composite = CompositeChecksum::new(ChecksumAlgorithm::Crc32).unwrap();
+        composite
+            .add_part(&checksum(ChecksumAlgorithm::Crc32, b"hello "))
+            .unwrap();
+        composite
+            .add_part(&checksum(ChecksumAlgorithm::Crc32, b"world"))
+            .unwrap();
+        let value = composite.finalize();
+
+        let rendered = value.to_string();
+        assert_eq!("1Fu2mQ==-2", rendered);
+        assert_eq!(value, rendered.parse::<CompositeChecksumValue>().unwrap());
+    }
+
+    #[test]
+    fn composite_checksum_value_rejects_invalid_values() {
+        for invalid in [
+            "",
+            "AAAAAA==",
+            "AAAAAA==-",
+            "AAAAAA==-0",
+            "-3",
+            "!!!-3",
+            "AAAAAA==-x",
+        ] {
+            assert!(
+                invalid.parse::<CompositeChecksumValue>().is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn composite_checksum_rejects_md5() {
+        #[allow(deprecated)]
+        let err = CompositeChecksum::new(ChecksumAlgorithm::Md5).unwrap_err();
+        assert!(err.to_string().contains("md5"), "{err}");
+    }
+
+    #[test]
+    fn composite_checksum_rejects_wrong_checksum_length() {
+        let mut composite = CompositeChecksum::new(ChecksumAlgorithm::Sha256).unwrap();
+        assert!(composite.add_part(&[0, 0, 0, 0]).is_err());
+        assert_eq!(0, composite.part_count());
+    }
+}
@@ -29,10 +29,110 @@ impl fmt::Display for UnknownChecksumAlgorithmError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            r#"unknown checksum algorithm "{}", please pass a known algorithm name ("crc32", "crc32c", "sha1", "sha256", "md5")"#,
+            r#"unknown checksum algorithm "{}", please pass a known algorithm name ("crc32", "crc32c", "crc64nvme", "sha1", "sha256", "sha512", "xxhash64", "xxhash3", "md5")"#,
             self.checksum_algorithm
         )
     }
 }
 
 impl Error for UnknownChecksumAlgorithmError {}
+
+/// A checksum algorithm couldn't be registered because its name is taken by a built-in algorithm
+///
+/// See [`ChecksumRegistry`](crate::registry::ChecksumRegistry) for details.
+#[derive(Debug)]
+pub struct BuiltInChecksumAlgorithmError {
+    checksum_algorithm: String,
+}
+
+impl BuiltInChecksumAlgorithmError {
+    pub(crate) fn new(checksum_algorithm: impl Into<String>) -> Self {
+        Self {
+            checksum_algorithm: checksum_algorithm.into(),
+        }
+    }
+
+    /// The name of the checksum algorithm that couldn't be registered
+    pub fn checksum_algorithm(&self) -> &str {
+        &self.checksum_algorithm
+    }
+}
+
+impl fmt::Display for BuiltInChecksumAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#"checksum algorithm "{}" is built in and can't be replaced, please register the checksum under a different name"#,
+            self.checksum_algorithm
+        )
+    }
+}
+
+impl Error for BuiltInChecksumAlgorithmError {}
+
+/// An error that occurred while combining part checksums into a whole-object checksum.
+///
+/// See the [`composite`](crate::composite) module for details.
+#[derive(Debug)]
+pub struct CompositeChecksumError {
+    kind: CompositeChecksumErrorKind,
+}
+
+#[derive(Debug)]
+enum CompositeChecksumErrorKind {
+    UnsupportedAlgorithm(&'static str),
+    UnsupportedCompositeAlgorithm(&'static str),
+    InvalidChecksumLength { expected: usize, actual: usize },
+    InvalidCompositeValue(String),
+}
+
+impl CompositeChecksumError {
+    pub(crate) fn unsupported_algorithm(checksum_algorithm: &'static str) -> Self {
+        Self {
+            kind: CompositeChecksumErrorKind::UnsupportedAlgorithm(checksum_algorithm),
+        }
+    }
+
+    pub(crate) fn unsupported_composite_algorithm(checksum_algorithm: &'static str) -> Self {
+        Self {
+            kind: CompositeChecksumErrorKind::UnsupportedCompositeAlgorithm(checksum_algorithm),
+        }
+    }
+
+    pub(crate) fn invalid_checksum_length(expected: usize, actual: usize) -> Self {
+        Self {
+            kind: CompositeChecksumErrorKind::InvalidChecksumLength { expected, actual },
+        }
+    }
+
+    pub(crate) fn invalid_composite_value(value: impl Into<String>) -> Self {
+        Self {
+            kind: CompositeChecksumErrorKind::InvalidCompositeValue(value.into()),
+        }
+    }
+}
+
+impl fmt::Display for CompositeChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CompositeChecksumErrorKind::UnsupportedAlgorithm(checksum_algorithm) => write!(
+                f,
+                r#"checksum algorithm "{checksum_algorithm}" can't be combined, only CRC algorithms ("crc32", "crc32c", "crc64nvme") support full object checksums"#
+            ),
+            CompositeChecksumErrorKind::UnsupportedCompositeAlgorithm(checksum_algorithm) => write!(
+                f,
+                r#"checksum algorithm "{checksum_algorithm}" is deprecated and can't be used for composite checksums"#
+            ),
+            CompositeChecksumErrorKind::InvalidChecksumLength { expected, actual } => write!(
+                f,
+                "part checksum was {actual} bytes long but the checksum algorithm produces {expected} byte checksums"
+            ),
+            CompositeChecksumErrorKind::InvalidCompositeValue(value) => write!(
+                f,
+                r#""{value}" is not a valid composite checksum, expected a base64-encoded checksum followed by "-<part count>""#
+            ),
+        }
+    }
+}
+
+impl Error for CompositeChecksumError {}
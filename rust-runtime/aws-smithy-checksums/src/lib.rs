@@ -22,8 +22,12 @@ use bytes::Bytes;
 use std::{fmt::Debug, str::FromStr};
 
 pub mod body;
+pub mod composite;
 pub mod error;
 pub mod http;
+pub mod registry;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 
 // Valid checksum algorithm names
 pub const CRC_32_NAME: &str = "crc32";
@@ -31,6 +35,9 @@ pub const CRC_32_C_NAME: &str = "crc32c";
 pub const CRC_64_NVME_NAME: &str = "crc64nvme";
 pub const SHA_1_NAME: &str = "sha1";
 pub const SHA_256_NAME: &str = "sha256";
+pub const SHA_512_NAME: &str = "sha512";
+pub const XXHASH_64_NAME: &str = "xxhash64";
+pub const XXHASH_3_NAME: &str = "xxhash3";
 pub const MD5_NAME: &str = "md5";
 
 /// We only support checksum calculation and validation for these checksum algorithms.
@@ -45,6 +52,9 @@ pub enum ChecksumAlgorithm {
     Sha1,
     Sha256,
     Crc64Nvme,
+    Sha512,
+    XxHash64,
+    XxHash3,
 }
 
 impl FromStr for ChecksumAlgorithm {
@@ -56,6 +66,9 @@ impl FromStr for ChecksumAlgorithm {
     /// - "crc64nvme"
     /// - "sha1"
     /// - "sha256"
+    /// - "sha512"
+    /// - "xxhash64"
+    /// - "xxhash3"
     ///
     /// Passing an invalid name will return an error.
     fn from_str(checksum_algorithm: &str) -> Result<Self, Self::Err> {
@@ -72,6 +85,12 @@ impl FromStr for ChecksumAlgorithm {
             Ok(Self::Crc32)
         } else if checksum_algorithm.eq_ignore_ascii_case(CRC_64_NVME_NAME) {
             Ok(Self::Crc64Nvme)
+        } else if checksum_algorithm.eq_ignore_ascii_case(SHA_512_NAME) {
+            Ok(Self::Sha512)
+        } else if checksum_algorithm.eq_ignore_ascii_case(XXHASH_64_NAME) {
+            Ok(Self::XxHash64)
+        } else if checksum_algorithm.eq_ignore_ascii_case(XXHASH_3_NAME) {
+            Ok(Self::XxHash3)
         } else {
             Err(UnknownChecksumAlgorithmError::new(checksum_algorithm))
         }
@@ -89,6 +108,9 @@ impl ChecksumAlgorithm {
             Self::Md5 => Box::<Crc32>::default(),
             Self::Sha1 => Box::<Sha1>::default(),
             Self::Sha256 => Box::<Sha256>::default(),
+            Self::Sha512 => Box::<Sha512>::default(),
+            Self::XxHash64 => Box::<XxHash64>::default(),
+            Self::XxHash3 => Box::<XxHash3>::default(),
         }
     }
 
@@ -102,6 +124,9 @@ impl ChecksumAlgorithm {
             Self::Md5 => MD5_NAME,
             Self::Sha1 => SHA_1_NAME,
             Self::Sha256 => SHA_256_NAME,
+            Self::Sha512 => SHA_512_NAME,
+            Self::XxHash64 => XXHASH_64_NAME,
+            Self::XxHash3 => XXHASH_3_NAME,
         }
     }
 }
@@ -324,6 +349,125 @@ impl Checksum for Sha256 {
     }
 }
 
+#[derive(Debug, Default)]
+struct Sha512 {
+    hasher: sha2::Sha512,
+}
+
+impl Sha512 {
+    fn update(&mut self, bytes: &[u8]) {
+        use sha2::Digest;
+        self.hasher.update(bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        use sha2::Digest;
+        Bytes::copy_from_slice(self.hasher.finalize().as_ref())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        use sha2::Digest;
+        sha2::Sha512::output_size() as u64
+    }
+}
+
+impl Checksum for Sha512 {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes);
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
+#[derive(Clone)]
+struct XxHash64 {
+    hasher: xxhash_rust::xxh64::Xxh64,
+}
+
+impl Default for XxHash64 {
+    fn default() -> Self {
+        Self {
+            hasher: xxhash_rust::xxh64::Xxh64::new(0),
+        }
+    }
+}
+
+impl Debug for XxHash64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XxHash64").finish_non_exhaustive()
+    }
+}
+
+impl XxHash64 {
+    fn update(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        Bytes::copy_from_slice(self.hasher.digest().to_be_bytes().as_slice())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        8
+    }
+}
+
+impl Checksum for XxHash64 {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes)
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
+#[derive(Clone, Default)]
+struct XxHash3 {
+    hasher: xxhash_rust::xxh3::Xxh3Default,
+}
+
+impl Debug for XxHash3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XxHash3").finish_non_exhaustive()
+    }
+}
+
+impl XxHash3 {
+    fn update(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        Bytes::copy_from_slice(self.hasher.digest().to_be_bytes().as_slice())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        8
+    }
+}
+
+impl Checksum for XxHash3 {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes)
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Default)]
 struct Md5 {
@@ -368,9 +512,9 @@ mod tests {
     use super::{
         http::{
             CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, MD5_HEADER_NAME, SHA_1_HEADER_NAME,
-            SHA_256_HEADER_NAME,
+            SHA_256_HEADER_NAME, SHA_512_HEADER_NAME, XXHASH_3_HEADER_NAME, XXHASH_64_HEADER_NAME,
         },
-        Crc32, Crc32c, Md5, Sha1, Sha256,
+        Crc32, Crc32c, Md5, Sha1, Sha256, Sha512, XxHash3, XxHash64,
     };
 
     use crate::http::HttpChecksum;
@@ -465,6 +609,65 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_sha512_checksum() {
+        let mut checksum = Sha512::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(SHA_512_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0x0E1E21ECF105EC853D24D728867AD70613C21663A4693074B2A3619C1BD39D66B588C33723BB466C72424E80E3CA63C249078AB347BAB9428500E7EE43059D0D";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
+    #[test]
+    fn test_xxhash64_checksum() {
+        let mut checksum = XxHash64::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(XXHASH_64_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0xFA56F7EBF111F1BA";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
+    #[test]
+    fn test_xxhash3_checksum() {
+        let mut checksum = XxHash3::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(XXHASH_3_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0x8F0FA94A1FE96CC4";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
+    #[test]
+    fn test_checksum_algorithm_round_trips_through_str() {
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha256,
+            ChecksumAlgorithm::Sha512,
+            ChecksumAlgorithm::XxHash64,
+            ChecksumAlgorithm::XxHash3,
+        ] {
+            assert_eq!(algorithm, algorithm.as_str().parse().unwrap());
+            assert_eq!(
+                algorithm,
+                algorithm.as_str().to_uppercase().parse().unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_md5_checksum() {
         let mut checksum = Md5::default();
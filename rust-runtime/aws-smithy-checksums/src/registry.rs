@@ -0,0 +1,214 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A registry of user-defined checksum algorithms.
+//!
+//! [`ChecksumAlgorithm`] only knows about the checksum algorithms built into this crate. A
+//! [`ChecksumRegistry`] maps additional algorithm names to [`HttpChecksum`] implementations so that
+//! they can be resolved by name wherever a built-in algorithm could be.
+//!
+//! ```
+//! use aws_smithy_checksums::http::HttpChecksum;
+//! use aws_smithy_checksums::registry::ChecksumRegistry;
+//! use aws_smithy_checksums::Checksum;
+//! use bytes::Bytes;
+//!
+//! #[derive(Default)]
+//! struct ByteCount(u32);
+//!
+//! impl Checksum for ByteCount {
+//!     fn update(&mut self, bytes: &[u8]) {
+//!         self.0 += bytes.len() as u32;
+//!     }
+//!     fn finalize(self: Box<Self>) -> Bytes {
+//!         Bytes::copy_from_slice(&self.0.to_be_bytes())
+//!     }
+//!     fn size(&self) -> u64 {
+//!         4
+//!     }
+//! }
+//!
+//! impl HttpChecksum for ByteCount {
+//!     fn header_name(&self) -> &'static str {
+//!         "x-amz-checksum-bytecount"
+//!     }
+//! }
+//!
+//! let registry = ChecksumRegistry::new()
+//!     .with_checksum("bytecount", || Box::<ByteCount>::default())
+//!     .expect("bytecount is not a built-in algorithm");
+//!
+//! let checksum = registry.resolve("bytecount").unwrap();
+//! assert_eq!("x-amz-checksum-bytecount", checksum.header_name());
+//! // Built-in algorithms still resolve, but can't be replaced
+//! assert!(registry.resolve("crc32").is_ok());
+//! assert!(ChecksumRegistry::new()
+//!     .with_checksum("crc32", || Box::<ByteCount>::default())
+//!     .is_err());
+//! ```
+
+use crate::error::{BuiltInChecksumAlgorithmError, UnknownChecksumAlgorithmError};
+use crate::http::HttpChecksum;
+use crate::ChecksumAlgorithm;
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+type ChecksumFactory = Arc<dyn Fn() -> Box<dyn HttpChecksum> + Send + Sync>;
+
+/// A registry of checksum algorithms, resolvable by name.
+///
+/// Names are matched case-insensitively. Names that aren't registered fall back to the built-in
+/// [`ChecksumAlgorithm`]s. The names of built-in algorithms can't be registered, since clients
+/// apply their own defaults and metrics to built-in algorithms.
+///
+/// Clients look for a `ChecksumRegistry` in their config bag when calculating request checksums
+/// and validating response checksums, so it can be provided with a runtime plugin. Responses are
+/// validated against a registered algorithm whenever its header is present, even if the operation's
+/// model only lists built-in algorithms.
+#[derive(Clone, Default)]
+pub struct ChecksumRegistry {
+    checksums: BTreeMap<String, ChecksumFactory>,
+}
+
+impl fmt::Debug for ChecksumRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChecksumRegistry")
+            .field("checksums", &self.checksums.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Storable for ChecksumRegistry {
+    type Storer = StoreReplace<Self>;
+}
+
+impl ChecksumRegistry {
+    /// Create a new, empty `ChecksumRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a checksum algorithm under `name`.
+    ///
+    /// `factory` is called to create a new checksum calculator every time the algorithm is
+    /// resolved, e.g. once per request attempt. Returns an error if `name` is the name of a
+    /// built-in algorithm.
+    pub fn with_checksum(
+        mut self,
+        name: impl Into<String>,
+        factory: impl Fn() -> Box<dyn HttpChecksum> + Send + Sync + 'static,
+    ) -> Result<Self, BuiltInChecksumAlgorithmError> {
+        self.register_checksum(name, factory)?;
+        Ok(self)
+    }
+
+    /// Register a checksum algorithm under `name`.
+    ///
+    /// `factory` is called to create a new checksum calculator every time the algorithm is
+    /// resolved, e.g. once per request attempt. Returns an error if `name` is the name of a
+    /// built-in algorithm.
+    pub fn register_checksum(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn() -> Box<dyn HttpChecksum> + Send + Sync + 'static,
+    ) -> Result<&mut Self, BuiltInChecksumAlgorithmError> {
+        let name = name.into();
+        if ChecksumAlgorithm::from_str(&name).is_ok() {
+            return Err(BuiltInChecksumAlgorithmError::new(name));
+        }
+        self.checksums
+            .insert(name.to_ascii_lowercase(), Arc::new(factory));
+        Ok(self)
+    }
+
+    /// Returns true if a checksum algorithm was registered under `name`.
+    ///
+    /// This doesn't consider built-in algorithms.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.checksums.contains_key(&name.to_ascii_lowercase())
+    }
+
+    /// The names of all registered checksum algorithms, in lowercase and sorted alphabetically.
+    pub fn registered_names(&self) -> impl Iterator<Item = &str> {
+        self.checksums.keys().map(String::as_str)
+    }
+
+    /// Return a new checksum calculator for the algorithm called `name`.
+    ///
+    /// Registered algorithms are checked first, then built-in algorithms. If neither match, an
+    /// error is returned.
+    pub fn resolve(
+        &self,
+        name: &str,
+    ) -> Result<Box<dyn HttpChecksum>, UnknownChecksumAlgorithmError> {
+        match self.checksums.get(&name.to_ascii_lowercase()) {
+            Some(factory) => Ok(factory()),
+            None => ChecksumAlgorithm::from_str(name).map(ChecksumAlgorithm::into_impl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChecksumRegistry;
+    use crate::http::CRC_32_HEADER_NAME;
+    use crate::test_util::ByteCount;
+    use crate::ChecksumAlgorithm;
+    use bytes::Bytes;
+
+    #[test]
+    fn resolves_registered_checksums_case_insensitively() {
+        let registry = ChecksumRegistry::new()
+            .with_checksum("ByteCount", || Box::<ByteCount>::default())
+            .unwrap();
+        assert!(registry.is_registered("BYTECOUNT"));
+        assert_eq!(
+            vec!["bytecount"],
+            registry.registered_names().collect::<Vec<_>>()
+        );
+
+        let mut checksum = registry.resolve("bytecount").unwrap();
+        assert_eq!("x-amz-checksum-bytecount", checksum.header_name());
+        checksum.update(b"Hello world");
+        assert_eq!(Bytes::from_static(&[0, 0, 0, 11]), checksum.finalize());
+    }
+
+    #[test]
+    fn falls_back_to_built_in_checksums() {
+        let registry = ChecksumRegistry::new();
+        assert!(!registry.is_registered("crc32"));
+        let checksum = registry.resolve("CRC32").unwrap();
+        assert_eq!(CRC_32_HEADER_NAME, checksum.header_name());
+    }
+
+    #[test]
+    fn built_in_checksums_cant_be_registered() {
+        let mut registry = ChecksumRegistry::new();
+        for name in [ChecksumAlgorithm::Crc32.as_str(), "CRC32C", "xxhash3"] {
+            let err = registry
+                .register_checksum(name, || Box::<ByteCount>::default())
+                .expect_err("it should error");
+            assert_eq!(name, err.checksum_algorithm());
+        }
+        assert_eq!(0, registry.registered_names().count());
+        assert_eq!(
+            CRC_32_HEADER_NAME,
+            registry.resolve("crc32").unwrap().header_name()
+        );
+    }
+
+    #[test]
+    fn errors_for_unknown_checksums() {
+        let err = ChecksumRegistry::new()
+            .resolve("made-up")
+            .err()
+            .expect("it should error");
+        assert_eq!("made-up", err.checksum_algorithm());
+    }
+}
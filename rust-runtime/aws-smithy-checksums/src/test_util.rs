@@ -0,0 +1,38 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Checksum implementations for testing.
+
+use crate::http::HttpChecksum;
+use crate::Checksum;
+use bytes::Bytes;
+
+/// A checksum of the number of bytes it was updated with, encoded as a big-endian `u32`.
+///
+/// Its header name is `x-amz-checksum-bytecount`, so it can be registered in a
+/// [`ChecksumRegistry`](crate::registry::ChecksumRegistry) to test user-defined checksum
+/// algorithms.
+#[derive(Debug, Default)]
+pub struct ByteCount(u32);
+
+impl Checksum for ByteCount {
+    fn update(&mut self, bytes: &[u8]) {
+        self.0 += bytes.len() as u32;
+    }
+
+    fn finalize(self: Box<Self>) -> Bytes {
+        Bytes::copy_from_slice(&self.0.to_be_bytes())
+    }
+
+    fn size(&self) -> u64 {
+        4
+    }
+}
+
+impl HttpChecksum for ByteCount {
+    fn header_name(&self) -> &'static str {
+        "x-amz-checksum-bytecount"
+    }
+}
@@ -3,12 +3,11 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-//! HTTP body-wrappers that perform request compression
+//! HTTP body-wrappers that perform request compression and response decompression
 
-// Putting this in a `mod` since I expect we'll have to handle response
-// decompression some day.
 /// Functionality for compressing an HTTP request body.
 pub mod compress {
+    use crate::CompressStream;
     use aws_smithy_types::body::SdkBody;
     use pin_project_lite::pin_project;
 
@@ -17,10 +16,16 @@ pub mod compress {
         ///
         /// Compression options may disable request compression for small data payload, or entirely.
         /// Additionally, some services may not support compression.
+        ///
+        /// When streamed, all data frames are compressed into a single compressed stream that is
+        /// finished before any trailers are sent.
         pub struct CompressedBody<InnerBody, CompressionImpl> {
             #[pin]
             body: InnerBody,
             compress_request: CompressionImpl,
+            stream_compressor: Option<Box<dyn CompressStream>>,
+            is_compression_finished: bool,
+            trailers: Option<http_1x::HeaderMap>,
             is_end_stream: bool,
         }
     }
@@ -31,6 +36,9 @@ pub mod compress {
             Self {
                 body,
                 compress_request,
+                stream_compressor: None,
+                is_compression_finished: false,
+                trailers: None,
                 is_end_stream: false,
             }
         }
@@ -40,8 +48,10 @@ pub mod compress {
     pub mod http_body_1_x {
         use crate::body::compress::CompressedBody;
         use crate::http::CompressRequest;
+        use crate::CompressStream;
         use aws_smithy_runtime_api::box_error::BoxError;
         use aws_smithy_types::body::SdkBody;
+        use bytes::Bytes;
         use http_body_1x::{Body, Frame, SizeHint};
         use std::pin::Pin;
         use std::task::{ready, Context, Poll};
@@ -54,27 +64,47 @@ pub mod compress {
                 mut self: Pin<&mut Self>,
                 cx: &mut Context<'_>,
             ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-                let this = self.as_mut().project();
-                Poll::Ready(match ready!(this.body.poll_frame(cx)) {
-                    Some(Ok(f)) => {
-                        if f.is_data() {
-                            let d = f.into_data().expect("we checked for data first");
-                            let mut out = Vec::new();
-                            this.compress_request.compress_bytes(&d, &mut out)?;
-                            Some(Ok(Frame::data(out.into())))
-                        } else if f.is_trailers() {
-                            // Trailers don't get compressed.
-                            Some(Ok(f))
-                        } else {
-                            unreachable!("Frame is either data or trailers")
-                        }
+                let mut this = self.as_mut().project();
+                loop {
+                    // Trailers are held back until the compressed stream has been finished.
+                    if let Some(trailers) = this.trailers.take() {
+                        return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                    }
+                    if *this.is_end_stream {
+                        return Poll::Ready(None);
                     }
-                    None => {
-                        *this.is_end_stream = true;
-                        None
+
+                    let compressed = match ready!(this.body.as_mut().poll_frame(cx)) {
+                        Some(Ok(f)) => match f.into_data() {
+                            Ok(data) => this
+                                .stream_compressor
+                                .get_or_insert_with(|| this.compress_request.stream_compressor())
+                                .compress_chunk(&data)?,
+                            Err(f) => {
+                                // Trailers don't get compressed.
+                                *this.trailers = f.into_trailers().ok();
+                                finish_compression(
+                                    this.compress_request.as_ref(),
+                                    this.stream_compressor,
+                                    this.is_compression_finished,
+                                )?
+                            }
+                        },
+                        Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                        None => {
+                            *this.is_end_stream = true;
+                            finish_compression(
+                                this.compress_request.as_ref(),
+                                this.stream_compressor,
+                                this.is_compression_finished,
+                            )?
+                        }
+                    };
+                    // Encoders may buffer input, so there isn't always output to send.
+                    if !compressed.is_empty() {
+                        return Poll::Ready(Some(Ok(Frame::data(compressed))));
                     }
-                    other => other,
-                })
+                }
             }
 
             fn is_end_stream(&self) -> bool {
@@ -87,6 +117,21 @@ pub mod compress {
                 SizeHint::default()
             }
         }
+
+        /// Finish the compressed stream exactly once, returning any remaining compressed output.
+        fn finish_compression(
+            compress_request: &dyn CompressRequest,
+            stream_compressor: &mut Option<Box<dyn CompressStream>>,
+            is_compression_finished: &mut bool,
+        ) -> Result<Bytes, BoxError> {
+            if *is_compression_finished {
+                return Ok(Bytes::new());
+            }
+            *is_compression_finished = true;
+            stream_compressor
+                .get_or_insert_with(|| compress_request.stream_compressor())
+                .finish()
+        }
         impl CompressedBody<SdkBody, Box<dyn CompressRequest>> {
             /// Consumes this `CompressedBody` and returns an [`SdkBody`] containing the compressed data.
             ///
@@ -104,13 +149,109 @@ pub mod compress {
     }
 }
 
+/// Functionality for decompressing an HTTP response body.
+pub mod decompress {
+    use crate::DecompressStream;
+    use aws_smithy_types::body::SdkBody;
+    use pin_project_lite::pin_project;
+
+    pin_project! {
+        /// A `Body` that decompresses its data with a `DecompressStream` implementor.
+        ///
+        /// Data is decompressed as it's streamed, so the inner body always observes the
+        /// compressed bytes as they were sent over the wire.
+        pub struct DecompressedBody<InnerBody> {
+            #[pin]
+            body: InnerBody,
+            decompress_stream: Box<dyn DecompressStream>,
+            trailers: Option<http_1x::HeaderMap>,
+            is_end_stream: bool,
+        }
+    }
+
+    impl DecompressedBody<SdkBody> {
+        /// Given an [`SdkBody`] and a `Box<dyn DecompressStream>`, create a new `DecompressedBody<SdkBody>`.
+        pub fn new(body: SdkBody, decompress_stream: Box<dyn DecompressStream>) -> Self {
+            Self {
+                body,
+                decompress_stream,
+                trailers: None,
+                is_end_stream: false,
+            }
+        }
+    }
+
+    /// Support for the `http-body-1-0` and `http-1-0` crates.
+    pub mod http_body_1_x {
+        use crate::body::decompress::DecompressedBody;
+        use aws_smithy_types::body::SdkBody;
+        use http_body_1x::{Body, Frame, SizeHint};
+        use std::pin::Pin;
+        use std::task::{ready, Context, Poll};
+
+        impl Body for DecompressedBody<SdkBody> {
+            type Data = bytes::Bytes;
+            type Error = aws_smithy_types::body::Error;
+
+            fn poll_frame(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+                let mut this = self.as_mut().project();
+                loop {
+                    if let Some(trailers) = this.trailers.take() {
+                        return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+                    }
+                    if *this.is_end_stream {
+                        return Poll::Ready(None);
+                    }
+
+                    let decompressed = match ready!(this.body.as_mut().poll_frame(cx)) {
+                        Some(Ok(f)) => match f.into_data() {
+                            Ok(data) => this.decompress_stream.decompress_chunk(&data)?,
+                            Err(f) => {
+                                // Trailers don't get decompressed.
+                                *this.trailers = f.into_trailers().ok();
+                                this.decompress_stream.finish()?
+                            }
+                        },
+                        Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                        None => {
+                            *this.is_end_stream = true;
+                            this.decompress_stream.finish()?
+                        }
+                    };
+                    // Decoders may need more input before producing output.
+                    if !decompressed.is_empty() {
+                        return Poll::Ready(Some(Ok(Frame::data(decompressed))));
+                    }
+                }
+            }
+
+            fn is_end_stream(&self) -> bool {
+                self.is_end_stream
+            }
+
+            fn size_hint(&self) -> SizeHint {
+                // We can't return a hint because we don't know how large
+                // the decompressed data will be
+                SizeHint::default()
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::body::compress::CompressedBody;
+    use crate::body::decompress::DecompressedBody;
     use crate::{CompressionAlgorithm, CompressionOptions};
     use aws_smithy_types::body::SdkBody;
-    use bytes::Buf;
+    use bytes::{Buf, Bytes};
     use bytes_utils::SegmentedBuf;
+    use flate2::read::MultiGzDecoder;
+    use http_body_1x::Frame;
+    use http_body_util::StreamBody;
     use std::io::Read;
     const UNCOMPRESSED_INPUT: &[u8] = b"hello world";
     const COMPRESSED_OUTPUT: &[u8] = &[
@@ -151,4 +292,57 @@ mod test {
         // Verify data is compressed as expected
         assert_eq!(COMPRESSED_OUTPUT, actual_output);
     }
+
+    #[tokio::test]
+    async fn test_streaming_body_is_compressed_as_a_single_stream() {
+        let mut trailers = http_1x::HeaderMap::new();
+        trailers.insert("x-amz-checksum-crc32", "AAAAAA==".parse().unwrap());
+        let frames: Vec<Result<Frame<Bytes>, std::convert::Infallible>> = vec![
+            Ok(Frame::data(Bytes::from_static(b"hello "))),
+            Ok(Frame::data(Bytes::from_static(b"streaming "))),
+            Ok(Frame::data(Bytes::from_static(b"world"))),
+            Ok(Frame::trailers(trailers.clone())),
+        ];
+        let body = SdkBody::from_body_1_x(StreamBody::new(futures_util::stream::iter(frames)));
+        let compress_request =
+            CompressionAlgorithm::Gzip.into_impl_http_body_1_x(&CompressionOptions::default());
+        let collected = CompressedBody::new(body, compress_request)
+            .collect()
+            .await
+            .expect("body is valid");
+
+        // Trailers are passed through untouched, after the compressed data
+        assert_eq!(Some(&trailers), collected.trailers());
+        let mut actual_output = String::new();
+        MultiGzDecoder::new(collected.to_bytes().reader())
+            .read_to_string(&mut actual_output)
+            .unwrap();
+        assert_eq!("hello streaming world", actual_output);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_body_is_decompressed() {
+        let chunks: Vec<Result<Frame<Bytes>, std::convert::Infallible>> = COMPRESSED_OUTPUT
+            .chunks(5)
+            .map(|chunk| Ok(Frame::data(Bytes::copy_from_slice(chunk))))
+            .collect();
+        let body = SdkBody::from_body_1_x(StreamBody::new(futures_util::stream::iter(chunks)));
+        let decompressed =
+            DecompressedBody::new(body, CompressionAlgorithm::Gzip.stream_decompressor())
+                .collect()
+                .await
+                .expect("body is valid")
+                .to_bytes();
+
+        assert_eq!(UNCOMPRESSED_INPUT, decompressed);
+    }
+
+    #[tokio::test]
+    async fn test_truncated_body_fails_to_decompress() {
+        let body = SdkBody::from(&COMPRESSED_OUTPUT[..COMPRESSED_OUTPUT.len() - 4]);
+        DecompressedBody::new(body, CompressionAlgorithm::Gzip.stream_decompressor())
+            .collect()
+            .await
+            .expect_err("truncated body must fail");
+    }
 }
@@ -0,0 +1,217 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::http::CompressRequest;
+use crate::{
+    decompress_error, Compress, CompressStream, CompressionOptions, DecompressStream,
+    DecompressedBuffer,
+};
+use aws_smithy_runtime_api::box_error::BoxError;
+use brotli::{CompressorWriter, DecompressorWriter};
+use bytes::Bytes;
+use std::io::prelude::*;
+
+/// Size of the internal buffer used by the Brotli encoder.
+const BUFFER_SIZE: usize = 4096;
+/// Base-2 logarithm of the sliding window size. `22` is the Brotli default.
+const LG_WINDOW_SIZE: u32 = 22;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Brotli {
+    quality: u32,
+}
+
+impl Brotli {
+    fn encoder<W: Write>(&self, writer: W) -> CompressorWriter<W> {
+        CompressorWriter::new(writer, BUFFER_SIZE, self.quality, LG_WINDOW_SIZE)
+    }
+
+    fn compress_bytes(&self, bytes: &[u8], writer: impl Write) -> Result<(), BoxError> {
+        let mut encoder = self.encoder(writer);
+        encoder.write_all(bytes)?;
+        // Brotli only finishes the stream when the encoder is consumed.
+        encoder.into_inner();
+
+        Ok(())
+    }
+}
+
+impl Compress for Brotli {
+    fn compress_bytes(&mut self, bytes: &[u8], writer: &mut dyn Write) -> Result<(), BoxError> {
+        Brotli::compress_bytes(self, bytes, writer)
+    }
+}
+
+impl CompressRequest for Brotli {
+    fn header_value(&self) -> http_1x::HeaderValue {
+        http_1x::HeaderValue::from_static("br")
+    }
+
+    fn stream_compressor(&self) -> Box<dyn CompressStream> {
+        Box::new(BrotliStream {
+            encoder: Some(self.encoder(Vec::new())),
+        })
+    }
+}
+
+/// Compresses a stream of chunks into a single Brotli stream.
+///
+/// Brotli streams can't be concatenated, so unlike gzip, compressing each chunk
+/// independently would produce an undecodable body.
+struct BrotliStream {
+    encoder: Option<CompressorWriter<Vec<u8>>>,
+}
+
+impl CompressStream for BrotliStream {
+    fn compress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        let encoder = self
+            .encoder
+            .as_mut()
+            .ok_or("brotli stream was already finished")?;
+        encoder.write_all(bytes)?;
+        Ok(std::mem::take(encoder.get_mut()).into())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        match self.encoder.take() {
+            Some(encoder) => Ok(encoder.into_inner().into()),
+            None => Ok(Bytes::new()),
+        }
+    }
+}
+
+/// Decompresses a stream of chunks containing a single Brotli stream.
+#[derive(Default)]
+pub(crate) struct BrotliDecompressStream {
+    decoder: Option<DecompressorWriter<DecompressedBuffer>>,
+    limit: Option<usize>,
+    is_finished: bool,
+}
+
+impl BrotliDecompressStream {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+}
+
+impl DecompressStream for BrotliDecompressStream {
+    fn decompress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        if self.is_finished {
+            return Err("brotli stream was already finished".into());
+        }
+        let limit = self.limit;
+        let decoder = self.decoder.get_or_insert_with(|| {
+            DecompressorWriter::new(DecompressedBuffer::new(limit), BUFFER_SIZE)
+        });
+        decoder.write_all(bytes).map_err(decompress_error)?;
+        Ok(decoder.get_mut().take())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        self.is_finished = true;
+        // An empty body has nothing to decompress.
+        match self.decoder.take() {
+            Some(mut decoder) => {
+                decoder.close().map_err(decompress_error)?;
+                Ok(decoder.get_mut().take())
+            }
+            None => Ok(Bytes::new()),
+        }
+    }
+}
+
+impl From<&CompressionOptions> for Brotli {
+    fn from(options: &CompressionOptions) -> Self {
+        Brotli {
+            quality: options.level,
+        }
+    }
+}
+
+impl From<CompressionOptions> for Brotli {
+    fn from(options: CompressionOptions) -> Self {
+        Brotli::from(&options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Brotli, BrotliDecompressStream};
+    use crate::http::CompressRequest;
+    use crate::{CompressionOptions, DecompressStream};
+    use pretty_assertions::assert_eq;
+    use std::io::Read;
+
+    fn gettysburg_address() -> &'static [u8] {
+        include_bytes!("../test-data/gettysburg_address.txt")
+    }
+
+    fn decompress(compressed: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        brotli::Decompressor::new(compressed, 4096)
+            .read_to_end(&mut out)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn test_brotli_compression() {
+        let brotli = Brotli::from(&CompressionOptions::default());
+        let mut compressed_output = Vec::new();
+        brotli
+            .compress_bytes(gettysburg_address(), &mut compressed_output)
+            .expect("compression succeeds");
+        assert!(compressed_output.len() < gettysburg_address().len());
+
+        assert_eq!(gettysburg_address(), decompress(&compressed_output));
+    }
+
+    #[test]
+    fn test_brotli_stream_compression() {
+        let brotli = Brotli::from(&CompressionOptions::default());
+        let mut stream = brotli.stream_compressor();
+        let mut compressed_output = Vec::new();
+        for chunk in gettysburg_address().chunks(100) {
+            compressed_output.extend_from_slice(&stream.compress_chunk(chunk).unwrap());
+        }
+        compressed_output.extend_from_slice(&stream.finish().unwrap());
+
+        assert_eq!(gettysburg_address(), decompress(&compressed_output));
+    }
+
+    #[test]
+    fn test_brotli_stream_decompression() {
+        let mut compressed = Vec::new();
+        Brotli::from(&CompressionOptions::default())
+            .compress_bytes(gettysburg_address(), &mut compressed)
+            .unwrap();
+
+        let mut stream = BrotliDecompressStream::default();
+        let mut uncompressed_actual = Vec::new();
+        for chunk in compressed.chunks(100) {
+            uncompressed_actual.extend_from_slice(&stream.decompress_chunk(chunk).unwrap());
+        }
+        uncompressed_actual.extend_from_slice(&stream.finish().unwrap());
+
+        assert_eq!(gettysburg_address(), uncompressed_actual.as_slice());
+    }
+
+    #[test]
+    fn test_brotli_stream_decompression_detects_truncation() {
+        let mut compressed = Vec::new();
+        Brotli::from(&CompressionOptions::default())
+            .compress_bytes(gettysburg_address(), &mut compressed)
+            .unwrap();
+
+        let mut stream = BrotliDecompressStream::default();
+        stream
+            .decompress_chunk(&compressed[..compressed.len() - 10])
+            .unwrap();
+        stream.finish().expect_err("truncated stream must fail");
+    }
+}
@@ -0,0 +1,9 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client interceptors for request compression and response decompression.
+
+pub mod request_compression;
+pub mod response_decompression;
@@ -0,0 +1,457 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client interceptor for Smithy [`@requestCompression`][spec].
+//!
+//! [spec]: https://smithy.io/2.0/spec/behavior-traits.html#requestcompression-trait
+
+use crate::body::compress::CompressedBody;
+use crate::http::CompressRequest;
+use crate::{CompressionAlgorithm, CompressionOptions};
+use aws_smithy_runtime::client::sdk_feature::SmithySdkFeature;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextMut,
+};
+use aws_smithy_runtime_api::client::interceptors::{dyn_dispatch_hint, Intercept};
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::{ConfigBag, Layer, Storable, StoreReplace};
+use aws_smithy_types::error::operation::BuildError;
+use std::{fmt, mem};
+
+#[derive(Debug)]
+struct RequestCompressionInterceptorState {
+    options: Option<CompressionOptions>,
+    algorithm: Option<CompressionAlgorithm>,
+}
+
+impl Storable for RequestCompressionInterceptorState {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Interceptor for Smithy [`@requestCompression`][spec].
+///
+/// Requests are compressed with the configured [`RequestCompressionAlgorithm`] if the operation
+/// supports it, and with the first algorithm the operation supports otherwise. Compression can be
+/// turned off with [`DisableRequestCompression`], and bodies smaller than
+/// [`RequestMinCompressionSizeBytes`] are sent as they are.
+///
+/// [spec]: https://smithy.io/2.0/spec/behavior-traits.html#requestcompression-trait
+pub struct RequestCompressionInterceptor {
+    supported_algorithms: Vec<CompressionAlgorithm>,
+}
+
+impl fmt::Debug for RequestCompressionInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestCompressionInterceptor")
+            .field("supported_algorithms", &self.supported_algorithms)
+            .finish()
+    }
+}
+
+impl RequestCompressionInterceptor {
+    /// Create a new `RequestCompressionInterceptor` for an operation supporting the given
+    /// algorithms, in order of preference.
+    pub fn new(supported_algorithms: impl IntoIterator<Item = CompressionAlgorithm>) -> Self {
+        Self {
+            supported_algorithms: supported_algorithms.into_iter().collect(),
+        }
+    }
+
+    /// Select the algorithm to compress with.
+    ///
+    /// The configured [`RequestCompressionAlgorithm`] is used if the operation supports it.
+    /// Otherwise, the first algorithm supported by the operation is used.
+    fn select_algorithm(&self, cfg: &ConfigBag) -> Option<CompressionAlgorithm> {
+        let preferred = cfg.load::<RequestCompressionAlgorithm>().map(|it| it.0);
+        preferred
+            .filter(|preferred| self.supported_algorithms.contains(preferred))
+            .or_else(|| self.supported_algorithms.first().copied())
+    }
+}
+
+/// The [`SmithySdkFeature`] recorded when a request is compressed with `algorithm`.
+fn sdk_feature(algorithm: CompressionAlgorithm) -> Option<SmithySdkFeature> {
+    match algorithm {
+        CompressionAlgorithm::Gzip => Some(SmithySdkFeature::GzipRequestCompression),
+        #[cfg(feature = "zstd")]
+        CompressionAlgorithm::Zstd => Some(SmithySdkFeature::ZstdRequestCompression),
+        #[cfg(feature = "brotli")]
+        CompressionAlgorithm::Brotli => Some(SmithySdkFeature::BrotliRequestCompression),
+    }
+}
+
+#[dyn_dispatch_hint]
+impl Intercept for RequestCompressionInterceptor {
+    fn name(&self) -> &'static str {
+        "RequestCompressionInterceptor"
+    }
+
+    fn read_before_execution(
+        &self,
+        _context: &BeforeSerializationInterceptorContextRef<'_>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let disable_request_compression = cfg
+            .load::<DisableRequestCompression>()
+            .cloned()
+            .unwrap_or_default();
+        let request_min_compression_size_bytes = cfg
+            .load::<RequestMinCompressionSizeBytes>()
+            .cloned()
+            .unwrap_or_default();
+        let options = CompressionOptions::default()
+            .with_min_compression_size_bytes(request_min_compression_size_bytes.0)?
+            .with_enabled(!disable_request_compression.0);
+
+        let mut layer = Layer::new("RequestCompressionInterceptor");
+        layer.store_put(RequestCompressionInterceptorState {
+            options: Some(options),
+            algorithm: self.select_algorithm(cfg),
+        });
+
+        cfg.push_layer(layer);
+
+        Ok(())
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let state = cfg
+            .load::<RequestCompressionInterceptorState>()
+            .expect("set in `read_before_execution`");
+
+        let options = state.options.clone().unwrap();
+        let request = context.request_mut();
+
+        // Don't wrap a body if compression is disabled.
+        if !options.is_enabled() {
+            tracing::trace!("request compression is disabled and will not be applied");
+            return Ok(());
+        }
+
+        // Don't wrap a body if none of the operation's encodings are supported.
+        let Some(algorithm) = state.algorithm else {
+            tracing::trace!(
+                "no supported request compression algorithm; request will not be compressed"
+            );
+            return Ok(());
+        };
+
+        // Don't wrap a body if it's below the minimum size
+        //
+        // Because compressing small amounts of data can actually increase its size,
+        // we check to see if the data is big enough to make compression worthwhile.
+        let size_hint = http_body_1x::Body::size_hint(request.body()).exact();
+        if let Some(known_size) = size_hint {
+            if known_size < options.min_compression_size_bytes() as u64 {
+                tracing::trace!(
+                    min_compression_size_bytes = options.min_compression_size_bytes(),
+                    known_size,
+                    "request body is below minimum size and will not be compressed"
+                );
+                return Ok(());
+            }
+            tracing::trace!("compressing sized request body...");
+        } else {
+            tracing::trace!("compressing unsized request body...");
+        }
+
+        wrap_request_body_in_compressed_body(request, algorithm.into_impl_http_body_1_x(&options))?;
+        if let Some(feature) = sdk_feature(algorithm) {
+            cfg.interceptor_state()
+                .store_append::<SmithySdkFeature>(feature);
+        }
+
+        Ok(())
+    }
+}
+
+fn wrap_request_body_in_compressed_body(
+    request: &mut HttpRequest,
+    request_compress_impl: Box<dyn CompressRequest>,
+) -> Result<(), BuildError> {
+    request.headers_mut().append(
+        request_compress_impl.header_name(),
+        request_compress_impl.header_value(),
+    );
+    let mut body = {
+        let body = mem::replace(request.body_mut(), SdkBody::taken());
+
+        if body.is_streaming() {
+            request
+                .headers_mut()
+                .remove(http_1x::header::CONTENT_LENGTH);
+            body.map(move |body| {
+                let body = CompressedBody::new(body, request_compress_impl.clone());
+                SdkBody::from_body_1_x(body)
+            })
+        } else {
+            let body = CompressedBody::new(body, request_compress_impl.clone());
+            let body = body.into_compressed_sdk_body().map_err(BuildError::other)?;
+
+            let content_length = body.content_length().expect("this payload is in-memory");
+            request
+                .headers_mut()
+                .insert(http_1x::header::CONTENT_LENGTH, content_length.to_string());
+
+            body
+        }
+    };
+    mem::swap(request.body_mut(), &mut body);
+
+    Ok(())
+}
+
+/// Whether request compression is disabled. Requests are compressed by default.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DisableRequestCompression(pub bool);
+
+impl From<bool> for DisableRequestCompression {
+    fn from(value: bool) -> Self {
+        DisableRequestCompression(value)
+    }
+}
+
+impl Storable for DisableRequestCompression {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The minimum size, in bytes, of a request body for it to be compressed. The default is `10_240`.
+#[derive(Debug, Copy, Clone)]
+pub struct RequestMinCompressionSizeBytes(pub u32);
+
+impl Default for RequestMinCompressionSizeBytes {
+    fn default() -> Self {
+        RequestMinCompressionSizeBytes(10240)
+    }
+}
+
+impl From<u32> for RequestMinCompressionSizeBytes {
+    fn from(value: u32) -> Self {
+        RequestMinCompressionSizeBytes(value)
+    }
+}
+
+impl Storable for RequestMinCompressionSizeBytes {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The preferred algorithm for request compression.
+///
+/// It is only used for operations whose `@requestCompression` trait lists it.
+#[derive(Debug, Copy, Clone)]
+pub struct RequestCompressionAlgorithm(pub CompressionAlgorithm);
+
+impl From<CompressionAlgorithm> for RequestCompressionAlgorithm {
+    fn from(value: CompressionAlgorithm) -> Self {
+        RequestCompressionAlgorithm(value)
+    }
+}
+
+impl Storable for RequestCompressionAlgorithm {
+    type Storer = StoreReplace<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        wrap_request_body_in_compressed_body, RequestCompressionInterceptor,
+        RequestMinCompressionSizeBytes,
+    };
+    use crate::{CompressionAlgorithm, CompressionOptions};
+    use aws_smithy_runtime::client::sdk_feature::SmithySdkFeature;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::{ConfigBag, Layer};
+    use http_body_util::BodyExt;
+
+    const UNCOMPRESSED_INPUT: &[u8] = b"hello world";
+    const COMPRESSED_OUTPUT: &[u8] = &[
+        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 203, 72, 205, 201, 201, 87, 40, 207, 47, 202, 73, 1, 0,
+        133, 17, 74, 13, 11, 0, 0, 0,
+    ];
+
+    #[tokio::test]
+    async fn test_compressed_body_is_retryable() {
+        let mut request: HttpRequest = http_1x::Request::builder()
+            .body(SdkBody::retryable(move || {
+                SdkBody::from(UNCOMPRESSED_INPUT)
+            }))
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        // ensure original SdkBody is retryable
+        let mut body = request.body().try_clone().unwrap();
+        let mut body_data = Vec::new();
+        while let Some(Ok(frame)) = body.frame().await {
+            let data = frame.into_data().expect("Data frame");
+            body_data.extend_from_slice(&data)
+        }
+        // Not yet wrapped, should still be the same as UNCOMPRESSED_INPUT.
+        assert_eq!(UNCOMPRESSED_INPUT, body_data);
+
+        let compression_algorithm = CompressionAlgorithm::Gzip;
+        let compression_options = CompressionOptions::default()
+            .with_min_compression_size_bytes(0)
+            .unwrap();
+
+        wrap_request_body_in_compressed_body(
+            &mut request,
+            compression_algorithm.into_impl_http_body_1_x(&compression_options),
+        )
+        .unwrap();
+
+        // ensure again that wrapped SdkBody is retryable
+        let mut body = request.body().try_clone().expect("body is retryable");
+        let mut body_data = Vec::new();
+        while let Some(Ok(frame)) = body.frame().await {
+            let data = frame.into_data().expect("Data frame");
+            body_data.extend_from_slice(&data)
+        }
+
+        // Since this body was wrapped, the output should be compressed data
+        assert_ne!(UNCOMPRESSED_INPUT, body_data.as_slice());
+        assert_eq!(COMPRESSED_OUTPUT, body_data.as_slice());
+    }
+
+    fn context() -> InterceptorContext {
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.enter_serialization_phase();
+        context.set_request(
+            http_1x::Request::builder()
+                .body(SdkBody::from(UNCOMPRESSED_INPUT))
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let _ = context.take_input();
+        context.enter_before_transmit_phase();
+        context
+    }
+
+    fn compress(sut: &RequestCompressionInterceptor, cfg: &mut ConfigBag) -> HttpRequest {
+        let mut layer = Layer::new("test");
+        layer.store_put(RequestMinCompressionSizeBytes::from(0));
+        cfg.push_layer(layer);
+        let mut context = context();
+        let ctx = Into::into(&context);
+
+        sut.read_before_execution(&ctx, cfg).unwrap();
+
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut ctx = Into::into(&mut context);
+        sut.modify_before_retry_loop(&mut ctx, &rc, cfg).unwrap();
+
+        context.take_request().unwrap()
+    }
+
+    #[cfg(any(feature = "zstd", feature = "brotli"))]
+    async fn decompressed_body(algorithm: CompressionAlgorithm, request: HttpRequest) -> Vec<u8> {
+        let body = request.into_body().collect().await.unwrap().to_bytes();
+        let mut decompressor = algorithm.stream_decompressor();
+        let mut decompressed = decompressor.decompress_chunk(&body).unwrap().to_vec();
+        decompressed.extend_from_slice(&decompressor.finish().unwrap());
+        decompressed
+    }
+
+    #[tokio::test]
+    async fn test_sdk_feature_gzip_request_compression_should_be_tracked() {
+        let mut cfg = ConfigBag::base();
+        let sut = RequestCompressionInterceptor::new(vec![CompressionAlgorithm::Gzip]);
+        compress(&sut, &mut cfg);
+
+        assert_eq!(
+            &SmithySdkFeature::GzipRequestCompression,
+            cfg.load::<SmithySdkFeature>().next().unwrap()
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[tokio::test]
+    async fn test_first_supported_encoding_is_used() {
+        let mut cfg = ConfigBag::base();
+        let sut = RequestCompressionInterceptor::new(vec![
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Gzip,
+        ]);
+        let request = compress(&sut, &mut cfg);
+
+        assert_eq!(Some("zstd"), request.headers().get("content-encoding"));
+        assert_eq!(
+            vec![&SmithySdkFeature::ZstdRequestCompression],
+            cfg.load::<SmithySdkFeature>().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            UNCOMPRESSED_INPUT,
+            decompressed_body(CompressionAlgorithm::Zstd, request).await
+        );
+    }
+
+    #[cfg(feature = "brotli")]
+    #[tokio::test]
+    async fn test_configured_algorithm_is_used_when_supported() {
+        let mut cfg = ConfigBag::base();
+        let mut layer = Layer::new("config");
+        layer.store_put(super::RequestCompressionAlgorithm::from(
+            CompressionAlgorithm::Brotli,
+        ));
+        cfg.push_layer(layer);
+        let sut = RequestCompressionInterceptor::new(vec![
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Brotli,
+        ]);
+        let request = compress(&sut, &mut cfg);
+
+        assert_eq!(Some("br"), request.headers().get("content-encoding"));
+        assert_eq!(
+            vec![&SmithySdkFeature::BrotliRequestCompression],
+            cfg.load::<SmithySdkFeature>().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            UNCOMPRESSED_INPUT,
+            decompressed_body(CompressionAlgorithm::Brotli, request).await
+        );
+    }
+
+    #[cfg(feature = "brotli")]
+    #[tokio::test]
+    async fn test_configured_algorithm_is_ignored_when_unsupported() {
+        let mut cfg = ConfigBag::base();
+        let mut layer = Layer::new("config");
+        layer.store_put(super::RequestCompressionAlgorithm::from(
+            CompressionAlgorithm::Brotli,
+        ));
+        cfg.push_layer(layer);
+        let sut = RequestCompressionInterceptor::new(vec![CompressionAlgorithm::Gzip]);
+        let request = compress(&sut, &mut cfg);
+
+        assert_eq!(Some("gzip"), request.headers().get("content-encoding"));
+    }
+
+    #[tokio::test]
+    async fn test_no_supported_encoding_leaves_request_uncompressed() {
+        let mut cfg = ConfigBag::base();
+        let sut = RequestCompressionInterceptor::new(Vec::new());
+        let request = compress(&sut, &mut cfg);
+
+        assert_eq!(None, request.headers().get("content-encoding"));
+        assert_eq!(None, cfg.load::<SmithySdkFeature>().next());
+        assert_eq!(
+            UNCOMPRESSED_INPUT,
+            request.into_body().collect().await.unwrap().to_bytes()
+        );
+    }
+}
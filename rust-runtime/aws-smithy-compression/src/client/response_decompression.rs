@@ -0,0 +1,317 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client interceptor for response decompression.
+
+use crate::body::decompress::DecompressedBody;
+use crate::CompressionAlgorithm;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeDeserializationInterceptorContextMut, BeforeTransmitInterceptorContextMut,
+};
+use aws_smithy_runtime_api::client::interceptors::{dyn_dispatch_hint, Intercept};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::mem;
+
+const ACCEPT_ENCODING: &str = "accept-encoding";
+const CONTENT_ENCODING: &str = "content-encoding";
+const CONTENT_LENGTH: &str = "content-length";
+
+/// Interceptor that transparently decompresses response bodies.
+///
+/// This interceptor is opt-in. When added to a client, it advertises the supported algorithms with
+/// an `Accept-Encoding` header (unless the request already sets one) and wraps any response body
+/// whose `Content-Encoding` is a supported algorithm so that it's inflated as it's read. Because
+/// decompression happens while streaming, this also works for streaming `ByteStream` outputs.
+///
+/// The `Content-Length` header of a decompressed response is removed since it describes the body
+/// as it was sent over the wire. `Content-Encoding` is left untouched.
+///
+/// # Interceptor order
+///
+/// Interceptors wrap the response body in the order they run, so for stalled stream protection to
+/// measure the raw wire size of the response, this interceptor has to run after the
+/// `StalledStreamProtectionInterceptor`. Generated clients register that interceptor for each
+/// operation, after every interceptor registered with the client config, so register this one for
+/// each operation as well, e.g. with `.customize().interceptor(...)`.
+///
+/// When registered with the client config, responses are still decompressed, but stalled stream
+/// protection measures the throughput of the decompressed body. Since decompressed data is usually
+/// larger than the data received, a stalled download may then take longer to be detected.
+#[derive(Debug, Clone)]
+pub struct ResponseDecompressionInterceptor {
+    algorithms: Vec<CompressionAlgorithm>,
+}
+
+impl Default for ResponseDecompressionInterceptor {
+    fn default() -> Self {
+        Self {
+            algorithms: CompressionAlgorithm::supported().to_vec(),
+        }
+    }
+}
+
+impl ResponseDecompressionInterceptor {
+    /// Create a new `ResponseDecompressionInterceptor` that accepts every supported algorithm.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only accept and decompress the given algorithms, in order of preference.
+    pub fn with_algorithms(
+        mut self,
+        algorithms: impl IntoIterator<Item = CompressionAlgorithm>,
+    ) -> Self {
+        self.algorithms = algorithms.into_iter().collect();
+        self
+    }
+
+    fn accept_encoding(&self) -> String {
+        self.algorithms
+            .iter()
+            .map(CompressionAlgorithm::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn algorithm_for(&self, content_encoding: &str) -> Option<CompressionAlgorithm> {
+        let algorithm = content_encoding
+            .trim()
+            .parse::<CompressionAlgorithm>()
+            .ok()?;
+        self.algorithms.contains(&algorithm).then_some(algorithm)
+    }
+}
+
+#[dyn_dispatch_hint]
+impl Intercept for ResponseDecompressionInterceptor {
+    fn name(&self) -> &'static str {
+        "ResponseDecompressionInterceptor"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let headers = context.request_mut().headers_mut();
+        if !self.algorithms.is_empty() && !headers.contains_key(ACCEPT_ENCODING) {
+            headers.insert(ACCEPT_ENCODING, self.accept_encoding());
+        }
+        Ok(())
+    }
+
+    fn modify_before_deserialization(
+        &self,
+        context: &mut BeforeDeserializationInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let response = context.response_mut();
+        let Some(content_encoding) = response.headers().get(CONTENT_ENCODING) else {
+            return Ok(());
+        };
+        let Some(algorithm) = self.algorithm_for(content_encoding) else {
+            tracing::trace!(
+                content_encoding,
+                "response content-encoding is not supported and will not be decompressed"
+            );
+            return Ok(());
+        };
+
+        tracing::trace!(
+            content_encoding = algorithm.as_str(),
+            "decompressing response body"
+        );
+        // The decompressed body doesn't have the length of the body sent over the wire.
+        response.headers_mut().remove(CONTENT_LENGTH);
+        let body = mem::replace(response.body_mut(), SdkBody::taken());
+        // `map` rather than `map_preserve_contents` because decompression alters the body's data.
+        *response.body_mut() = body.map(move |body| {
+            SdkBody::from_body_1_x(DecompressedBody::new(body, algorithm.stream_decompressor()))
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseDecompressionInterceptor;
+    use crate::CompressionAlgorithm;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_async::time::SystemTimeSource;
+    use aws_smithy_http_client::test_util::infallible_client_fn;
+    use aws_smithy_runtime::client::orchestrator::operation::Operation;
+    use aws_smithy_runtime::client::stalled_stream_protection::StalledStreamProtectionInterceptor;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use bytes::Bytes;
+    use http_body_1x::Frame;
+    use http_body_util::{BodyExt, StreamBody};
+    use std::convert::Infallible;
+
+    const UNCOMPRESSED_INPUT: &[u8] = b"hello world";
+    const COMPRESSED_OUTPUT: &[u8] = &[
+        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 203, 72, 205, 201, 201, 87, 40, 207, 47, 202, 73, 1, 0,
+        133, 17, 74, 13, 11, 0, 0, 0,
+    ];
+
+    fn context(response: HttpResponse) -> InterceptorContext {
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.enter_serialization_phase();
+        context.set_request(
+            http_1x::Request::builder()
+                .body(SdkBody::empty())
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let _ = context.take_input();
+        context.enter_before_transmit_phase();
+        context.enter_transmit_phase();
+        let _ = context.take_request();
+        context.set_response(response);
+        context.enter_before_deserialization_phase();
+        context
+    }
+
+    fn streaming_response(content_encoding: &str, data: &'static [u8]) -> HttpResponse {
+        let chunks: Vec<Result<Frame<Bytes>, std::convert::Infallible>> = data
+            .chunks(4)
+            .map(|chunk| Ok(Frame::data(Bytes::from_static(chunk))))
+            .collect();
+        http_1x::Response::builder()
+            .header("content-encoding", content_encoding)
+            .body(SdkBody::from_body_1_x(StreamBody::new(
+                futures_util::stream::iter(chunks),
+            )))
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    async fn read_response_body(
+        interceptor: &ResponseDecompressionInterceptor,
+        response: HttpResponse,
+    ) -> Bytes {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+        let mut context = context(response);
+        let mut ctx = Into::into(&mut context);
+        interceptor
+            .modify_before_deserialization(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+        let body = context.response_mut().unwrap().take_body();
+        body.collect().await.unwrap().to_bytes()
+    }
+
+    #[tokio::test]
+    async fn streaming_gzip_response_is_decompressed() {
+        let body = read_response_body(
+            &ResponseDecompressionInterceptor::new(),
+            streaming_response("gzip", COMPRESSED_OUTPUT),
+        )
+        .await;
+        assert_eq!(UNCOMPRESSED_INPUT, body);
+    }
+
+    #[tokio::test]
+    async fn unsupported_content_encoding_is_left_alone() {
+        let body = read_response_body(
+            &ResponseDecompressionInterceptor::new(),
+            streaming_response("identity", UNCOMPRESSED_INPUT),
+        )
+        .await;
+        assert_eq!(UNCOMPRESSED_INPUT, body);
+    }
+
+    #[tokio::test]
+    async fn disabled_algorithm_is_left_alone() {
+        let body = read_response_body(
+            &ResponseDecompressionInterceptor::new().with_algorithms([]),
+            streaming_response("gzip", COMPRESSED_OUTPUT),
+        )
+        .await;
+        assert_eq!(COMPRESSED_OUTPUT, body);
+    }
+
+    #[tokio::test]
+    async fn content_length_of_decompressed_response_is_removed() {
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+        let response: HttpResponse = http_1x::Response::builder()
+            .header("content-encoding", "gzip")
+            .header("content-length", COMPRESSED_OUTPUT.len())
+            .body(SdkBody::from(COMPRESSED_OUTPUT))
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let mut context = context(response);
+        let mut ctx = Into::into(&mut context);
+        ResponseDecompressionInterceptor::new()
+            .modify_before_deserialization(&mut ctx, &rc, &mut cfg)
+            .unwrap();
+
+        let response = context.response_mut().unwrap();
+        assert_eq!(None, response.headers().get("content-length"));
+        assert_eq!(Some("gzip"), response.headers().get("content-encoding"));
+        let body = response.take_body().collect().await.unwrap().to_bytes();
+        assert_eq!(UNCOMPRESSED_INPUT, body);
+    }
+
+    #[tokio::test]
+    async fn client_level_interceptor_works_with_default_stalled_stream_protection() {
+        let operation = Operation::builder()
+            .service_name("test")
+            .operation_name("test")
+            .http_client(infallible_client_fn(|request| {
+                assert!(request.headers().contains_key("accept-encoding"));
+                http_1x::Response::builder()
+                    .header("content-encoding", "gzip")
+                    .header("content-length", COMPRESSED_OUTPUT.len())
+                    .body(SdkBody::from(COMPRESSED_OUTPUT))
+                    .unwrap()
+            }))
+            .endpoint_url("http://localhost:1234")
+            .no_auth()
+            .no_retry()
+            .sleep_impl(TokioSleep::new())
+            .time_source(SystemTimeSource::new())
+            // Registered with the client, so it runs before stalled stream protection
+            .interceptor(ResponseDecompressionInterceptor::new())
+            // Generated clients register stalled stream protection for each operation, after the
+            // interceptors of the client
+            .runtime_plugin(
+                StaticRuntimePlugin::new().with_runtime_components(
+                    RuntimeComponentsBuilder::new("operation")
+                        .with_interceptor(StalledStreamProtectionInterceptor::default()),
+                ),
+            )
+            .serializer(|_: ()| Ok(HttpRequest::new(SdkBody::empty())))
+            .deserializer::<Bytes, Infallible>(|response| {
+                Ok(Bytes::copy_from_slice(response.body().bytes().unwrap()))
+            })
+            .build();
+
+        // Stalled stream protection is enabled by the default config
+        let output = operation.invoke(()).await.unwrap();
+        assert_eq!(UNCOMPRESSED_INPUT, output);
+    }
+
+    #[test]
+    fn accept_encoding_lists_algorithms() {
+        let interceptor =
+            ResponseDecompressionInterceptor::new().with_algorithms([CompressionAlgorithm::Gzip]);
+        assert_eq!("gzip", interceptor.accept_encoding());
+    }
+}
@@ -4,9 +4,11 @@
  */
 
 use crate::http::CompressRequest;
-use crate::{Compress, CompressionOptions};
+use crate::{decompress_error, DecompressStream, DecompressedBuffer};
+use crate::{Compress, CompressStream, CompressionOptions};
 use aws_smithy_runtime_api::box_error::BoxError;
-use flate2::write::GzEncoder;
+use bytes::Bytes;
+use flate2::write::{GzEncoder, MultiGzDecoder};
 use std::io::prelude::*;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -34,6 +36,77 @@ impl CompressRequest for Gzip {
     fn header_value(&self) -> http_1x::HeaderValue {
         http_1x::HeaderValue::from_static("gzip")
     }
+
+    fn stream_compressor(&self) -> Box<dyn CompressStream> {
+        Box::new(GzipStream {
+            encoder: Some(GzEncoder::new(Vec::new(), self.compression)),
+        })
+    }
+}
+
+/// Compresses a stream of chunks into a single gzip member.
+struct GzipStream {
+    encoder: Option<GzEncoder<Vec<u8>>>,
+}
+
+impl CompressStream for GzipStream {
+    fn compress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        let encoder = self
+            .encoder
+            .as_mut()
+            .ok_or("gzip stream was already finished")?;
+        encoder.write_all(bytes)?;
+        Ok(std::mem::take(encoder.get_mut()).into())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        match self.encoder.take() {
+            Some(encoder) => Ok(encoder.finish()?.into()),
+            None => Ok(Bytes::new()),
+        }
+    }
+}
+
+/// Decompresses a stream of chunks containing one or more gzip members.
+#[derive(Default)]
+pub(crate) struct GzipDecompressStream {
+    decoder: Option<MultiGzDecoder<DecompressedBuffer>>,
+    limit: Option<usize>,
+    is_finished: bool,
+}
+
+impl GzipDecompressStream {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+}
+
+impl DecompressStream for GzipDecompressStream {
+    fn decompress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        if self.is_finished {
+            return Err("gzip stream was already finished".into());
+        }
+        let limit = self.limit;
+        let decoder = self
+            .decoder
+            .get_or_insert_with(|| MultiGzDecoder::new(DecompressedBuffer::new(limit)));
+        decoder.write_all(bytes).map_err(decompress_error)?;
+        // The decoder buffers its output until it's flushed.
+        decoder.flush().map_err(decompress_error)?;
+        Ok(decoder.get_mut().take())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        self.is_finished = true;
+        // An empty body has nothing to decompress.
+        match self.decoder.take() {
+            Some(decoder) => Ok(decoder.finish().map_err(decompress_error)?.take()),
+            None => Ok(Bytes::new()),
+        }
+    }
 }
 
 impl From<&CompressionOptions> for Gzip {
@@ -55,8 +128,9 @@ impl From<CompressionOptions> for Gzip {
 // Windows line-endings will cause the compression test to fail.
 #[cfg(all(test, not(windows)))]
 mod tests {
-    use super::Gzip;
-    use crate::CompressionOptions;
+    use super::{Gzip, GzipDecompressStream};
+    use crate::http::CompressRequest;
+    use crate::{CompressionOptions, DecompressStream};
     use flate2::read::GzDecoder;
     use pretty_assertions::assert_eq;
     use std::io::Read;
@@ -95,4 +169,44 @@ mod tests {
 
         assert_eq!(uncompressed_expected, uncompressed_actual);
     }
+
+    #[test]
+    fn test_gzip_stream_compression() {
+        let gzip = Gzip::from(&CompressionOptions::default());
+        let mut stream = gzip.stream_compressor();
+        let mut compressed_output = Vec::new();
+        for chunk in gettysburg_address().chunks(100) {
+            compressed_output.extend_from_slice(&stream.compress_chunk(chunk).unwrap());
+        }
+        compressed_output.extend_from_slice(&stream.finish().unwrap());
+
+        let mut uncompressed_actual = Vec::new();
+        GzDecoder::new(&compressed_output[..])
+            .read_to_end(&mut uncompressed_actual)
+            .unwrap();
+
+        assert_eq!(gettysburg_address(), uncompressed_actual.as_slice());
+    }
+
+    #[test]
+    fn test_gzip_stream_decompression() {
+        let mut stream = GzipDecompressStream::default();
+        let mut uncompressed_actual = Vec::new();
+        for chunk in gzip_compressed_gettysburg_address().chunks(100) {
+            uncompressed_actual.extend_from_slice(&stream.decompress_chunk(chunk).unwrap());
+        }
+        uncompressed_actual.extend_from_slice(&stream.finish().unwrap());
+
+        assert_eq!(gettysburg_address(), uncompressed_actual.as_slice());
+    }
+
+    #[test]
+    fn test_gzip_stream_decompression_detects_truncation() {
+        let compressed = gzip_compressed_gettysburg_address();
+        let mut stream = GzipDecompressStream::default();
+        stream
+            .decompress_chunk(&compressed[..compressed.len() - 10])
+            .unwrap();
+        stream.finish().expect_err("truncated stream must fail");
+    }
 }
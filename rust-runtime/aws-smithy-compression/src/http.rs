@@ -6,7 +6,9 @@
 //! Checksum support for HTTP requests and responses.
 
 /// Support for the `http-body-1-0` and `http-1-0` crates.
-use crate::Compress;
+use crate::{Compress, CompressStream};
+use aws_smithy_runtime_api::box_error::BoxError;
+use bytes::Bytes;
 use http_1x::header::{HeaderName, HeaderValue};
 
 /// Implementors of this trait can be used to compress HTTP requests.
@@ -18,6 +20,30 @@ pub trait CompressRequest: Compress + CloneCompressRequest {
 
     /// Return the header value for the content-encoding header.
     fn header_value(&self) -> HeaderValue;
+
+    /// Return a [`CompressStream`] for compressing a streaming request body.
+    ///
+    /// The default implementation compresses each chunk independently with [`Compress::compress_bytes`].
+    /// This is only correct for encodings that allow concatenating compressed payloads, so implementors
+    /// should override it to return a stateful encoder when possible.
+    fn stream_compressor(&self) -> Box<dyn CompressStream> {
+        Box::new(CompressEachChunk(self.clone_request_compressor()))
+    }
+}
+
+/// A [`CompressStream`] that compresses every chunk as a standalone payload.
+struct CompressEachChunk(Box<dyn CompressRequest>);
+
+impl CompressStream for CompressEachChunk {
+    fn compress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        let mut out = Vec::new();
+        self.0.compress_bytes(bytes, &mut out)?;
+        Ok(out.into())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        Ok(Bytes::new())
+    }
 }
 
 /// Enables CompressRequest implementors to be cloned.
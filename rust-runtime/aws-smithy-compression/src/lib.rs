@@ -18,16 +18,28 @@
 
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use bytes::Bytes;
+use std::fmt;
 use std::io::Write;
 use std::str::FromStr;
 
 pub mod body;
+#[cfg(feature = "brotli")]
+mod br;
+#[cfg(feature = "client")]
+pub mod client;
 mod gzip;
 pub mod http;
+#[cfg(feature = "zstd")]
+mod zstandard;
 
 // Valid compression algorithm names
 /// The name of the `gzip` algorithm.
 pub const GZIP_NAME: &str = "gzip";
+/// The name of the `zstd` algorithm.
+pub const ZSTD_NAME: &str = "zstd";
+/// The name of the `br` (Brotli) algorithm.
+pub const BROTLI_NAME: &str = "br";
 
 /// The maximum-allowable value per internal standards is 10 Megabytes.
 const MAX_MIN_COMPRESSION_SIZE_BYTES: u32 = 10_485_760;
@@ -44,6 +56,129 @@ pub trait Compress: Send + Sync {
     fn compress_bytes(&mut self, bytes: &[u8], writer: &mut dyn Write) -> Result<(), BoxError>;
 }
 
+/// Types implementing this trait can incrementally compress a stream of data.
+///
+/// Unlike [`Compress`], which compresses a complete payload in one call, a
+/// `CompressStream` keeps encoder state between calls so that all chunks of a
+/// streaming body end up in a single compressed stream.
+pub trait CompressStream: Send + Sync {
+    /// Compress the next chunk of data, returning whatever compressed output is ready.
+    ///
+    /// The returned bytes may be empty if the encoder is still buffering input.
+    fn compress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError>;
+
+    /// Finish the compressed stream, returning any remaining compressed output.
+    ///
+    /// No more chunks may be compressed after calling this.
+    fn finish(&mut self) -> Result<Bytes, BoxError>;
+}
+
+/// Types implementing this trait can incrementally decompress a stream of data.
+pub trait DecompressStream: Send + Sync {
+    /// Decompress the next chunk of data, returning whatever decompressed output is ready.
+    ///
+    /// The returned bytes may be empty if the decoder needs more input.
+    fn decompress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError>;
+
+    /// Finish the decompressed stream, returning any remaining decompressed output.
+    ///
+    /// Returns an error if the compressed stream was truncated.
+    fn finish(&mut self) -> Result<Bytes, BoxError>;
+}
+
+/// The error returned by a [`DecompressStream`] when the decompressed data exceeds its maximum size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecompressedSizeLimitExceeded {
+    limit: usize,
+}
+
+impl DecompressedSizeLimitExceeded {
+    /// The configured maximum, in bytes.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for DecompressedSizeLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "data exceeded the maximum decompressed size of {} bytes",
+            self.limit
+        )
+    }
+}
+
+impl std::error::Error for DecompressedSizeLimitExceeded {}
+
+/// Counts the decompressed bytes produced by a [`DecompressStream`] against its optional maximum.
+#[derive(Debug, Default)]
+struct DecompressedSize {
+    size: usize,
+    limit: Option<usize>,
+}
+
+impl DecompressedSize {
+    fn new(limit: Option<usize>) -> Self {
+        Self { size: 0, limit }
+    }
+
+    /// Record `len` more decompressed bytes, failing if they take the total past the maximum.
+    fn add(&mut self, len: usize) -> Result<(), DecompressedSizeLimitExceeded> {
+        self.size = self.size.saturating_add(len);
+        match self.limit {
+            Some(limit) if self.size > limit => Err(DecompressedSizeLimitExceeded { limit }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A buffer that decoders write their output to, which fails as soon as the decompressed data
+/// exceeds its maximum size rather than after the decoder has inflated a whole input chunk.
+#[derive(Debug, Default)]
+struct DecompressedBuffer {
+    buffer: Vec<u8>,
+    size: DecompressedSize,
+}
+
+impl DecompressedBuffer {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            buffer: Vec::new(),
+            size: DecompressedSize::new(limit),
+        }
+    }
+
+    /// Take the data written so far.
+    fn take(&mut self) -> Bytes {
+        std::mem::take(&mut self.buffer).into()
+    }
+}
+
+impl Write for DecompressedBuffer {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.size.add(data.len()).map_err(std::io::Error::other)?;
+        self.buffer.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Convert an I/O error returned by a decoder into a [`BoxError`], surfacing a
+/// [`DecompressedSizeLimitExceeded`] raised by its [`DecompressedBuffer`] as is.
+fn decompress_error(err: std::io::Error) -> BoxError {
+    match err
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<DecompressedSizeLimitExceeded>())
+    {
+        Some(exceeded) => Box::new(*exceeded),
+        None => err.into(),
+    }
+}
+
 /// Options for configuring request compression.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -142,6 +277,12 @@ impl Storable for CompressionOptions {
 pub enum CompressionAlgorithm {
     /// The [gzip](https://en.wikipedia.org/wiki/Gzip) compression algorithm
     Gzip,
+    /// The [Zstandard](https://en.wikipedia.org/wiki/Zstd) compression algorithm
+    #[cfg(feature = "zstd")]
+    Zstd,
+    /// The [Brotli](https://en.wikipedia.org/wiki/Brotli) compression algorithm
+    #[cfg(feature = "brotli")]
+    Brotli,
 }
 
 impl FromStr for CompressionAlgorithm {
@@ -151,14 +292,23 @@ impl FromStr for CompressionAlgorithm {
     ///
     /// Valid algorithm names are:
     /// - "gzip"
+    /// - "zstd" (requires the `zstd` feature)
+    /// - "br" (requires the `brotli` feature)
     ///
     /// Passing an invalid name will return an error.
     fn from_str(compression_algorithm: &str) -> Result<Self, Self::Err> {
         if compression_algorithm.eq_ignore_ascii_case(GZIP_NAME) {
-            Ok(Self::Gzip)
-        } else {
-            Err(format!("unknown compression algorithm `{compression_algorithm}`").into())
+            return Ok(Self::Gzip);
         }
+        #[cfg(feature = "zstd")]
+        if compression_algorithm.eq_ignore_ascii_case(ZSTD_NAME) {
+            return Ok(Self::Zstd);
+        }
+        #[cfg(feature = "brotli")]
+        if compression_algorithm.eq_ignore_ascii_case(BROTLI_NAME) {
+            return Ok(Self::Brotli);
+        }
+        Err(format!("unknown compression algorithm `{compression_algorithm}`").into())
     }
 }
 
@@ -170,22 +320,100 @@ impl CompressionAlgorithm {
     ) -> Box<dyn http::CompressRequest> {
         match self {
             Self::Gzip => Box::new(gzip::Gzip::from(options)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Box::new(zstandard::Zstd::from(options)),
+            #[cfg(feature = "brotli")]
+            Self::Brotli => Box::new(br::Brotli::from(options)),
+        }
+    }
+
+    /// Return a [`DecompressStream`] that decodes data compressed with this algorithm.
+    pub fn stream_decompressor(self) -> Box<dyn DecompressStream> {
+        self.decompressor(None)
+    }
+
+    /// Return a [`DecompressStream`] that decodes data compressed with this algorithm, and fails
+    /// with a [`DecompressedSizeLimitExceeded`] error as soon as the decompressed data exceeds
+    /// `max_decompressed_size_bytes`.
+    ///
+    /// The limit is checked while the decoder produces output, so a small chunk of compressed
+    /// data that inflates to a huge size is never fully decompressed.
+    pub fn stream_decompressor_with_limit(
+        self,
+        max_decompressed_size_bytes: usize,
+    ) -> Box<dyn DecompressStream> {
+        self.decompressor(Some(max_decompressed_size_bytes))
+    }
+
+    fn decompressor(self, limit: Option<usize>) -> Box<dyn DecompressStream> {
+        match self {
+            Self::Gzip => Box::new(gzip::GzipDecompressStream::new(limit)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Box::new(zstandard::ZstdDecompressStream::new(limit)),
+            #[cfg(feature = "brotli")]
+            Self::Brotli => Box::new(br::BrotliDecompressStream::new(limit)),
         }
     }
 
+    /// Return all compression algorithms supported with the enabled crate features.
+    pub fn supported() -> &'static [CompressionAlgorithm] {
+        &[
+            Self::Gzip,
+            #[cfg(feature = "zstd")]
+            Self::Zstd,
+            #[cfg(feature = "brotli")]
+            Self::Brotli,
+        ]
+    }
+
     /// Return the name of this algorithm in string form
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Gzip { .. } => GZIP_NAME,
+            #[cfg(feature = "zstd")]
+            Self::Zstd => ZSTD_NAME,
+            #[cfg(feature = "brotli")]
+            Self::Brotli => BROTLI_NAME,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::CompressionAlgorithm;
+    use crate::{CompressionAlgorithm, CompressionOptions, DecompressedSizeLimitExceeded};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_stream_decompressor_with_limit() {
+        let payload = vec![0u8; 10 * 1024 * 1024];
+        for algorithm in CompressionAlgorithm::supported() {
+            let mut compressor = algorithm
+                .into_impl_http_body_1_x(&CompressionOptions::default())
+                .stream_compressor();
+            let mut compressed = compressor.compress_chunk(&payload).unwrap().to_vec();
+            compressed.extend_from_slice(&compressor.finish().unwrap());
+
+            let mut decompressor = algorithm.stream_decompressor_with_limit(payload.len());
+            let mut decompressed = decompressor.decompress_chunk(&compressed).unwrap().to_vec();
+            decompressed.extend_from_slice(&decompressor.finish().unwrap());
+            assert_eq!(payload.len(), decompressed.len(), "{algorithm:?}");
+
+            // The whole bomb is a single chunk, so the limit must be checked while decoding it.
+            let mut decompressor = algorithm.stream_decompressor_with_limit(1024);
+            let error = decompressor
+                .decompress_chunk(&compressed)
+                .and_then(|_| decompressor.finish())
+                .expect_err("the limit is exceeded");
+            assert_eq!(
+                Some(1024),
+                error
+                    .downcast_ref::<DecompressedSizeLimitExceeded>()
+                    .map(DecompressedSizeLimitExceeded::limit),
+                "{algorithm:?}: {error}"
+            );
+        }
+    }
+
     #[test]
     fn test_compression_algorithm_from_str_unknown() {
         let error = "some unknown compression algorithm"
@@ -202,4 +430,20 @@ mod tests {
         let algo = "gzip".parse::<CompressionAlgorithm>().unwrap();
         assert_eq!("gzip", algo.as_str());
     }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_compression_algorithm_from_str_zstd() {
+        let algo = "zstd".parse::<CompressionAlgorithm>().unwrap();
+        assert_eq!(CompressionAlgorithm::Zstd, algo);
+        assert_eq!("zstd", algo.as_str());
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_compression_algorithm_from_str_brotli() {
+        let algo = "br".parse::<CompressionAlgorithm>().unwrap();
+        assert_eq!(CompressionAlgorithm::Brotli, algo);
+        assert_eq!("br", algo.as_str());
+    }
 }
@@ -0,0 +1,234 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::http::CompressRequest;
+use crate::{Compress, CompressStream, CompressionOptions, DecompressStream, DecompressedSize};
+use aws_smithy_runtime_api::box_error::BoxError;
+use bytes::Bytes;
+use std::io::prelude::*;
+use zstd::stream::raw::{Decoder, InBuffer, Operation, OutBuffer};
+use zstd::stream::write::Encoder;
+
+/// Size of the scratch buffer that decompressed output is written to.
+const DECOMPRESS_BUFFER_SIZE: usize = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Zstd {
+    level: i32,
+}
+
+impl Zstd {
+    fn compress_bytes(&self, bytes: &[u8], writer: impl Write) -> Result<(), BoxError> {
+        let mut encoder = Encoder::new(writer, self.level)?;
+        encoder.write_all(bytes)?;
+        encoder.finish()?;
+
+        Ok(())
+    }
+}
+
+impl Compress for Zstd {
+    fn compress_bytes(&mut self, bytes: &[u8], writer: &mut dyn Write) -> Result<(), BoxError> {
+        Zstd::compress_bytes(self, bytes, writer)
+    }
+}
+
+impl CompressRequest for Zstd {
+    fn header_value(&self) -> http_1x::HeaderValue {
+        http_1x::HeaderValue::from_static("zstd")
+    }
+
+    fn stream_compressor(&self) -> Box<dyn CompressStream> {
+        Box::new(ZstdStream {
+            level: self.level,
+            encoder: None,
+            is_finished: false,
+        })
+    }
+}
+
+/// Compresses a stream of chunks into a single zstd frame.
+struct ZstdStream {
+    level: i32,
+    encoder: Option<Encoder<'static, Vec<u8>>>,
+    is_finished: bool,
+}
+
+impl ZstdStream {
+    fn encoder(&mut self) -> Result<&mut Encoder<'static, Vec<u8>>, BoxError> {
+        if self.is_finished {
+            return Err("zstd stream was already finished".into());
+        }
+        if self.encoder.is_none() {
+            self.encoder = Some(Encoder::new(Vec::new(), self.level)?);
+        }
+        Ok(self.encoder.as_mut().expect("set above"))
+    }
+}
+
+impl CompressStream for ZstdStream {
+    fn compress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        let encoder = self.encoder()?;
+        encoder.write_all(bytes)?;
+        Ok(std::mem::take(encoder.get_mut()).into())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        if self.is_finished {
+            return Ok(Bytes::new());
+        }
+        // Make sure an empty stream still produces a valid (empty) frame.
+        self.encoder()?;
+        self.is_finished = true;
+        let encoder = self.encoder.take().expect("set above");
+        Ok(encoder.finish()?.into())
+    }
+}
+
+/// Decompresses a stream of chunks containing one or more zstd frames.
+#[derive(Default)]
+pub(crate) struct ZstdDecompressStream {
+    decoder: Option<Decoder<'static>>,
+    size: DecompressedSize,
+    is_frame_complete: bool,
+}
+
+impl ZstdDecompressStream {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            size: DecompressedSize::new(limit),
+            ..Default::default()
+        }
+    }
+}
+
+impl DecompressStream for ZstdDecompressStream {
+    fn decompress_chunk(&mut self, bytes: &[u8]) -> Result<Bytes, BoxError> {
+        if self.decoder.is_none() {
+            self.decoder = Some(Decoder::new()?);
+        }
+        let decoder = self.decoder.as_mut().expect("set above");
+        let mut input = InBuffer::around(bytes);
+        let mut out = Vec::new();
+        let mut buffer = [0u8; DECOMPRESS_BUFFER_SIZE];
+        loop {
+            let mut output = OutBuffer::around(&mut buffer);
+            let read_before = input.pos();
+            // A hint of `0` means a frame was fully decoded and flushed.
+            let hint = decoder.run(&mut input, &mut output)?;
+            let written = output.pos();
+            // Checked for every buffer of output, since a single frame can inflate without bound.
+            self.size.add(written)?;
+            out.extend_from_slice(&buffer[..written]);
+            // A call that makes no progress after a complete frame doesn't start a new one.
+            if hint == 0 || written > 0 || input.pos() > read_before {
+                self.is_frame_complete = hint == 0;
+            }
+            // Keep going while there's unread input or the decoder may have more output to flush.
+            if input.pos() == bytes.len() && written < buffer.len() {
+                break;
+            }
+        }
+        Ok(out.into())
+    }
+
+    fn finish(&mut self) -> Result<Bytes, BoxError> {
+        // An empty body has nothing to decompress.
+        if self.decoder.take().is_some() && !self.is_frame_complete {
+            return Err("zstd stream ended before the frame was complete".into());
+        }
+        Ok(Bytes::new())
+    }
+}
+
+impl From<&CompressionOptions> for Zstd {
+    fn from(options: &CompressionOptions) -> Self {
+        Zstd {
+            // zstd treats level `0` as "use the default level", which compresses
+            // harder than level `1`, so the lowest levels are clamped to `1`.
+            level: options.level.max(1) as i32,
+        }
+    }
+}
+
+impl From<CompressionOptions> for Zstd {
+    fn from(options: CompressionOptions) -> Self {
+        Zstd::from(&options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Zstd, ZstdDecompressStream};
+    use crate::http::CompressRequest;
+    use crate::{CompressionOptions, DecompressStream};
+    use pretty_assertions::assert_eq;
+
+    fn gettysburg_address() -> &'static [u8] {
+        include_bytes!("../test-data/gettysburg_address.txt")
+    }
+
+    #[test]
+    fn test_zstd_compression() {
+        let zstd = Zstd::from(&CompressionOptions::default());
+        let mut compressed_output = Vec::new();
+        zstd.compress_bytes(gettysburg_address(), &mut compressed_output)
+            .expect("compression succeeds");
+        assert!(compressed_output.len() < gettysburg_address().len());
+
+        let uncompressed_actual = zstd::decode_all(&compressed_output[..]).unwrap();
+        assert_eq!(gettysburg_address(), uncompressed_actual.as_slice());
+    }
+
+    #[test]
+    fn test_zstd_stream_compression() {
+        let zstd = Zstd::from(&CompressionOptions::default());
+        let mut stream = zstd.stream_compressor();
+        let mut compressed_output = Vec::new();
+        for chunk in gettysburg_address().chunks(100) {
+            compressed_output.extend_from_slice(&stream.compress_chunk(chunk).unwrap());
+        }
+        compressed_output.extend_from_slice(&stream.finish().unwrap());
+
+        let uncompressed_actual = zstd::decode_all(&compressed_output[..]).unwrap();
+        assert_eq!(gettysburg_address(), uncompressed_actual.as_slice());
+    }
+
+    #[test]
+    fn test_zstd_stream_decompression() {
+        // Two concatenated frames must decode to the concatenated payloads
+        let mut compressed = zstd::encode_all(gettysburg_address(), 3).unwrap();
+        compressed.extend(zstd::encode_all(&b"fourscore"[..], 3).unwrap());
+
+        let mut stream = ZstdDecompressStream::default();
+        let mut uncompressed_actual = Vec::new();
+        for chunk in compressed.chunks(100) {
+            uncompressed_actual.extend_from_slice(&stream.decompress_chunk(chunk).unwrap());
+        }
+        uncompressed_actual.extend_from_slice(&stream.finish().unwrap());
+
+        let mut expected = gettysburg_address().to_vec();
+        expected.extend_from_slice(b"fourscore");
+        assert_eq!(expected, uncompressed_actual);
+    }
+
+    #[test]
+    fn test_zstd_stream_decompression_detects_truncation() {
+        let compressed = zstd::encode_all(gettysburg_address(), 3).unwrap();
+        let mut stream = ZstdDecompressStream::default();
+        stream
+            .decompress_chunk(&compressed[..compressed.len() - 10])
+            .unwrap();
+        stream.finish().expect_err("truncated stream must fail");
+    }
+
+    #[test]
+    fn test_zstd_level_is_respected() {
+        let options = CompressionOptions::default().with_level(9).unwrap();
+        assert_eq!(9, Zstd::from(&options).level);
+        let options = CompressionOptions::default().with_level(0).unwrap();
+        assert_eq!(1, Zstd::from(&options).level);
+    }
+}
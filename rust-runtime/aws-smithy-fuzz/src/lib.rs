@@ -18,12 +18,18 @@ use std::pin::pin;
 use std::sync::Mutex;
 use std::task::{Context, Poll};
 use tower::ServiceExt;
+pub mod minimize;
+pub mod structured;
 mod types;
 pub use lazy_static;
+pub use structured::{RequestGenerator, StructuredOperation};
 pub use types::{Body, FuzzResult, HttpRequest, HttpResponse};
 
 #[macro_export]
 /// Defines an extern `process_request` method that can be invoked as a shared library
+///
+/// When a [`RequestGenerator`] is passed as a second argument, an extern `generate_request` method
+/// is also defined, which enables structured fuzzing of the target.
 macro_rules! fuzz_harness {
     ($test_function: expr) => {
         $crate::lazy_static::lazy_static! {
@@ -42,6 +48,20 @@ macro_rules! fuzz_harness {
             $crate::ByteBuffer::from_vec(response.into_bytes())
         }
     };
+    ($test_function: expr, $request_generator: expr) => {
+        $crate::fuzz_harness!($test_function);
+
+        $crate::lazy_static::lazy_static! {
+            static ref GENERATOR: $crate::RequestGenerator = $request_generator;
+        }
+
+        #[no_mangle]
+        pub extern "C" fn generate_request(input: *const u8, len: usize) -> $crate::ByteBuffer {
+            let slice = unsafe { std::slice::from_raw_parts(input, len) };
+            let request = GENERATOR.generate(slice);
+            $crate::ByteBuffer::from_vec($crate::structured::encode_generated_request(request))
+        }
+    };
 }
 
 pub use ::ffi_support::ByteBuffer;
@@ -49,24 +69,56 @@ use bytes::Buf;
 use tokio::runtime::{Builder, Handle};
 use tower::util::BoxService;
 
+type ExternFn = unsafe extern "C" fn(*const u8, usize) -> ByteBuffer;
+
 #[derive(Clone)]
-pub struct FuzzTarget(os::unix::Symbol<unsafe extern "C" fn(*const u8, usize) -> ByteBuffer>);
+pub struct FuzzTarget {
+    process_request: os::unix::Symbol<ExternFn>,
+    generate_request: Option<os::unix::Symbol<ExternFn>>,
+}
+
 impl FuzzTarget {
     pub fn from_path(path: impl AsRef<Path>) -> Self {
         let path = path.as_ref();
         eprintln!("loading library from {}", path.display());
         let library = unsafe { Library::new(path).expect("could not load library") };
-        let func: Symbol<unsafe extern "C" fn(*const u8, usize) -> ByteBuffer> =
-            unsafe { library.get(b"process_request").unwrap() };
+        let func: Symbol<ExternFn> = unsafe { library.get(b"process_request").unwrap() };
+        // only present if the harness was defined with a `RequestGenerator`
+        let generate: Option<Symbol<ExternFn>> = unsafe { library.get(b"generate_request").ok() };
         // ensure we never unload the library
-        let func = unsafe { func.into_raw() };
+        let process_request = unsafe { func.into_raw() };
+        let generate_request = generate.map(|generate| unsafe { generate.into_raw() });
         std::mem::forget(library);
 
-        Self(func)
+        Self {
+            process_request,
+            generate_request,
+        }
+    }
+
+    /// Returns true if the target can generate structured requests.
+    pub fn supports_structured(&self) -> bool {
+        self.generate_request.is_some()
+    }
+
+    /// Generates a request from fuzzer input with the `RequestGenerator` of the target.
+    ///
+    /// Returns `None` if no request could be generated from `input`.
+    ///
+    /// # Panics
+    /// If the target wasn't defined with a `RequestGenerator`.
+    pub fn generate_request(&self, input: &[u8]) -> Option<HttpRequest> {
+        let generate = self
+            .generate_request
+            .as_ref()
+            .expect("target was not built with a request generator");
+        let buffer = unsafe { generate(input.as_ptr(), input.len()) };
+        let data = buffer.destroy_into_vec();
+        structured::decode_generated_request(&data)
     }
 
     pub fn invoke_bytes(&self, input: &[u8]) -> FuzzResult {
-        let buffer = unsafe { (self.0)(input.as_ptr(), input.len()) };
+        let buffer = unsafe { (self.process_request)(input.as_ptr(), input.len()) };
         let data = buffer.destroy_into_vec();
         FuzzResult::from_bytes(&data)
     }
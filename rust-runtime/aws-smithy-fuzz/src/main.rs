@@ -5,9 +5,11 @@
 
 #![cfg(not(windows))]
 
+use aws_smithy_fuzz::minimize::{minimize, protocol_test};
 use aws_smithy_fuzz::{FuzzResult, FuzzTarget, HttpRequest};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::io::{BufRead, BufWriter};
@@ -40,6 +42,18 @@ enum Commands {
     /// Replay subcommand
     Replay(ReplayArgs),
 
+    /// Minimize a test case
+    ///
+    /// Shrinks a request that crashes a target or that targets respond differently to, and prints
+    /// it as a protocol test.
+    Minimize(MinimizeArgs),
+
+    /// Deduplicate a corpus by behavior
+    ///
+    /// Keeps the smallest test case for each distinct behavior of the targets. This does not
+    /// measure coverage: use `cargo afl cmin` for a coverage-based minimization.
+    Dedup(DedupArgs),
+
     /// Setup smithy-rs targets
     ///
     /// This does all of the schlep of setting of smithy-rs copies at different revisions for fuzz testing
@@ -106,6 +120,13 @@ struct FuzzArgs {
     /// The number of parallel fuzzers to run
     #[arg(short, long)]
     num_fuzzers: Option<usize>,
+
+    /// Generate requests from the operation schemas of the targets instead of mutating raw requests
+    ///
+    /// This requires targets defined with a `RequestGenerator`. Requests are generated by the
+    /// first target and sent to all of them.
+    #[arg(long)]
+    structured: bool,
 }
 
 #[derive(Parser)]
@@ -168,6 +189,68 @@ struct ReplayArgs {
     /// This is helpful for sanity checking that everything is working properly
     #[arg(long)]
     corpus: bool,
+
+    /// Replay test cases found by a structured fuzzing session
+    #[arg(long)]
+    structured: bool,
+}
+
+#[derive(Parser)]
+struct MinimizeArgs {
+    /// Custom path to the configuration file.
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        default_value = "smithy-fuzz-config.json"
+    )]
+    config_path: String,
+
+    /// The test case to minimize, e.g. one of the crashes found by AFL
+    #[arg(short, long)]
+    test_case: PathBuf,
+
+    /// Where to write the minimized test case. Defaults to the test case with a `.min` suffix.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Shape ID of the protocol used in the protocol test, e.g. `aws.protocols#restJson1`
+    #[arg(short, long)]
+    protocol: String,
+
+    /// ID of the protocol test
+    #[arg(long, default_value = "FuzzMinimizedTestCase")]
+    id: String,
+
+    /// The test case was found by a structured fuzzing session
+    ///
+    /// The minimized test case is the generated request, which can be replayed without `--structured`.
+    #[arg(long)]
+    structured: bool,
+}
+
+#[derive(Parser)]
+struct DedupArgs {
+    /// Custom path to the configuration file.
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        default_value = "smithy-fuzz-config.json"
+    )]
+    config_path: String,
+
+    /// Directories of test cases to deduplicate. Defaults to the corpus and to the queues of AFL.
+    #[arg(short, long)]
+    input: Vec<PathBuf>,
+
+    /// Directory to write the deduplicated corpus to
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// The test cases are inputs of a structured fuzzing session
+    #[arg(long)]
+    structured: bool,
 }
 
 #[derive(Deserialize)]
@@ -206,6 +289,8 @@ fn main() {
         Commands::Fuzz(args) => fuzz(args),
         Commands::Initialize(args) => initialize(args),
         Commands::Replay(args) => replay(args),
+        Commands::Minimize(args) => minimize_test_case(args),
+        Commands::Dedup(args) => dedup_corpus(args),
         Commands::SetupSmithy(args) => setup_smithy(args),
         Commands::InvokeTestCase(InvokeArgs {
             test_case,
@@ -446,7 +531,10 @@ fn fuzz(args: FuzzArgs) {
     let config: FuzzConfig = serde_json::from_str(&config).unwrap();
     if args.enter_fuzzing_loop {
         let libraries = force_load_libraries(&config.targets);
-        enter_fuzz_loop(libraries, None)
+        if args.structured {
+            assert_structured(&libraries);
+        }
+        enter_fuzz_loop(libraries, None, args.structured)
     } else {
         eprintln!(
             "Preparing to start fuzzing... {} targets.",
@@ -474,6 +562,9 @@ fn fuzz(args: FuzzArgs) {
                 .arg("--config-path")
                 .arg(&args.config_path)
                 .arg("--enter-fuzzing-loop");
+            if args.structured {
+                cmd.arg("--structured");
+            }
             cmd
         };
         let mut main_runner = base_command();
@@ -625,6 +716,7 @@ fn replay(
         invoke_only,
         json,
         corpus,
+        structured,
     }: ReplayArgs,
 ) {
     let config = fs::read_to_string(config_path).unwrap();
@@ -637,11 +729,24 @@ fn replay(
             false => load_all_crashes(&config.afl_output_dir),
         }
     };
+    let generator = load_generator(&config, structured);
     eprintln!("Replaying {} crashes.", crashes.len());
     for crash in crashes {
         eprintln!("{}", crash.display());
         let data = fs::read(&crash).unwrap();
-        let http_request = HttpRequest::from_unknown_bytes(&data);
+        let (http_request, crash) = match &generator {
+            // the targets are invoked with the generated request, not with the fuzzer input
+            Some(generator) => {
+                let Some(request) = generator.generate_request(&data) else {
+                    eprintln!("no request was generated from this test case, skipping");
+                    continue;
+                };
+                let path = scratch_path("generated-request");
+                fs::write(&path, request.as_bytes()).unwrap();
+                (Some(request), path)
+            }
+            None => (HttpRequest::from_unknown_bytes(&data), crash),
+        };
         let mut results: HashMap<String, CrashResult> = HashMap::new();
         #[derive(Debug, Serialize)]
         #[serde(tag = "type")]
@@ -666,21 +771,11 @@ fn replay(
         }
 
         for library in &config.targets {
-            let result = Command::new(env::current_exe().unwrap())
-                .arg("invoke-test-case")
-                .arg("--shared-library-path")
-                .arg(library.shared_library.as_deref().unwrap())
-                .arg("--test-case")
-                .arg(&crash)
-                .output()
-                .unwrap();
-            let result = match serde_json::from_slice::<FuzzResult>(&result.stdout) {
+            let result = match invoke_isolated(library, &crash) {
                 Ok(result) => CrashResult::FuzzResult {
                     result: format!("{:?}", result),
                 },
-                Err(_err) => CrashResult::Panic {
-                    message: String::from_utf8_lossy(&result.stderr).to_string(),
-                },
+                Err(message) => CrashResult::Panic { message },
             };
             results.insert(library.human_name(), result);
         }
@@ -711,6 +806,221 @@ fn replay(
     }
 }
 
+/// Invokes a test case against a target in a subprocess, so that the target may panic
+///
+/// If the target panics, the output of the subprocess is returned as an error.
+fn invoke_isolated(library: &Target, test_case: &Path) -> Result<FuzzResult, String> {
+    let result = Command::new(env::current_exe().unwrap())
+        .arg("invoke-test-case")
+        .arg("--shared-library-path")
+        .arg(library.shared_library.as_deref().unwrap())
+        .arg("--test-case")
+        .arg(test_case)
+        .output()
+        .unwrap();
+    serde_json::from_slice::<FuzzResult>(&result.stdout)
+        .map_err(|_err| String::from_utf8_lossy(&result.stderr).to_string())
+}
+
+/// Path of a file in the temporary directory that is private to this process
+fn scratch_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("aws-smithy-fuzz-{}-{name}", std::process::id()))
+}
+
+/// Invokes a request against all targets, see [`invoke_isolated`]
+fn invoke_all(targets: &[Target], request: &HttpRequest) -> Vec<Result<FuzzResult, String>> {
+    let path = scratch_path("request");
+    fs::write(&path, request.as_bytes()).unwrap();
+    let results = targets
+        .iter()
+        .map(|target| invoke_isolated(target, &path))
+        .collect();
+    let _ = fs::remove_file(&path);
+    results
+}
+
+/// How the targets behaved for a request, ignoring the details of their responses
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Behavior {
+    method: String,
+    path: String,
+    /// The status code returned by each target, or `None` if it panicked
+    statuses: Vec<Option<u16>>,
+    /// Whether the targets responded differently
+    divergent: bool,
+}
+
+impl Behavior {
+    fn of(request: &HttpRequest, results: &[Result<FuzzResult, String>]) -> Self {
+        let responses: Vec<_> = results
+            .iter()
+            .map(|result| result.as_ref().ok().map(|result| &result.response))
+            .collect();
+        Self {
+            method: request.method.clone(),
+            path: request
+                .uri
+                .split('?')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            statuses: responses
+                .iter()
+                .map(|response| response.map(|response| response.status))
+                .collect(),
+            divergent: responses.iter().any(|response| response != &responses[0]),
+        }
+    }
+
+    /// Whether this is a behavior worth reporting: a target panicked or targets disagree
+    fn is_failure(&self) -> bool {
+        self.divergent || self.statuses.iter().any(Option::is_none)
+    }
+}
+
+/// Loads the request of a test case, generating it with the first target in structured mode
+fn load_request(data: &[u8], generator: Option<&FuzzTarget>) -> Option<HttpRequest> {
+    match generator {
+        Some(generator) => generator.generate_request(data),
+        None => HttpRequest::from_unknown_bytes(data),
+    }
+}
+
+fn load_generator(config: &FuzzConfig, structured: bool) -> Option<FuzzTarget> {
+    structured.then(|| {
+        let generator = force_load_libraries(&config.targets[..1]).remove(0);
+        assert_structured(std::slice::from_ref(&generator));
+        generator
+    })
+}
+
+fn minimize_test_case(
+    MinimizeArgs {
+        config_path,
+        test_case,
+        output,
+        protocol,
+        id,
+        structured,
+    }: MinimizeArgs,
+) {
+    let config = fs::read_to_string(config_path).unwrap();
+    let config: FuzzConfig = serde_json::from_str(&config).unwrap();
+    let generator = load_generator(&config, structured);
+    let data = fs::read(&test_case).unwrap();
+    let request = load_request(&data, generator.as_ref())
+        .expect("the test case does not contain a valid request");
+
+    let results = invoke_all(&config.targets, &request);
+    let behavior = Behavior::of(&request, &results);
+    if !behavior.is_failure() {
+        eprintln!("the targets behave the same and do not panic, nothing to minimize");
+        return;
+    }
+    let statuses = behavior.statuses.clone();
+    let mut attempts = 0;
+    let minimized = minimize(&request, |candidate| {
+        attempts += 1;
+        let results = invoke_all(&config.targets, candidate);
+        let candidate = Behavior::of(candidate, &results);
+        // the set of panicking targets must be preserved, the responses may change
+        candidate.is_failure()
+            && candidate
+                .statuses
+                .iter()
+                .map(Option::is_none)
+                .collect::<Vec<_>>()
+                == statuses.iter().map(Option::is_none).collect::<Vec<_>>()
+    });
+    eprintln!(
+        "minimized the request from {} to {} body bytes in {} attempts",
+        request.body.len(),
+        minimized.body.len(),
+        attempts
+    );
+
+    let output = output.unwrap_or_else(|| {
+        let mut path = test_case.into_os_string();
+        path.push(".min");
+        path.into()
+    });
+    fs::write(&output, minimized.as_bytes()).unwrap();
+    eprintln!("wrote the minimized test case to {}", output.display());
+
+    let results = invoke_all(&config.targets, &minimized);
+    for (target, result) in config.targets.iter().zip(&results) {
+        match result {
+            Ok(result) => eprintln!("{}: {:?}", target.human_name(), result),
+            Err(message) => eprintln!("{}: panicked\n{}", target.human_name(), message),
+        }
+    }
+    // the first target that did not panic is used as the reference for the expected status
+    let status = results
+        .iter()
+        .find_map(|result| result.as_ref().ok())
+        .map(|result| result.response.status)
+        .unwrap_or(400);
+    println!("{}", protocol_test(&id, &protocol, &minimized, status));
+}
+
+/// Keeps the smallest test case for each [Behavior] of the targets.
+///
+/// Test cases that exercise different code paths but behave the same are considered duplicates,
+/// so this is coarser than a coverage-based minimization such as `cargo afl cmin`.
+fn dedup_corpus(
+    DedupArgs {
+        config_path,
+        input,
+        output,
+        structured,
+    }: DedupArgs,
+) {
+    let config = fs::read_to_string(config_path).unwrap();
+    let config: FuzzConfig = serde_json::from_str(&config).unwrap();
+    let generator = load_generator(&config, structured);
+    let test_cases = if input.is_empty() {
+        let mut test_cases = load_corpus(&config.afl_input_dir);
+        test_cases.extend(load_inputs_at_pattern(
+            &config.afl_output_dir.join("fuzzer*/queue"),
+        ));
+        test_cases
+    } else {
+        input
+            .iter()
+            .flat_map(|dir| load_inputs_at_pattern(dir))
+            .collect()
+    };
+
+    let mut smallest: HashMap<Behavior, (usize, PathBuf)> = HashMap::new();
+    for test_case in &test_cases {
+        let data = fs::read(test_case).unwrap();
+        let Some(request) = load_request(&data, generator.as_ref()) else {
+            continue;
+        };
+        if request.into_http_request_04x().is_none() {
+            continue;
+        }
+        let behavior = Behavior::of(&request, &invoke_all(&config.targets, &request));
+        match smallest.get(&behavior) {
+            Some((size, _)) if *size <= data.len() => {}
+            _ => {
+                smallest.insert(behavior, (data.len(), test_case.clone()));
+            }
+        }
+    }
+
+    fs::create_dir_all(&output).unwrap();
+    for (id, (_size, test_case)) in smallest.values().enumerate() {
+        fs::copy(test_case, output.join(format!("{id}"))).unwrap();
+    }
+    eprintln!(
+        "kept {} of {} test cases in {}",
+        smallest.len(),
+        test_cases.len(),
+        output.display()
+    );
+}
+
 fn invoke_testcase(test_case: impl AsRef<Path>, shared_library_path: impl AsRef<Path>) {
     let data = fs::read(test_case).unwrap();
     let library = FuzzTarget::from_path(shared_library_path);
@@ -719,19 +1029,48 @@ fn invoke_testcase(test_case: impl AsRef<Path>, shared_library_path: impl AsRef<
     println!("{}", serde_json::to_string(&result).unwrap());
 }
 
+fn assert_structured(libraries: &[FuzzTarget]) {
+    assert!(
+        libraries.iter().all(FuzzTarget::supports_structured),
+        "structured fuzzing requires targets defined with a `RequestGenerator`"
+    );
+}
+
+/// Decodes the request that the fuzzer input maps to, along with its serialized form
+///
+/// In structured mode, the request is generated from the fuzzer input by the first target.
+/// Otherwise, the fuzzer input is the serialized request.
+fn prepare_input<'a>(
+    data: &'a [u8],
+    libraries: &[FuzzTarget],
+    structured: bool,
+) -> Option<(HttpRequest, Cow<'a, [u8]>)> {
+    if structured {
+        let request = libraries[0].generate_request(data)?;
+        let bytes = request.as_bytes();
+        Some((request, Cow::Owned(bytes)))
+    } else {
+        let request = HttpRequest::from_unknown_bytes(data)?;
+        Some((request, Cow::Borrowed(data)))
+    }
+}
+
 /// Enters the fuzzing loop. This method should only be entered when `afl` is driving the binary
-fn enter_fuzz_loop(libraries: Vec<FuzzTarget>, mut log: Option<BufWriter<fs::File>>) {
+fn enter_fuzz_loop(
+    libraries: Vec<FuzzTarget>,
+    mut log: Option<BufWriter<fs::File>>,
+    structured: bool,
+) {
     afl::fuzz(true, |data: &[u8]| {
         use std::io::Write;
         #[allow(clippy::disallowed_methods)]
         let start = SystemTime::now();
 
-        let http_request = HttpRequest::from_unknown_bytes(data);
-        if let Some(request) = http_request {
+        if let Some((request, data)) = prepare_input(data, &libraries, structured) {
             if request.into_http_request_04x().is_some() {
                 let mut results = vec![];
                 for library in &libraries {
-                    results.push(library.invoke_bytes(data));
+                    results.push(library.invoke_bytes(&data));
                 }
                 log.iter_mut().for_each(|log| {
                     log.write_all(
@@ -748,7 +1087,7 @@ fn enter_fuzz_loop(libraries: Vec<FuzzTarget>, mut log: Option<BufWriter<fs::Fil
                 });
                 for result in &results {
                     if result.response != results[0].response {
-                        if check_for_nondeterminism(data, &libraries) {
+                        if check_for_nondeterminism(&data, &libraries) {
                             break;
                         }
                         panic!("inconsistent results: {:#?}", results);
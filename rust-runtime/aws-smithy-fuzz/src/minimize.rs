@@ -0,0 +1,289 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Test case minimization.
+//!
+//! [`minimize`] delta-debugs an [`HttpRequest`]: it repeatedly removes headers, trailers, query
+//! parameters and chunks of the body, keeping each removal that preserves the behavior being
+//! minimized, until no removal does. [`protocol_test`] renders the result as a Smithy protocol test.
+
+use crate::HttpRequest;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Minimizes `request` while `is_interesting` holds.
+///
+/// `is_interesting` must hold for `request`. It's only called with requests that are valid HTTP
+/// requests, and the request returned is the smallest request found for which it holds.
+pub fn minimize(
+    request: &HttpRequest,
+    mut is_interesting: impl FnMut(&HttpRequest) -> bool,
+) -> HttpRequest {
+    let mut test = |candidate: &HttpRequest| {
+        candidate.into_http_request_04x().is_some() && is_interesting(candidate)
+    };
+    let mut current = request.clone();
+    loop {
+        let before = current.clone();
+
+        let headers = minimize_list(flatten(&current.headers), |headers| {
+            test(&HttpRequest {
+                headers: unflatten(headers),
+                ..current.clone()
+            })
+        });
+        current.headers = unflatten(&headers);
+
+        let trailers = minimize_list(flatten(&current.trailers), |trailers| {
+            test(&HttpRequest {
+                trailers: unflatten(trailers),
+                ..current.clone()
+            })
+        });
+        current.trailers = unflatten(&trailers);
+
+        if let Some((path, query)) = current.uri.split_once('?') {
+            let path = path.to_string();
+            let params: Vec<String> = query.split('&').map(str::to_string).collect();
+            let params = minimize_list(params, |params| {
+                test(&HttpRequest {
+                    uri: with_query(&path, params),
+                    ..current.clone()
+                })
+            });
+            current.uri = with_query(&path, &params);
+        }
+
+        let body = minimize_list(current.body.clone(), |body| {
+            test(&HttpRequest {
+                body: body.to_vec(),
+                ..current.clone()
+            })
+        });
+        current.body = body;
+
+        if current == before {
+            return current;
+        }
+    }
+}
+
+/// Removes chunks of `items`, from halves down to single items, while `test` holds.
+fn minimize_list<T: Clone>(mut items: Vec<T>, mut test: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = items.len().div_ceil(2);
+    while chunk > 0 {
+        let mut start = 0;
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[end..]);
+            if test(&candidate) {
+                items = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    items
+}
+
+/// Flattens headers into sorted name/value pairs, so that minimization is deterministic.
+fn flatten(headers: &HashMap<String, Vec<String>>) -> Vec<(String, String)> {
+    let mut pairs: Vec<_> = headers
+        .iter()
+        .flat_map(|(name, values)| {
+            values
+                .iter()
+                .map(move |value| (name.clone(), value.clone()))
+        })
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+fn unflatten(pairs: &[(String, String)]) -> HashMap<String, Vec<String>> {
+    let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, value) in pairs {
+        headers.entry(name.clone()).or_default().push(value.clone());
+    }
+    headers
+}
+
+fn with_query(path: &str, params: &[String]) -> String {
+    if params.is_empty() {
+        path.to_string()
+    } else {
+        format!("{path}?{}", params.join("&"))
+    }
+}
+
+/// Renders `request` as a case of Smithy's `@httpMalformedRequestTests` trait.
+///
+/// `protocol` is the shape ID of the protocol trait of the service, e.g. `aws.protocols#restJson1`,
+/// and `status` is the status code the server is expected to respond with. Bodies that aren't
+/// valid UTF-8 are base64 encoded.
+pub fn protocol_test(id: &str, protocol: &str, request: &HttpRequest, status: u16) -> String {
+    let (path, query) = match request.uri.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (request.uri.as_str(), None),
+    };
+    let mut out = String::new();
+    writeln!(out, "{{").unwrap();
+    writeln!(out, "    id: {},", quote(id)).unwrap();
+    writeln!(
+        out,
+        "    documentation: \"Minimized from a test case found by aws-smithy-fuzz\","
+    )
+    .unwrap();
+    writeln!(out, "    protocol: {protocol},").unwrap();
+    writeln!(out, "    request: {{").unwrap();
+    writeln!(out, "        method: {},", quote(&request.method)).unwrap();
+    writeln!(out, "        uri: {},", quote(path)).unwrap();
+    if let Some(query) = query {
+        let params: Vec<_> = query.split('&').map(quote).collect();
+        writeln!(out, "        queryParams: [{}],", params.join(", ")).unwrap();
+    }
+    if !request.headers.is_empty() {
+        let mut names: Vec<_> = request.headers.keys().collect();
+        names.sort();
+        writeln!(out, "        headers: {{").unwrap();
+        for name in names {
+            let value = request.headers[name].join(", ");
+            writeln!(out, "            {}: {},", quote(name), quote(&value)).unwrap();
+        }
+        writeln!(out, "        }},").unwrap();
+    }
+    if !request.body.is_empty() {
+        let body = match std::str::from_utf8(&request.body) {
+            Ok(body) => body.to_string(),
+            Err(_) => aws_smithy_types::base64::encode(&request.body),
+        };
+        writeln!(out, "        body: {},", quote(&body)).unwrap();
+    }
+    writeln!(out, "    }},").unwrap();
+    writeln!(out, "    response: {{").unwrap();
+    writeln!(out, "        code: {status},").unwrap();
+    writeln!(out, "    }},").unwrap();
+    write!(out, "}}").unwrap();
+    out
+}
+
+/// Quotes a string as a Smithy IDL string literal, which shares its escapes with JSON.
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::minimize::{minimize, minimize_list, protocol_test};
+    use crate::HttpRequest;
+    use std::collections::HashMap;
+
+    fn request() -> HttpRequest {
+        HttpRequest {
+            uri: "/items/a?x=1&y=2&z=3".to_string(),
+            method: "POST".to_string(),
+            headers: HashMap::from([
+                (
+                    "content-type".to_string(),
+                    vec!["application/json".to_string()],
+                ),
+                (
+                    "x-noise".to_string(),
+                    vec!["a".to_string(), "b".to_string()],
+                ),
+            ]),
+            trailers: HashMap::from([("x-trailer".to_string(), vec!["t".to_string()])]),
+            body: br#"{"name": "crash", "other": 12345}"#.to_vec(),
+        }
+    }
+
+    #[test]
+    fn minimize_list_keeps_required_items() {
+        let items: Vec<u32> = (0..100).collect();
+        let minimized = minimize_list(items, |items| items.contains(&17) && items.contains(&80));
+        assert_eq!(vec![17, 80], minimized);
+    }
+
+    #[test]
+    fn minimize_removes_everything_irrelevant() {
+        let minimized = minimize(&request(), |request| {
+            request.headers.contains_key("content-type")
+                && request.uri.contains("y=2")
+                && request.body.windows(5).any(|w| w == b"crash")
+        });
+        assert_eq!(
+            HttpRequest {
+                uri: "/items/a?y=2".to_string(),
+                method: "POST".to_string(),
+                headers: HashMap::from([(
+                    "content-type".to_string(),
+                    vec!["application/json".to_string()]
+                )]),
+                trailers: HashMap::new(),
+                body: b"crash".to_vec(),
+            },
+            minimized
+        );
+    }
+
+    #[test]
+    fn minimize_returns_the_request_if_nothing_can_be_removed() {
+        let request = request();
+        assert_eq!(
+            request,
+            minimize(&request, |candidate| candidate == &request)
+        );
+    }
+
+    #[test]
+    fn renders_protocol_test() {
+        let request = HttpRequest {
+            uri: "/items/a?y=2".to_string(),
+            method: "POST".to_string(),
+            headers: HashMap::from([(
+                "content-type".to_string(),
+                vec!["application/json".to_string()],
+            )]),
+            trailers: HashMap::new(),
+            body: br#"{"name": "crash"}"#.to_vec(),
+        };
+        pretty_assertions::assert_eq!(
+            r#"{
+    id: "FuzzCase",
+    documentation: "Minimized from a test case found by aws-smithy-fuzz",
+    protocol: aws.protocols#restJson1,
+    request: {
+        method: "POST",
+        uri: "/items/a",
+        queryParams: ["y=2"],
+        headers: {
+            "content-type": "application/json",
+        },
+        body: "{\"name\": \"crash\"}",
+    },
+    response: {
+        code: 400,
+    },
+}"#,
+            protocol_test("FuzzCase", "aws.protocols#restJson1", &request, 400)
+        );
+    }
+
+    #[test]
+    fn binary_bodies_are_base64_encoded() {
+        let request = HttpRequest {
+            uri: "/".to_string(),
+            method: "POST".to_string(),
+            body: vec![0xbf, 0xff],
+            ..Default::default()
+        };
+        assert!(
+            protocol_test("FuzzCase", "smithy.protocols#rpcv2Cbor", &request, 400)
+                .contains(r#"body: "v/8=","#)
+        );
+    }
+}
@@ -0,0 +1,544 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Structure-aware request generation.
+//!
+//! Mutating raw [`HttpRequest`] bytes rarely produces a request that makes it past routing and
+//! into the deserializers of a server. Instead, a [`RequestGenerator`] interprets the fuzzer input
+//! as a source of [`arbitrary`] decisions: it picks an operation, generates a value for every
+//! member of the operation input by following its [`Schema`], and serializes it with a
+//! [`ClientProtocol`] into a valid protocol request.
+//!
+//! Mutations of the fuzzer input therefore become mutations of the members of the input, which
+//! exercises deserialization and constraint validation instead of request routing.
+
+use crate::HttpRequest;
+use arbitrary::Unstructured;
+use aws_smithy_schema::protocol::{ClientProtocol, SharedClientProtocol};
+use aws_smithy_schema::serde::{SerdeError, SerializableStruct, ShapeSerializer};
+use aws_smithy_schema::{Schema, ShapeType};
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::{BigDecimal, BigInteger, DateTime, Document, Number};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The maximum depth of nested aggregates (lists, maps, structures and documents).
+const MAX_DEPTH: usize = 4;
+
+/// The maximum number of elements generated for a list, a map or a document.
+const MAX_COLLECTION_LEN: usize = 8;
+
+/// An operation that a [`RequestGenerator`] generates requests for.
+#[derive(Debug)]
+pub struct StructuredOperation {
+    input_schema: &'static Schema,
+    endpoint: String,
+    headers: Vec<(String, String)>,
+}
+
+impl StructuredOperation {
+    /// Creates an operation from the schema of its input structure.
+    ///
+    /// For protocols using HTTP bindings, the URI and method of the request come from the `@http`
+    /// trait carried by `input_schema`.
+    pub fn new(input_schema: &'static Schema) -> Self {
+        Self {
+            input_schema,
+            endpoint: String::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Sets the endpoint the request is serialized against.
+    ///
+    /// RPC protocols don't use the `@http` trait: use this to set the path of the operation,
+    /// e.g. `/service/PokemonService/operation/GetPokemonSpecies` for `rpcv2Cbor`.
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// Adds a header to every request, on top of the ones set by the protocol.
+    ///
+    /// This is typically used for protocol headers that aren't derived from the input, like
+    /// `X-Amz-Target` for the AWS JSON protocols or `Smithy-Protocol` for `rpcv2Cbor`.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Generates protocol requests from fuzzer input, following the schemas of a set of operations.
+///
+/// ```ignore
+/// use aws_smithy_cbor::protocol::RpcV2CborProtocol;
+/// use aws_smithy_fuzz::{RequestGenerator, StructuredOperation};
+///
+/// let generator = RequestGenerator::new(RpcV2CborProtocol::new())
+///     .with_operation(
+///         StructuredOperation::new(GetPokemonSpeciesInput::SCHEMA)
+///             .with_endpoint("/service/PokemonService/operation/GetPokemonSpecies")
+///             .with_header("Smithy-Protocol", "rpc-v2-cbor"),
+///     )
+///     .with_dictionary(["PIKACHU", "en"]);
+/// let request = generator.generate(b"fuzzer input");
+/// ```
+pub struct RequestGenerator {
+    protocol: SharedClientProtocol,
+    operations: Vec<StructuredOperation>,
+    dictionary: Vec<String>,
+}
+
+impl RequestGenerator {
+    /// Creates a generator serializing requests with `protocol`.
+    pub fn new(protocol: impl ClientProtocol + 'static) -> Self {
+        Self {
+            protocol: SharedClientProtocol::new(protocol),
+            operations: Vec::new(),
+            dictionary: Vec::new(),
+        }
+    }
+
+    /// Adds an operation to generate requests for.
+    pub fn with_operation(mut self, operation: StructuredOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Adds words that generated strings are drawn from, in addition to arbitrary strings.
+    ///
+    /// The dictionary of the `lexicon.json` produced by `fuzzgen` contains the enum values and
+    /// the constants of the model, which are hard for a fuzzer to discover by itself.
+    pub fn with_dictionary(mut self, words: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.dictionary.extend(words.into_iter().map(Into::into));
+        self
+    }
+
+    /// Generates a request from the fuzzer input.
+    ///
+    /// Returns `None` if the generated input can't be serialized, e.g. because the value generated
+    /// for an `@httpLabel` member is empty.
+    pub fn generate(&self, data: &[u8]) -> Option<HttpRequest> {
+        let mut u = Unstructured::new(data);
+        let operation = u.choose(&self.operations).ok()?;
+        let input = Generator {
+            u: &mut u,
+            dictionary: &self.dictionary,
+        }
+        .structure(operation.input_schema, 0)
+        .ok()?;
+        let request = self
+            .protocol
+            .serialize_request(
+                &input,
+                operation.input_schema,
+                &operation.endpoint,
+                &ConfigBag::base(),
+            )
+            .ok()?;
+
+        let mut headers: HashMap<String, Vec<String>> = HashMap::new();
+        for (name, value) in request.headers() {
+            headers
+                .entry(name.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+        for (name, value) in &operation.headers {
+            headers.entry(name.clone()).or_default().push(value.clone());
+        }
+        Some(HttpRequest {
+            uri: request.uri().to_string(),
+            method: request.method().to_string(),
+            headers,
+            trailers: Default::default(),
+            body: request.body().bytes().unwrap_or_default().to_vec(),
+        })
+    }
+}
+
+impl fmt::Debug for RequestGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestGenerator")
+            .field("protocol", &self.protocol.protocol_id())
+            .field("operations", &self.operations)
+            .field("dictionary", &self.dictionary.len())
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+pub fn encode_generated_request(request: Option<HttpRequest>) -> Vec<u8> {
+    bincode::serialize(&request).unwrap()
+}
+
+pub(crate) fn decode_generated_request(bytes: &[u8]) -> Option<HttpRequest> {
+    bincode::deserialize(bytes).unwrap()
+}
+
+/// A value generated for a shape, serialized by following its schema.
+#[derive(Debug, Clone)]
+enum Value {
+    Boolean(bool),
+    Byte(i8),
+    Short(i16),
+    Integer(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    BigInteger(BigInteger),
+    BigDecimal(BigDecimal),
+    String(String),
+    Blob(Vec<u8>),
+    Timestamp(DateTime),
+    Document(Document),
+    List {
+        element: &'static Schema,
+        elements: Vec<Value>,
+    },
+    Map {
+        key: &'static Schema,
+        value: &'static Schema,
+        entries: Vec<(String, Value)>,
+    },
+    Structure(StructureValue),
+}
+
+/// The members generated for a structure or a union.
+#[derive(Debug, Clone)]
+struct StructureValue {
+    members: Vec<(&'static Schema, Value)>,
+}
+
+impl SerializableStruct for StructureValue {
+    fn serialize_members(&self, serializer: &mut dyn ShapeSerializer) -> Result<(), SerdeError> {
+        for (schema, value) in &self.members {
+            value.serialize(schema, serializer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Value {
+    fn serialize(
+        &self,
+        schema: &Schema,
+        serializer: &mut dyn ShapeSerializer,
+    ) -> Result<(), SerdeError> {
+        match self {
+            Value::Boolean(value) => serializer.write_boolean(schema, *value),
+            Value::Byte(value) => serializer.write_byte(schema, *value),
+            Value::Short(value) => serializer.write_short(schema, *value),
+            Value::Integer(value) => serializer.write_integer(schema, *value),
+            Value::Long(value) => serializer.write_long(schema, *value),
+            Value::Float(value) => serializer.write_float(schema, *value),
+            Value::Double(value) => serializer.write_double(schema, *value),
+            Value::BigInteger(value) => serializer.write_big_integer(schema, value),
+            Value::BigDecimal(value) => serializer.write_big_decimal(schema, value),
+            Value::String(value) => serializer.write_string(schema, value),
+            Value::Blob(value) => serializer.write_blob(schema, value),
+            Value::Timestamp(value) => serializer.write_timestamp(schema, value),
+            Value::Document(value) => serializer.write_document(schema, value),
+            Value::List { element, elements } => serializer.write_list(schema, &|serializer| {
+                for value in elements {
+                    value.serialize(element, serializer)?;
+                }
+                Ok(())
+            }),
+            Value::Map {
+                key,
+                value,
+                entries,
+            } => serializer.write_map(schema, &|serializer| {
+                for (entry_key, entry_value) in entries {
+                    serializer.write_string(key, entry_key)?;
+                    entry_value.serialize(value, serializer)?;
+                }
+                Ok(())
+            }),
+            Value::Structure(value) => serializer.write_struct(schema, value),
+        }
+    }
+}
+
+/// Where a generated string ends up, which restricts the characters it can contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    /// The body, a URI label or a query parameter, which are escaped by the protocol.
+    Escaped,
+    /// The value of a header.
+    HeaderValue,
+    /// The suffix of the name of a header bound with `@httpPrefixHeaders`.
+    HeaderName,
+}
+
+struct Generator<'a, 'u> {
+    u: &'a mut Unstructured<'u>,
+    dictionary: &'a [String],
+}
+
+impl Generator<'_, '_> {
+    fn structure(
+        &mut self,
+        schema: &'static Schema,
+        depth: usize,
+    ) -> arbitrary::Result<StructureValue> {
+        let mut members = Vec::new();
+        if schema.is_union() {
+            // Unions have exactly one member set.
+            if !schema.members().is_empty() {
+                let member: &'static Schema = *self.u.choose(schema.members())?;
+                if let Some(value) = self.member(member, depth + 1)? {
+                    members.push((member, value));
+                }
+            }
+            return Ok(StructureValue { members });
+        }
+        for member in schema.members() {
+            let member: &'static Schema = member;
+            // Members are optional from the point of view of the generator: omitting required
+            // members exercises the validation of the server.
+            if self.u.arbitrary()? {
+                if let Some(value) = self.member(member, depth + 1)? {
+                    members.push((member, value));
+                }
+            }
+        }
+        Ok(StructureValue { members })
+    }
+
+    /// Generates a value for a member, or `None` if the schema doesn't carry enough information
+    /// to generate one.
+    fn member(
+        &mut self,
+        schema: &'static Schema,
+        depth: usize,
+    ) -> arbitrary::Result<Option<Value>> {
+        let placement = if schema.http_header().is_some() {
+            Placement::HeaderValue
+        } else {
+            Placement::Escaped
+        };
+        self.value(schema, placement, depth)
+    }
+
+    fn value(
+        &mut self,
+        schema: &'static Schema,
+        placement: Placement,
+        depth: usize,
+    ) -> arbitrary::Result<Option<Value>> {
+        let value = match schema.shape_type() {
+            ShapeType::Boolean => Value::Boolean(self.u.arbitrary()?),
+            ShapeType::Byte => Value::Byte(self.u.arbitrary()?),
+            ShapeType::Short => Value::Short(self.u.arbitrary()?),
+            ShapeType::Integer => Value::Integer(self.u.arbitrary()?),
+            ShapeType::Long => Value::Long(self.u.arbitrary()?),
+            ShapeType::Float => Value::Float(self.u.arbitrary()?),
+            ShapeType::Double => Value::Double(self.u.arbitrary()?),
+            ShapeType::BigInteger => Value::BigInteger(
+                self.u
+                    .arbitrary::<i128>()?
+                    .to_string()
+                    .parse()
+                    .expect("integers are valid big integers"),
+            ),
+            ShapeType::BigDecimal => Value::BigDecimal(
+                format!(
+                    "{}.{}",
+                    self.u.arbitrary::<i64>()?,
+                    self.u.arbitrary::<u32>()?
+                )
+                .parse()
+                .expect("decimals are valid big decimals"),
+            ),
+            ShapeType::String => Value::String(self.string(placement)?),
+            ShapeType::Blob => Value::Blob(self.u.arbitrary()?),
+            ShapeType::Timestamp => Value::Timestamp(DateTime::from_secs_and_nanos(
+                // Between 1900-01-01 and 9999-12-31, the range supported by all timestamp formats.
+                self.u.int_in_range(-2_208_988_800..=253_402_300_799)?,
+                self.u.int_in_range(0..=999_999_999)?,
+            )),
+            ShapeType::Document => Value::Document(self.document(depth)?),
+            ShapeType::List => {
+                let Some(element) = schema.member_static() else {
+                    return Ok(None);
+                };
+                let mut elements = Vec::new();
+                if depth < MAX_DEPTH {
+                    for _ in 0..self.u.int_in_range(0..=MAX_COLLECTION_LEN)? {
+                        if let Some(value) = self.value(element, placement, depth + 1)? {
+                            elements.push(value);
+                        }
+                    }
+                }
+                Value::List { element, elements }
+            }
+            ShapeType::Map => {
+                let (Some(key), Some(value)) = (schema.key_static(), schema.member_static()) else {
+                    return Ok(None);
+                };
+                let (key_placement, value_placement) = if schema.http_prefix_headers().is_some() {
+                    (Placement::HeaderName, Placement::HeaderValue)
+                } else {
+                    (placement, placement)
+                };
+                let mut entries = Vec::new();
+                if depth < MAX_DEPTH {
+                    for _ in 0..self.u.int_in_range(0..=MAX_COLLECTION_LEN)? {
+                        let entry_key = self.string(key_placement)?;
+                        if let Some(entry_value) = self.value(value, value_placement, depth + 1)? {
+                            entries.push((entry_key, entry_value));
+                        }
+                    }
+                }
+                Value::Map {
+                    key,
+                    value,
+                    entries,
+                }
+            }
+            ShapeType::Structure | ShapeType::Union => {
+                // Member schemas don't reference the members of the structure they target: nested
+                // structures are generated empty, and nested unions are omitted.
+                if schema.members().is_empty() && (schema.is_union() || depth >= MAX_DEPTH) {
+                    return Ok(None);
+                }
+                Value::Structure(self.structure(schema, depth)?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+
+    fn string(&mut self, placement: Placement) -> arbitrary::Result<String> {
+        let value = if !self.dictionary.is_empty() && self.u.arbitrary()? {
+            self.u.choose(self.dictionary)?.clone()
+        } else {
+            self.u.arbitrary()?
+        };
+        Ok(match placement {
+            Placement::Escaped => value,
+            // Control characters aren't allowed in header values.
+            Placement::HeaderValue => value
+                .chars()
+                .filter(|c| !c.is_ascii_control() || *c == '\t')
+                .collect(),
+            Placement::HeaderName => value
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect(),
+        })
+    }
+
+    fn document(&mut self, depth: usize) -> arbitrary::Result<Document> {
+        let max_kind = if depth < MAX_DEPTH { 7 } else { 5 };
+        Ok(match self.u.int_in_range(0..=max_kind)? {
+            0 => Document::Null,
+            1 => Document::Bool(self.u.arbitrary()?),
+            2 => Document::Number(Number::PosInt(self.u.arbitrary()?)),
+            3 => Document::Number(Number::NegInt(self.u.arbitrary()?)),
+            4 => Document::Number(Number::Float(self.u.arbitrary()?)),
+            5 => Document::String(self.string(Placement::Escaped)?),
+            6 => {
+                let mut elements = Vec::new();
+                for _ in 0..self.u.int_in_range(0..=MAX_COLLECTION_LEN)? {
+                    elements.push(self.document(depth + 1)?);
+                }
+                Document::Array(elements)
+            }
+            _ => {
+                let mut entries = HashMap::new();
+                for _ in 0..self.u.int_in_range(0..=MAX_COLLECTION_LEN)? {
+                    let key = self.string(Placement::Escaped)?;
+                    entries.insert(key, self.document(depth + 1)?);
+                }
+                Document::Object(entries)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{RequestGenerator, StructuredOperation};
+    use aws_smithy_json::protocol::aws_rest_json_1::AwsRestJsonProtocol;
+    use aws_smithy_schema::traits::HttpTrait;
+    use aws_smithy_schema::{prelude, shape_id, Schema, ShapeType};
+
+    static NAME: Schema = Schema::new_member(
+        shape_id!("test", "GetItemInput", "name"),
+        ShapeType::String,
+        "name",
+        0,
+    )
+    .with_http_label();
+    static FILTER: Schema = Schema::new_member(
+        shape_id!("test", "GetItemInput", "filter"),
+        ShapeType::String,
+        "filter",
+        1,
+    )
+    .with_http_query("filter");
+    static TRACE: Schema = Schema::new_member(
+        shape_id!("test", "GetItemInput", "trace"),
+        ShapeType::String,
+        "trace",
+        2,
+    )
+    .with_http_header("x-trace");
+    static TAGS: Schema = Schema::new_member(
+        shape_id!("test", "GetItemInput", "tags"),
+        ShapeType::List,
+        "tags",
+        3,
+    )
+    .with_list_member(&prelude::STRING);
+    static INPUT: Schema = Schema::new_struct(
+        shape_id!("test", "GetItemInput"),
+        ShapeType::Structure,
+        &[&NAME, &FILTER, &TRACE, &TAGS],
+    )
+    .with_http(HttpTrait::new("POST", "/items/{name}", None));
+
+    fn generator() -> RequestGenerator {
+        RequestGenerator::new(AwsRestJsonProtocol::new())
+            .with_operation(StructuredOperation::new(&INPUT).with_header("x-extra", "extra"))
+    }
+
+    #[test]
+    fn generates_requests_following_the_schema() {
+        let mut generated = 0;
+        for seed in 0..256_u32 {
+            let data: Vec<u8> = (0..64_u32)
+                .map(|idx| (seed.wrapping_mul(31).wrapping_add(idx * 7) % 251) as u8)
+                .collect();
+            let Some(request) = generator().generate(&data) else {
+                continue;
+            };
+            generated += 1;
+            assert_eq!("POST", request.method);
+            assert!(request.uri.starts_with("/items/"), "{}", request.uri);
+            assert_eq!(vec!["extra".to_string()], request.headers["x-extra"]);
+            assert!(
+                request.into_http_request_04x().is_some(),
+                "generated requests are valid HTTP requests"
+            );
+        }
+        assert!(generated > 0, "no request was generated");
+    }
+
+    #[test]
+    fn generation_is_deterministic() {
+        let data = b"some fuzzer input that is long enough to generate members";
+        assert_eq!(generator().generate(data), generator().generate(data));
+    }
+
+    #[test]
+    fn no_operations_generates_nothing() {
+        let generator = RequestGenerator::new(AwsRestJsonProtocol::new());
+        assert_eq!(None, generator.generate(b"input"));
+    }
+}
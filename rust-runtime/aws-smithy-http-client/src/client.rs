@@ -4,6 +4,8 @@
  */
 
 mod dns;
+/// Connection pool metrics and introspection
+pub mod pool;
 /// Proxy configuration
 pub mod proxy;
 mod timeout;
@@ -43,7 +45,6 @@ use hyper_util::client::legacy::connect::dns::GaiResolver;
 use hyper_util::client::legacy::connect::{
     capture_connection, CaptureConnection, Connect, HttpConnector as HyperHttpConnector, HttpInfo,
 };
-use hyper_util::client::proxy::matcher::Matcher;
 use hyper_util::rt::{TokioExecutor, TokioTimer};
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -80,6 +81,10 @@ pub fn default_connector(
     }
 }
 
+/// Delay before racing a connection attempt to the other address family, as recommended by
+/// [RFC 8305](https://www.rfc-editor.org/rfc/rfc8305#section-8).
+const DEFAULT_HAPPY_EYEBALLS_DELAY: Duration = Duration::from_millis(250);
+
 /// [`HttpConnector`] used to make HTTP requests.
 ///
 /// This connector also implements socket connect and read timeouts.
@@ -89,6 +94,7 @@ pub fn default_connector(
 #[derive(Debug)]
 pub struct Connector {
     adapter: Box<dyn HttpConnector>,
+    pool_tracker: Option<pool::PoolTracker>,
 }
 
 impl Connector {
@@ -96,6 +102,14 @@ impl Connector {
     pub fn builder() -> ConnectorBuilder {
         ConnectorBuilder::default()
     }
+
+    /// Returns a snapshot of this connector's connection pool statistics.
+    ///
+    /// Returns `None` unless pool metrics were enabled with
+    /// [`ConnectorBuilder::pool_metrics`].
+    pub fn pool_stats(&self) -> Option<pool::PoolStats> {
+        self.pool_tracker.as_ref().map(pool::PoolTracker::stats)
+    }
 }
 
 impl HttpConnector for Connector {
@@ -115,6 +129,10 @@ pub struct ConnectorBuilder<Tls = TlsUnset> {
     enable_tcp_nodelay: bool,
     interface: Option<String>,
     proxy_config: Option<proxy::ProxyConfig>,
+    pool_metrics: bool,
+    pool_tracker: Option<pool::PoolTracker>,
+    happy_eyeballs_delay: Option<Option<Duration>>,
+    split_connect_timeout: bool,
     #[allow(unused)]
     tls: Tls,
 }
@@ -135,6 +153,10 @@ impl<Tls: Default> Default for ConnectorBuilder<Tls> {
             enable_tcp_nodelay: true,
             interface: None,
             proxy_config: None,
+            pool_metrics: false,
+            pool_tracker: None,
+            happy_eyeballs_delay: None,
+            split_connect_timeout: false,
             tls: Tls::default(),
         }
     }
@@ -166,6 +188,10 @@ impl ConnectorBuilder<TlsUnset> {
             proxy_config: self.proxy_config,
             pool_idle_timeout: self.pool_idle_timeout,
             pool_max_idle_per_host: self.pool_max_idle_per_host,
+            pool_metrics: self.pool_metrics,
+            pool_tracker: self.pool_tracker,
+            happy_eyeballs_delay: self.happy_eyeballs_delay,
+            split_connect_timeout: self.split_connect_timeout,
             tls: TlsProviderSelected {
                 provider,
                 context: TlsContext::default(),
@@ -206,6 +232,34 @@ impl ConnectorBuilder<TlsUnset> {
 impl<Any> ConnectorBuilder<Any> {
     /// Create a [`Connector`] from this builder and a given connector.
     pub(crate) fn wrap_connector<C>(self, tcp_connector: C) -> Connector
+    where
+        C: Send + Sync + 'static,
+        C: Clone,
+        C: tower::Service<Uri>,
+        C::Response: Read + Write + Connection + Send + Sync + Unpin,
+        C: Connect,
+        C::Future: Unpin + Send + 'static,
+        C::Error: Into<BoxError>,
+    {
+        let pool_tracker = match (self.pool_metrics, &self.pool_tracker) {
+            (false, _) => None,
+            (true, Some(tracker)) => Some(tracker.clone()),
+            (true, None) => Some(pool::PoolTracker::new()),
+        };
+        match pool_tracker {
+            Some(tracker) => {
+                let tcp_connector = tracker.wrap_connector(tcp_connector);
+                self.build_connector(tcp_connector, Some(tracker))
+            }
+            None => self.build_connector(tcp_connector, None),
+        }
+    }
+
+    fn build_connector<C>(
+        self,
+        tcp_connector: C,
+        pool_tracker: Option<pool::PoolTracker>,
+    ) -> Connector
     where
         C: Send + Sync + 'static,
         C: Clone,
@@ -247,13 +301,15 @@ impl<Any> ConnectorBuilder<Any> {
         let proxy_matcher = self
             .proxy_config
             .as_ref()
-            .map(|config| config.clone().into_hyper_util_matcher());
+            .map(|config| config.clone().into_proxy_matcher());
 
         Connector {
             adapter: Box::new(Adapter {
                 client: read_timeout,
                 proxy_matcher,
+                pool_tracker: pool_tracker.clone(),
             }),
+            pool_tracker,
         }
     }
 
@@ -268,6 +324,8 @@ impl<Any> ConnectorBuilder<Any> {
     fn base_connector_with_resolver<R>(&self, resolver: R) -> HyperHttpConnector<R> {
         let mut conn = HyperHttpConnector::new_with_resolver(resolver);
         conn.set_nodelay(self.enable_tcp_nodelay);
+        conn.set_happy_eyeballs_timeout(self.happy_eyeballs_timeout());
+        conn.set_connect_timeout(self.attempt_connect_timeout());
         #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
         if let Some(interface) = &self.interface {
             conn.set_interface(interface);
@@ -275,6 +333,26 @@ impl<Any> ConnectorBuilder<Any> {
         conn
     }
 
+    /// The delay before racing the other address family, as passed to hyper's `HttpConnector`
+    fn happy_eyeballs_timeout(&self) -> Option<Duration> {
+        self.happy_eyeballs_delay
+            .unwrap_or(Some(DEFAULT_HAPPY_EYEBALLS_DELAY))
+    }
+
+    /// The connect timeout that hyper's `HttpConnector` splits across the resolved addresses.
+    ///
+    /// When opted in, every connection attempt gets a share of the connect timeout, so that an
+    /// address that never answers doesn't use up the whole timeout before the next address is
+    /// tried. The overall timeout is enforced by `ConnectTimeout` either way.
+    fn attempt_connect_timeout(&self) -> Option<Duration> {
+        if !self.split_connect_timeout {
+            return None;
+        }
+        self.connector_settings
+            .as_ref()
+            .and_then(|settings| settings.connect_timeout())
+    }
+
     /// Set the async sleep implementation used for timeouts
     ///
     /// Calling this is only necessary for testing or to use something other than
@@ -466,6 +544,124 @@ impl<Any> ConnectorBuilder<Any> {
         self
     }
 
+    /// Set the delay between starting a connection attempt to one address family and racing it
+    /// with an attempt to the other, as described in [RFC 8305] (Happy Eyeballs).
+    ///
+    /// When a host resolves to both IPv6 and IPv4 addresses, the connector first tries the
+    /// addresses of the same family as the first resolved address. If none of them has connected
+    /// after this delay, attempts to the addresses of the other family start in parallel, and the
+    /// first connection to succeed is used. This avoids waiting for the connect timeout when one
+    /// address family is unreachable, for example when IPv6 traffic is blackholed.
+    ///
+    /// Within an address family, addresses are tried in the order they were resolved. See
+    /// [`split_connect_timeout`](Self::split_connect_timeout) to bound each of these attempts.
+    ///
+    /// Pass `None` to disable racing, in which case addresses are tried one after another.
+    ///
+    /// Defaults to 250 milliseconds, the connection attempt delay recommended by RFC 8305.
+    ///
+    /// [RFC 8305]: https://www.rfc-editor.org/rfc/rfc8305
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rustls-aws-lc")]
+    /// # {
+    /// use aws_smithy_http_client::{Connector, tls};
+    /// use std::time::Duration;
+    ///
+    /// let connector = Connector::builder()
+    ///     .happy_eyeballs_delay(Duration::from_millis(100))
+    ///     .tls_provider(tls::Provider::Rustls(tls::rustls_provider::CryptoMode::AwsLc))
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn happy_eyeballs_delay<D>(mut self, val: D) -> Self
+    where
+        D: Into<Option<Duration>>,
+    {
+        self.happy_eyeballs_delay = Some(val.into());
+        self
+    }
+
+    /// Set the delay between starting a connection attempt to one address family and racing it
+    /// with an attempt to the other.
+    ///
+    /// Pass `None` to use the default delay of 250 milliseconds, `Some(None)` to disable racing.
+    ///
+    /// This is the mutable version of [`happy_eyeballs_delay`](Self::happy_eyeballs_delay).
+    pub fn set_happy_eyeballs_delay(&mut self, val: Option<Option<Duration>>) -> &mut Self {
+        self.happy_eyeballs_delay = val;
+        self
+    }
+
+    /// Split the connect timeout from [`HttpConnectorSettings`] evenly across the resolved addresses.
+    ///
+    /// When enabled, each connection attempt is given an equal share of the connect timeout, so
+    /// that an address that never answers doesn't use up the whole timeout before the next address
+    /// is tried. When disabled, an attempt may take the whole connect timeout.
+    ///
+    /// Either way, establishing the connection fails once the connect timeout elapses.
+    ///
+    /// Defaults to `false`.
+    pub fn split_connect_timeout(mut self, split_connect_timeout: bool) -> Self {
+        self.split_connect_timeout = split_connect_timeout;
+        self
+    }
+
+    /// Split the connect timeout from [`HttpConnectorSettings`] evenly across the resolved addresses.
+    ///
+    /// This is the mutable version of [`split_connect_timeout`](Self::split_connect_timeout).
+    pub fn set_split_connect_timeout(&mut self, split_connect_timeout: bool) -> &mut Self {
+        self.split_connect_timeout = split_connect_timeout;
+        self
+    }
+
+    /// Enable connection pool metrics and introspection.
+    ///
+    /// When enabled, the connector keeps per-host statistics about its connection pool, which can
+    /// be inspected with [`Connector::pool_stats`]. They're also exported as gauges and counters
+    /// through the meter provider of the global
+    /// [`TelemetryProvider`](aws_smithy_observability::TelemetryProvider). See the [`pool`] module
+    /// for the list of instruments.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rustls-aws-lc")]
+    /// # {
+    /// use aws_smithy_http_client::{Connector, tls};
+    ///
+    /// let connector = Connector::builder()
+    ///     .pool_metrics(true)
+    ///     .tls_provider(tls::Provider::Rustls(tls::rustls_provider::CryptoMode::AwsLc))
+    ///     .build();
+    /// assert!(connector.pool_stats().is_some());
+    /// # }
+    /// ```
+    pub fn pool_metrics(mut self, enabled: bool) -> Self {
+        self.pool_metrics = enabled;
+        self
+    }
+
+    /// Enable connection pool metrics and introspection.
+    ///
+    /// This is the mutable version of [`pool_metrics`](Self::pool_metrics).
+    pub fn set_pool_metrics(&mut self, enabled: bool) -> &mut Self {
+        self.pool_metrics = enabled;
+        self
+    }
+
+    /// Share a pool tracker between several connectors, so that their statistics are aggregated
+    /// and their instruments are only registered once.
+    pub(crate) fn set_pool_tracker(&mut self, tracker: Option<pool::PoolTracker>) -> &mut Self {
+        self.pool_metrics = tracker.is_some();
+        self.pool_tracker = tracker;
+        self
+    }
+
     /// Override the Hyper client [`Builder`](hyper_util::client::legacy::Builder) used to construct this client.
     ///
     /// This enables changing settings like forcing HTTP2 and modifying other default client behavior.
@@ -496,7 +692,8 @@ struct Adapter<C> {
     client: timeout::HttpReadTimeout<
         hyper_util::client::legacy::Client<timeout::ConnectTimeout<C>, SdkBody>,
     >,
-    proxy_matcher: Option<Matcher>,
+    proxy_matcher: Option<proxy::ProxyMatcher>,
+    pool_tracker: Option<pool::PoolTracker>,
 }
 
 impl<C> fmt::Debug for Adapter<C> {
@@ -504,6 +701,7 @@ impl<C> fmt::Debug for Adapter<C> {
         f.debug_struct("Adapter")
             .field("client", &"** hyper client **")
             .field("proxy_matcher", &self.proxy_matcher.is_some())
+            .field("pool_tracker", &self.pool_tracker.is_some())
             .finish()
     }
 }
@@ -603,6 +801,10 @@ where
         self.add_proxy_auth_header(&mut request);
 
         let capture_connection = capture_connection(&mut request);
+        let pool_tracking = self
+            .pool_tracker
+            .clone()
+            .map(|tracker| (tracker, capture_connection.clone()));
         if let Some(capture_smithy_connection) =
             request.extensions().get::<CaptureSmithyConnection>()
         {
@@ -613,10 +815,13 @@ where
         use tower::Service;
         let fut = client.call(request);
         HttpConnectorFuture::new(async move {
-            let response = fut
-                .await
-                .map_err(downcast_error)?
-                .map(SdkBody::from_body_1_x);
+            let response = fut.await.map_err(downcast_error)?;
+            let response = match pool_tracking {
+                Some((tracker, capture_connection)) => response.map(|body| {
+                    SdkBody::from_body_1_x(tracker.wrap_body(&capture_connection, body))
+                }),
+                None => response.map(SdkBody::from_body_1_x),
+            };
             match HttpResponse::try_from(response) {
                 Ok(response) => Ok(response),
                 Err(err) => Err(ConnectorError::other(err.into(), None)),
@@ -806,6 +1011,7 @@ pub struct Builder<Tls = TlsUnset> {
     client_builder: Option<hyper_util::client::legacy::Builder>,
     pool_idle_timeout: Option<Option<Duration>>,
     pool_max_idle_per_host: Option<usize>,
+    pool_metrics: bool,
     #[allow(unused)]
     tls_provider: Tls,
 }
@@ -884,12 +1090,13 @@ cfg_tls! {
         /// The trusted certificates will be loaded later when this becomes the selected
         /// HTTP client for a Smithy client.
         pub fn build_https(self) -> SharedHttpClient {
+            let pool_tracker = self.pool_metrics.then(pool::PoolTracker::new);
             build_with_conn_fn(
                 self.client_builder,
                 self.pool_idle_timeout,
                 self.pool_max_idle_per_host,
                 move |client_builder, settings, runtime_components| {
-                    let builder = new_conn_builder(client_builder, settings, runtime_components)
+                    let builder = new_conn_builder(client_builder, settings, runtime_components, pool_tracker.as_ref())
                         .tls_provider(self.tls_provider.provider.clone())
                         .tls_context(self.tls_provider.context.clone());
                     builder.build()
@@ -902,12 +1109,13 @@ cfg_tls! {
             self,
             resolver: impl ResolveDns + Clone + 'static,
         ) -> SharedHttpClient {
+            let pool_tracker = self.pool_metrics.then(pool::PoolTracker::new);
             build_with_conn_fn(
                 self.client_builder,
                 self.pool_idle_timeout,
                 self.pool_max_idle_per_host,
                 move |client_builder, settings, runtime_components| {
-                    let builder = new_conn_builder(client_builder, settings, runtime_components)
+                    let builder = new_conn_builder(client_builder, settings, runtime_components, pool_tracker.as_ref())
                         .tls_provider(self.tls_provider.provider.clone())
                         .tls_context(self.tls_provider.context.clone());
                     builder.build_with_resolver(resolver.clone())
@@ -1016,6 +1224,41 @@ impl<Any> Builder<Any> {
         self.pool_max_idle_per_host = val;
         self
     }
+
+    /// Enable connection pool metrics.
+    ///
+    /// When enabled, per-host connection pool gauges and counters are exported through the meter
+    /// provider of the global [`TelemetryProvider`](aws_smithy_observability::TelemetryProvider).
+    /// The statistics of every connector created by the client are aggregated. See the [`pool`]
+    /// module for the list of instruments.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rustls-aws-lc")]
+    /// # {
+    /// use aws_smithy_http_client::{Builder, tls};
+    ///
+    /// let client = Builder::new()
+    ///     .pool_metrics(true)
+    ///     .tls_provider(tls::Provider::Rustls(tls::rustls_provider::CryptoMode::AwsLc))
+    ///     .build_https();
+    /// # }
+    /// ```
+    pub fn pool_metrics(mut self, enabled: bool) -> Self {
+        self.pool_metrics = enabled;
+        self
+    }
+
+    /// Enable connection pool metrics.
+    ///
+    /// This is the mutable version of [`pool_metrics`](Self::pool_metrics).
+    pub fn set_pool_metrics(&mut self, enabled: bool) -> &mut Self {
+        self.pool_metrics = enabled;
+        self
+    }
 }
 
 impl Builder<TlsUnset> {
@@ -1046,12 +1289,18 @@ impl Builder<TlsUnset> {
     /// Build a new HTTP client without TLS enabled
     #[doc(hidden)]
     pub fn build_http(self) -> SharedHttpClient {
+        let pool_tracker = self.pool_metrics.then(pool::PoolTracker::new);
         build_with_conn_fn(
             self.client_builder,
             self.pool_idle_timeout,
             self.pool_max_idle_per_host,
             move |client_builder, settings, runtime_components| {
-                let builder = new_conn_builder(client_builder, settings, runtime_components);
+                let builder = new_conn_builder(
+                    client_builder,
+                    settings,
+                    runtime_components,
+                    pool_tracker.as_ref(),
+                );
                 builder.build_http()
             },
         )
@@ -1063,6 +1312,7 @@ impl Builder<TlsUnset> {
             client_builder: self.client_builder,
             pool_idle_timeout: self.pool_idle_timeout,
             pool_max_idle_per_host: self.pool_max_idle_per_host,
+            pool_metrics: self.pool_metrics,
             tls_provider: TlsProviderSelected {
                 provider,
                 context: TlsContext::default(),
@@ -1117,7 +1367,7 @@ where
         pool_idle_timeout,
         pool_max_idle_per_host,
         move |client_builder, settings, runtime_components| {
-            let builder = new_conn_builder(client_builder, settings, runtime_components);
+            let builder = new_conn_builder(client_builder, settings, runtime_components, None);
             builder.wrap_connector(tcp_connector_fn())
         },
     )
@@ -1127,9 +1377,11 @@ fn new_conn_builder(
     client_builder: hyper_util::client::legacy::Builder,
     settings: Option<&HttpConnectorSettings>,
     runtime_components: Option<&RuntimeComponents>,
+    pool_tracker: Option<&pool::PoolTracker>,
 ) -> ConnectorBuilder {
     let mut builder = Connector::builder().hyper_builder(client_builder);
     builder.set_connector_settings(settings.cloned());
+    builder.set_pool_tracker(pool_tracker.cloned());
     if let Some(components) = runtime_components {
         builder.set_sleep_impl(components.sleep_impl());
     }
@@ -1397,6 +1649,161 @@ mod test {
         );
     }
 
+    /// Resolves every name to the given addresses, in order.
+    #[derive(Debug, Clone)]
+    struct StaticResolver(Vec<std::net::IpAddr>);
+
+    impl aws_smithy_runtime_api::client::dns::ResolveDns for StaticResolver {
+        fn resolve_dns<'a>(
+            &'a self,
+            _name: &'a str,
+        ) -> aws_smithy_runtime_api::client::dns::DnsFuture<'a> {
+            aws_smithy_runtime_api::client::dns::DnsFuture::ready(Ok(self.0.clone()))
+        }
+    }
+
+    /// Listen on `127.0.0.1` and respond to every request with an empty `200 OK`.
+    async fn responding_ipv4_listener() -> std::net::SocketAddr {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0; 1024];
+                    let _ = stream.read(&mut buf).await;
+                    let _ = stream
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                        .await;
+                });
+            }
+        });
+        addr
+    }
+
+    /// Listen on `[::1]:port` without ever accepting, and fill the accept queue so that further
+    /// connection attempts are never answered.
+    ///
+    /// Returns `None` if IPv6 isn't available. This relies on Linux not answering connection
+    /// attempts once the accept queue of a listener with a backlog of 0 is full.
+    #[cfg(target_os = "linux")]
+    async fn blackholed_ipv6_listener(
+        port: u16,
+    ) -> Option<(tokio::net::TcpListener, tokio::net::TcpStream)> {
+        let socket = tokio::net::TcpSocket::new_v6().ok()?;
+        socket
+            .bind(std::net::SocketAddr::new(
+                std::net::Ipv6Addr::LOCALHOST.into(),
+                port,
+            ))
+            .ok()?;
+        let listener = socket.listen(0).ok()?;
+        let queued = tokio::net::TcpStream::connect(listener.local_addr().ok()?)
+            .await
+            .ok()?;
+        Some((listener, queued))
+    }
+
+    async fn call_dual_stack(connector: ConnectorBuilder, port: u16) -> ConnectionMetadata {
+        let resolver = dns::HyperUtilResolver {
+            resolver: StaticResolver(vec![
+                std::net::Ipv6Addr::LOCALHOST.into(),
+                std::net::Ipv4Addr::LOCALHOST.into(),
+            ]),
+        };
+        let http_connector = connector.base_connector_with_resolver(resolver);
+        let adapter = connector
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .wrap_connector(http_connector)
+            .adapter;
+
+        let capture = CaptureSmithyConnection::new();
+        let mut request = HttpRequest::get(format!("http://dual-stack.test:{port}")).unwrap();
+        request.add_extension(capture.clone());
+        let response = adapter.call(request).await.expect("request should succeed");
+        assert_eq!(200, response.status().as_u16());
+        capture.get().expect("the connection was captured")
+    }
+
+    #[test]
+    fn happy_eyeballs_delay_is_passed_to_the_http_connector() {
+        assert_eq!(
+            Some(DEFAULT_HAPPY_EYEBALLS_DELAY),
+            Connector::builder().happy_eyeballs_timeout()
+        );
+        assert_eq!(
+            Some(Duration::from_millis(100)),
+            Connector::builder()
+                .happy_eyeballs_delay(Duration::from_millis(100))
+                .happy_eyeballs_timeout()
+        );
+        assert_eq!(
+            None,
+            Connector::builder()
+                .happy_eyeballs_delay(None)
+                .happy_eyeballs_timeout()
+        );
+
+        let mut builder = Connector::builder();
+        builder.set_happy_eyeballs_delay(Some(None));
+        assert_eq!(None, builder.happy_eyeballs_timeout());
+        builder.set_happy_eyeballs_delay(None);
+        assert_eq!(
+            Some(DEFAULT_HAPPY_EYEBALLS_DELAY),
+            builder.happy_eyeballs_timeout()
+        );
+    }
+
+    #[test]
+    fn connect_timeout_is_only_split_when_opted_in() {
+        let connector_settings = HttpConnectorSettings::builder()
+            .connect_timeout(Duration::from_secs(2))
+            .build();
+
+        let builder = Connector::builder().connector_settings(connector_settings.clone());
+        assert_eq!(None, builder.attempt_connect_timeout());
+
+        let builder = Connector::builder()
+            .connector_settings(connector_settings)
+            .split_connect_timeout(true);
+        assert_eq!(
+            Some(Duration::from_secs(2)),
+            builder.attempt_connect_timeout()
+        );
+
+        // Without a connect timeout, there's nothing to split
+        let builder = Connector::builder().split_connect_timeout(true);
+        assert_eq!(None, builder.attempt_connect_timeout());
+    }
+
+    /// Races a blackholed IPv6 address against a responding IPv4 address on the loopback
+    /// interface. This needs IPv6 and relies on real connect timing, so it only runs on request.
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    #[ignore = "requires IPv6 on the loopback interface and relies on connect timing"]
+    async fn happy_eyeballs_races_the_other_address_family() {
+        let ipv4_addr = responding_ipv4_listener().await;
+        let _blackhole = blackholed_ipv6_listener(ipv4_addr.port())
+            .await
+            .expect("IPv6 is required for this test");
+
+        let connector = Connector::builder()
+            .connector_settings(
+                HttpConnectorSettings::builder()
+                    .connect_timeout(Duration::from_secs(10))
+                    .build(),
+            )
+            .happy_eyeballs_delay(Duration::from_millis(100));
+        let start = tokio::time::Instant::now();
+        let connection = call_dual_stack(connector, ipv4_addr.port()).await;
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "the IPv4 attempt should not wait for the IPv6 attempt to time out"
+        );
+        assert_eq!(Some(ipv4_addr), connection.remote_addr());
+    }
+
     #[cfg(feature = "s2n-tls")]
     #[tokio::test]
     async fn s2n_tls_provider() {
@@ -7,12 +7,15 @@ use crate::proxy;
 use aws_smithy_runtime_api::box_error::BoxError;
 use http_1x::Uri;
 use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_util::client::legacy::connect::proxy::SocksV5;
 use hyper_util::client::legacy::connect::{Connected, Connection};
+use hyper_util::client::proxy::matcher::Intercept;
 use pin_project_lite::pin_project;
 use std::future::Future;
 use std::io;
 use std::io::IoSlice;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 pub(crate) trait AsyncConn:
@@ -86,6 +89,111 @@ impl Write for Conn {
     }
 }
 
+/// Default port of SOCKS proxies
+const DEFAULT_SOCKS_PORT: u16 = 1080;
+
+/// Returns true if `intercept` routes connections through a SOCKS proxy rather than an HTTP proxy
+pub(crate) fn is_socks_proxy(intercept: &Intercept) -> bool {
+    intercept
+        .uri()
+        .scheme_str()
+        .is_some_and(|scheme| scheme.starts_with("socks"))
+}
+
+/// Open a connection to `dst` tunneled through the SOCKS5 proxy of `intercept`
+///
+/// `connector` is used to connect to the proxy itself. With the `socks5` scheme, `dst` is resolved
+/// locally and the proxy is given its IP address. With the `socks5h` scheme, the proxy resolves it.
+pub(crate) fn connect_through_socks5<C>(
+    mut connector: C,
+    intercept: &Intercept,
+    dst: Uri,
+) -> Pin<Box<dyn Future<Output = Result<C::Response, BoxError>> + Send>>
+where
+    C: tower::Service<Uri>,
+    C::Future: Send + 'static,
+    C::Response: Read + Write + Unpin + Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    let local_dns = match intercept.uri().scheme_str() {
+        Some("socks5") => true,
+        Some("socks5h") => false,
+        scheme => {
+            let err = BoxError::from(format!(
+                "unsupported SOCKS proxy scheme: {}",
+                scheme.unwrap_or_default()
+            ));
+            return Box::pin(async move { Err(err) });
+        }
+    };
+    // The connector only needs a plain TCP connection to the proxy, so don't give it a SOCKS
+    // scheme it doesn't know about.
+    let proxy_uri = match with_port(intercept.uri(), "http", DEFAULT_SOCKS_PORT) {
+        Ok(uri) => uri,
+        Err(err) => return Box::pin(async move { Err(err) }),
+    };
+    // SOCKS needs the destination port, which is implied by the scheme if it isn't explicit
+    let default_port = if dst.scheme() == Some(&http_1x::uri::Scheme::HTTPS) {
+        443
+    } else {
+        80
+    };
+    let dst = match with_port(&dst, dst.scheme_str().unwrap_or("http"), default_port) {
+        Ok(uri) => uri,
+        Err(err) => return Box::pin(async move { Err(err) }),
+    };
+    let auth = intercept
+        .raw_auth()
+        .map(|(username, password)| (username.to_owned(), password.to_owned()));
+
+    let connecting = connector.call(proxy_uri.clone());
+    Box::pin(async move {
+        // Connect to the proxy before the handshake so that connection errors keep their type
+        let conn = connecting.await.map_err(Into::into)?;
+        tracing::trace!("tunneling over SOCKS5 proxy");
+        let mut socks =
+            SocksV5::new(proxy_uri, EstablishedConnection(Some(conn))).local_dns(local_dns);
+        if let Some((username, password)) = auth {
+            socks = socks.with_auth(username, password);
+        }
+        tower::Service::call(&mut socks, dst)
+            .await
+            .map_err(|err| BoxError::from(format!("SOCKS5 handshake failed: {err}")))
+    })
+}
+
+/// A connector that hands out a connection that was already established
+struct EstablishedConnection<T>(Option<T>);
+
+impl<T> tower::Service<Uri> for EstablishedConnection<T> {
+    type Response = T;
+    type Error = io::Error;
+    type Future = std::future::Ready<Result<T, io::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _dst: Uri) -> Self::Future {
+        std::future::ready(
+            self.0
+                .take()
+                .ok_or_else(|| io::Error::other("the connection was already used")),
+        )
+    }
+}
+
+/// Returns `uri` with the given scheme, and with `default_port` if it doesn't have a port
+fn with_port(uri: &Uri, scheme: &str, default_port: u16) -> Result<Uri, BoxError> {
+    let host = uri.host().ok_or("missing host in URI")?;
+    let port = uri.port_u16().unwrap_or(default_port);
+    Ok(Uri::builder()
+        .scheme(scheme)
+        .authority(format!("{host}:{port}"))
+        .path_and_query("/")
+        .build()?)
+}
+
 /// HTTP-only proxy connector for handling HTTP requests through HTTP proxies
 ///
 /// This connector handles the HTTP proxy logic when no TLS provider is selected,
@@ -93,14 +201,20 @@ impl Write for Conn {
 #[derive(Debug, Clone)]
 pub(crate) struct HttpProxyConnector<C> {
     inner: C,
-    proxy_config: proxy::ProxyConfig,
+    proxy_matcher: Option<Arc<proxy::ProxyMatcher>>, // Pre-computed for performance
 }
 
 impl<C> HttpProxyConnector<C> {
     pub(crate) fn new(inner: C, proxy_config: proxy::ProxyConfig) -> Self {
+        // Pre-compute the proxy matcher once during construction
+        let proxy_matcher = if proxy_config.is_disabled() {
+            None
+        } else {
+            Some(Arc::new(proxy_config.into_proxy_matcher()))
+        };
         Self {
             inner,
-            proxy_config,
+            proxy_matcher,
         }
     }
 }
@@ -131,16 +245,33 @@ where
 
     fn call(&mut self, dst: Uri) -> Self::Future {
         // Check if this request should be proxied
-        let proxy_intercept = if !self.proxy_config.is_disabled() {
-            let matcher = self.proxy_config.clone().into_hyper_util_matcher();
+        let proxy_intercept = if let Some(ref matcher) = self.proxy_matcher {
             matcher.intercept(&dst)
         } else {
             None
         };
 
-        if let Some(intercept) = proxy_intercept {
+        if let Some(intercept) = proxy_intercept.as_ref().filter(|i| is_socks_proxy(i)) {
+            // Tunnel through the SOCKS proxy. The tunnel is transparent to hyper, so this isn't
+            // marked as a proxied connection, which would make hyper send absolute-form requests.
+            let fut = connect_through_socks5(self.inner.clone(), intercept, dst);
+            Box::pin(async move {
+                let conn = fut.await?;
+                Ok(connect::Conn {
+                    inner: Box::new(conn),
+                    is_proxy: false,
+                })
+            })
+        } else if let Some(intercept) = proxy_intercept {
             // HTTP through proxy: Connect to proxy server
             let proxy_uri = intercept.uri().clone();
+            if proxy_uri.scheme() == Some(&http_1x::uri::Scheme::HTTPS) {
+                // Don't fall back to an unencrypted connection to a proxy that expects TLS
+                let err = BoxError::from(format!(
+                    "cannot connect to HTTPS proxy {proxy_uri} without a TLS provider"
+                ));
+                return Box::pin(async move { Err(err) });
+            }
             let fut = self.inner.call(proxy_uri);
             Box::pin(async move {
                 let conn = fut.await.map_err(Into::into)?;
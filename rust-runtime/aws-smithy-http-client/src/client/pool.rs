@@ -0,0 +1,590 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Connection pool metrics and introspection.
+//!
+//! When enabled with [`ConnectorBuilder::pool_metrics`](crate::ConnectorBuilder::pool_metrics),
+//! a [`Connector`](crate::Connector) keeps per-host statistics about the connections in its pool.
+//! These can be inspected with [`Connector::pool_stats`](crate::Connector::pool_stats), and are
+//! exported through the meter provider of the global
+//! [`TelemetryProvider`](aws_smithy_observability::TelemetryProvider) as the following instruments,
+//! each with `server.address` and `server.port` attributes:
+//!
+//! | Instrument                                  | Kind    | Description                                            |
+//! |---------------------------------------------|---------|--------------------------------------------------------|
+//! | `smithy.client.http.connections.idle`       | Gauge   | Open connections not currently receiving a response    |
+//! | `smithy.client.http.connections.active`     | Gauge   | Open connections currently receiving a response        |
+//! | `smithy.client.http.connections.pending`    | Gauge   | Connection attempts that haven't completed yet         |
+//! | `smithy.client.http.connections.created`    | Counter | Connections established                                |
+//! | `smithy.client.http.connections.reused`     | Counter | Requests sent on a connection that served one before   |
+//!
+//! A connection is considered active from the time response headers are received on it until the
+//! response body has been fully read or dropped.
+
+use aws_smithy_observability::global::get_telemetry_provider;
+use aws_smithy_observability::instruments::{AsyncMeasure, MonotonicCounter};
+use aws_smithy_observability::{AttributeValue, Attributes};
+use aws_smithy_runtime_api::box_error::BoxError;
+use http_1x::Uri;
+use hyper::body::{Body, Frame, SizeHint};
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_util::client::legacy::connect::{CaptureConnection, Connected, Connection};
+use pin_project_lite::pin_project;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+const METER_SCOPE: &str = "aws-smithy-http-client";
+
+/// A snapshot of the connection pool statistics of a [`Connector`](crate::Connector).
+#[derive(Debug, Clone, Default)]
+pub struct PoolStats {
+    hosts: BTreeMap<String, HostPoolStats>,
+}
+
+impl PoolStats {
+    /// Statistics for the given host, in `host:port` form.
+    pub fn host(&self, authority: &str) -> Option<&HostPoolStats> {
+        self.hosts.get(authority)
+    }
+
+    /// Statistics for every host a connection was made to, keyed by `host:port`.
+    pub fn hosts(&self) -> impl Iterator<Item = (&str, &HostPoolStats)> {
+        self.hosts
+            .iter()
+            .map(|(authority, stats)| (authority.as_str(), stats))
+    }
+}
+
+/// Connection pool statistics for a single host.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostPoolStats {
+    open: u64,
+    active: u64,
+    pending: u64,
+    created: u64,
+    reused: u64,
+}
+
+impl HostPoolStats {
+    /// The number of open connections that aren't currently receiving a response.
+    pub fn idle(&self) -> u64 {
+        self.open.saturating_sub(self.active)
+    }
+
+    /// The number of open connections currently receiving a response.
+    pub fn active(&self) -> u64 {
+        self.active
+    }
+
+    /// The number of connection attempts that haven't completed yet.
+    pub fn pending(&self) -> u64 {
+        self.pending
+    }
+
+    /// The total number of connections established.
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// The total number of requests sent on a connection that had already served a request.
+    pub fn reused(&self) -> u64 {
+        self.reused
+    }
+}
+
+#[derive(Debug)]
+struct Host {
+    attributes: Attributes,
+    stats: HostPoolStats,
+}
+
+type Hosts = Arc<Mutex<BTreeMap<String, Host>>>;
+
+/// Tracks the connections of a pool, shared between every clone of a connector.
+#[derive(Clone)]
+pub(crate) struct PoolTracker {
+    inner: Arc<PoolTrackerInner>,
+}
+
+struct PoolTrackerInner {
+    hosts: Hosts,
+    gauges: Vec<Arc<dyn AsyncMeasure<Value = f64>>>,
+    created: Arc<dyn MonotonicCounter>,
+    reused: Arc<dyn MonotonicCounter>,
+}
+
+impl Drop for PoolTrackerInner {
+    fn drop(&mut self) {
+        for gauge in &self.gauges {
+            gauge.stop();
+        }
+    }
+}
+
+impl fmt::Debug for PoolTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolTracker").finish_non_exhaustive()
+    }
+}
+
+impl PoolTracker {
+    /// Create a new tracker, registering its instruments with the global meter provider.
+    pub(crate) fn new() -> Self {
+        let hosts: Hosts = Default::default();
+        let telemetry_provider = get_telemetry_provider().ok();
+        let meter = match &telemetry_provider {
+            Some(provider) => provider.meter_provider().get_meter(METER_SCOPE, None),
+            None => aws_smithy_observability::TelemetryProvider::noop()
+                .meter_provider()
+                .get_meter(METER_SCOPE, None),
+        };
+
+        let gauge =
+            |name: &'static str, description: &'static str, value: fn(&HostPoolStats) -> u64| {
+                let hosts = hosts.clone();
+                meter
+                    .create_gauge(name, move |measure| {
+                        for host in hosts.lock().unwrap().values() {
+                            measure.record(value(&host.stats) as f64, Some(&host.attributes), None);
+                        }
+                    })
+                    .set_units("{connection}")
+                    .set_description(description)
+                    .build()
+            };
+        let gauges = vec![
+            gauge(
+                "smithy.client.http.connections.idle",
+                "Open connections that aren't currently receiving a response",
+                HostPoolStats::idle,
+            ),
+            gauge(
+                "smithy.client.http.connections.active",
+                "Open connections that are currently receiving a response",
+                HostPoolStats::active,
+            ),
+            gauge(
+                "smithy.client.http.connections.pending",
+                "Connection attempts that haven't completed yet",
+                HostPoolStats::pending,
+            ),
+        ];
+
+        Self {
+            inner: Arc::new(PoolTrackerInner {
+                gauges,
+                created: meter
+                    .create_monotonic_counter("smithy.client.http.connections.created")
+                    .set_units("{connection}")
+                    .set_description("Connections established")
+                    .build(),
+                reused: meter
+                    .create_monotonic_counter("smithy.client.http.connections.reused")
+                    .set_units("{request}")
+                    .set_description(
+                        "Requests sent on a connection that had already served a request",
+                    )
+                    .build(),
+                hosts,
+            }),
+        }
+    }
+
+    /// Take a snapshot of the current statistics.
+    pub(crate) fn stats(&self) -> PoolStats {
+        PoolStats {
+            hosts: self
+                .inner
+                .hosts
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(authority, host)| (authority.clone(), host.stats.clone()))
+                .collect(),
+        }
+    }
+
+    fn update<R>(&self, authority: &str, f: impl FnOnce(&mut Host) -> R) -> R {
+        let mut hosts = self.inner.hosts.lock().unwrap();
+        let host = hosts.entry(authority.to_owned()).or_insert_with(|| Host {
+            attributes: host_attributes(authority),
+            stats: HostPoolStats::default(),
+        });
+        f(host)
+    }
+
+    /// Wrap a connector so that its connections are tracked.
+    pub(crate) fn wrap_connector<C>(&self, inner: C) -> TrackingConnector<C> {
+        TrackingConnector {
+            inner,
+            tracker: self.clone(),
+        }
+    }
+
+    /// Wrap a response body so that its connection counts as active until the body is finished.
+    pub(crate) fn wrap_body<B>(&self, capture: &CaptureConnection, body: B) -> TrackedBody<B> {
+        let mut extensions = http_1x::Extensions::new();
+        if let Some(connected) = capture.connection_metadata().as_ref() {
+            connected.get_extras(&mut extensions);
+        }
+        let guard = extensions
+            .get::<ConnectionHandle>()
+            .map(|handle| handle.begin_response());
+        TrackedBody { inner: body, guard }
+    }
+}
+
+fn host_attributes(authority: &str) -> Attributes {
+    let mut attributes = Attributes::new();
+    let (address, port) = match authority.rsplit_once(':') {
+        Some((address, port)) if !address.ends_with(']') || authority.starts_with('[') => {
+            (address, port.parse::<i64>().ok())
+        }
+        _ => (authority, None),
+    };
+    attributes.set(
+        "server.address",
+        AttributeValue::String(address.trim_start_matches('[').trim_end_matches(']').into()),
+    );
+    if let Some(port) = port {
+        attributes.set("server.port", AttributeValue::I64(port));
+    }
+    attributes
+}
+
+/// The `host:port` key a connection to `uri` is tracked under.
+fn authority_of(uri: &Uri) -> String {
+    let host = uri.host().unwrap_or_default();
+    let port = uri.port_u16().or_else(|| match uri.scheme_str() {
+        Some("https") => Some(443),
+        Some("http") => Some(80),
+        _ => None,
+    });
+    match port {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_owned(),
+    }
+}
+
+/// A connector that tracks the connections it establishes.
+#[derive(Clone, Debug)]
+pub(crate) struct TrackingConnector<C> {
+    inner: C,
+    tracker: PoolTracker,
+}
+
+impl<C> tower::Service<Uri> for TrackingConnector<C>
+where
+    C: tower::Service<Uri>,
+    C::Future: Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    type Response = TrackedConn<C::Response>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let authority = authority_of(&dst);
+        let tracker = self.tracker.clone();
+        tracker.update(&authority, |host| host.stats.pending += 1);
+        let pending = PendingGuard {
+            tracker: tracker.clone(),
+            authority: authority.clone(),
+        };
+        let fut = self.inner.call(dst);
+        Box::pin(async move {
+            let result = fut.await.map_err(Into::into);
+            drop(pending);
+            let conn = result?;
+            tracker.update(&authority, |host| {
+                host.stats.open += 1;
+                host.stats.created += 1;
+                tracker.inner.created.add(1, Some(&host.attributes), None);
+            });
+            Ok(TrackedConn {
+                inner: conn,
+                handle: ConnectionHandle(Arc::new(ConnectionState {
+                    tracker,
+                    authority,
+                    usage: Mutex::default(),
+                })),
+            })
+        })
+    }
+}
+
+/// Decrements the pending count when a connection attempt completes or is cancelled.
+struct PendingGuard {
+    tracker: PoolTracker,
+    authority: String,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        self.tracker
+            .update(&self.authority, |host| host.stats.pending -= 1);
+    }
+}
+
+/// Shared state of a single tracked connection.
+///
+/// This is attached to the connection's [`Connected`] metadata so that it can be found again from
+/// the request that used the connection.
+#[derive(Clone)]
+struct ConnectionHandle(Arc<ConnectionState>);
+
+struct ConnectionState {
+    tracker: PoolTracker,
+    authority: String,
+    usage: Mutex<ConnectionUsage>,
+}
+
+#[derive(Default)]
+struct ConnectionUsage {
+    in_flight: usize,
+    served: u64,
+    closed: bool,
+}
+
+impl ConnectionHandle {
+    fn begin_response(&self) -> ResponseGuard {
+        let state = &self.0;
+        let mut usage = state.usage.lock().unwrap();
+        if !usage.closed {
+            let reused = usage.served > 0;
+            let activated = usage.in_flight == 0;
+            state.tracker.update(&state.authority, |host| {
+                if reused {
+                    host.stats.reused += 1;
+                    state
+                        .tracker
+                        .inner
+                        .reused
+                        .add(1, Some(&host.attributes), None);
+                }
+                if activated {
+                    host.stats.active += 1;
+                }
+            });
+        }
+        usage.in_flight += 1;
+        usage.served += 1;
+        ResponseGuard {
+            handle: self.clone(),
+        }
+    }
+
+    fn end_response(&self) {
+        let state = &self.0;
+        let mut usage = state.usage.lock().unwrap();
+        usage.in_flight -= 1;
+        if usage.in_flight == 0 && !usage.closed {
+            state
+                .tracker
+                .update(&state.authority, |host| host.stats.active -= 1);
+        }
+    }
+
+    fn close(&self) {
+        let state = &self.0;
+        let mut usage = state.usage.lock().unwrap();
+        usage.closed = true;
+        let was_active = usage.in_flight > 0;
+        state.tracker.update(&state.authority, |host| {
+            host.stats.open -= 1;
+            if was_active {
+                host.stats.active -= 1;
+            }
+        });
+    }
+}
+
+/// Marks a connection as no longer receiving a response when dropped.
+struct ResponseGuard {
+    handle: ConnectionHandle,
+}
+
+impl Drop for ResponseGuard {
+    fn drop(&mut self) {
+        self.handle.end_response();
+    }
+}
+
+pin_project! {
+    /// A connection established by a [`TrackingConnector`].
+    pub(crate) struct TrackedConn<T> {
+        #[pin]
+        inner: T,
+        handle: ConnectionHandle,
+    }
+
+    impl<T> PinnedDrop for TrackedConn<T> {
+        fn drop(this: Pin<&mut Self>) {
+            this.handle.close();
+        }
+    }
+}
+
+impl<T: Connection> Connection for TrackedConn<T> {
+    fn connected(&self) -> Connected {
+        self.inner.connected().extra(self.handle.clone())
+    }
+}
+
+impl<T: Read> Read for TrackedConn<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+}
+
+impl<T: Write> Write for TrackedConn<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<Result<usize, io::Error>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+pin_project! {
+    /// A response body that keeps its connection marked as active until it's finished.
+    pub(crate) struct TrackedBody<B> {
+        #[pin]
+        inner: B,
+        guard: Option<ResponseGuard>,
+    }
+}
+
+impl<B: Body> Body for TrackedBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let frame = this.inner.poll_frame(cx);
+        if let Poll::Ready(None) | Poll::Ready(Some(Err(_))) = frame {
+            this.guard.take();
+        }
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{authority_of, host_attributes, PoolTracker};
+    use crate::client::timeout::test::NeverReplies;
+    use aws_smithy_observability::AttributeValue;
+    use http_1x::Uri;
+    use tower::Service;
+
+    #[test]
+    fn authority_includes_default_port() {
+        assert_eq!(
+            "example.com:443",
+            authority_of(&Uri::from_static("https://example.com/foo"))
+        );
+        assert_eq!(
+            "example.com:80",
+            authority_of(&Uri::from_static("http://example.com"))
+        );
+        assert_eq!(
+            "127.0.0.1:8080",
+            authority_of(&Uri::from_static("http://127.0.0.1:8080"))
+        );
+    }
+
+    #[test]
+    fn host_attributes_split_address_and_port() {
+        let attributes = host_attributes("example.com:443");
+        assert_eq!(
+            Some(&AttributeValue::String("example.com".into())),
+            attributes.get("server.address")
+        );
+        assert_eq!(
+            Some(&AttributeValue::I64(443)),
+            attributes.get("server.port")
+        );
+
+        let attributes = host_attributes("[::1]:8080");
+        assert_eq!(
+            Some(&AttributeValue::String("::1".into())),
+            attributes.get("server.address")
+        );
+        assert_eq!(
+            Some(&AttributeValue::I64(8080)),
+            attributes.get("server.port")
+        );
+    }
+
+    #[tokio::test]
+    async fn tracks_connection_lifecycle() {
+        let tracker = PoolTracker::new();
+        let mut connector = tracker.wrap_connector(NeverReplies);
+
+        let conn = connector
+            .call(Uri::from_static("https://example.com"))
+            .await
+            .unwrap();
+        let stats = tracker.stats();
+        let host = stats.host("example.com:443").unwrap();
+        assert_eq!(
+            (1, 0, 0, 1),
+            (host.idle(), host.active(), host.pending(), host.created())
+        );
+
+        drop(conn);
+        let stats = tracker.stats();
+        let host = stats.host("example.com:443").unwrap();
+        assert_eq!(
+            (0, 0, 0, 1),
+            (host.idle(), host.active(), host.pending(), host.created())
+        );
+    }
+}
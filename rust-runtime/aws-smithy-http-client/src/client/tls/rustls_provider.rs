@@ -328,15 +328,14 @@ pub(crate) mod build_connector {
 }
 
 pub(crate) mod connect {
-    use crate::client::connect::{Conn, Connecting};
-    use crate::client::proxy::ProxyConfig;
+    use crate::client::connect::{connect_through_socks5, is_socks_proxy, Conn, Connecting};
+    use crate::client::proxy::{ProxyConfig, ProxyMatcher};
     use aws_smithy_runtime_api::box_error::BoxError;
     use http_1x::uri::Scheme;
     use http_1x::Uri;
     use hyper::rt::{Read, ReadBufCursor, Write};
     use hyper_rustls::MaybeHttpsStream;
     use hyper_util::client::legacy::connect::{Connected, Connection, HttpConnector};
-    use hyper_util::client::proxy::matcher::Matcher;
     use hyper_util::rt::TokioIo;
     use pin_project_lite::pin_project;
     use std::error::Error;
@@ -355,7 +354,7 @@ pub(crate) mod connect {
     pub(crate) struct RustTlsConnector<R> {
         https: hyper_rustls::HttpsConnector<HttpConnector<R>>,
         tls_config: Arc<rustls::ClientConfig>,
-        proxy_matcher: Option<Arc<Matcher>>, // Pre-computed for performance
+        proxy_matcher: Option<Arc<ProxyMatcher>>, // Pre-computed for performance
     }
 
     impl<R> RustTlsConnector<R> {
@@ -368,7 +367,7 @@ pub(crate) mod connect {
             let proxy_matcher = if proxy_config.is_disabled() {
                 None
             } else {
-                Some(Arc::new(proxy_config.into_hyper_util_matcher()))
+                Some(Arc::new(proxy_config.into_proxy_matcher()))
             };
 
             Self {
@@ -404,7 +403,10 @@ pub(crate) mod connect {
             };
 
             if let Some(intercept) = proxy_intercept {
-                if dst.scheme() == Some(&Scheme::HTTPS) {
+                if is_socks_proxy(&intercept) {
+                    // HTTP or HTTPS through SOCKS proxy: Tunnel + manual TLS for HTTPS
+                    self.handle_through_socks_proxy(dst, intercept)
+                } else if dst.scheme() == Some(&Scheme::HTTPS) {
                     // HTTPS through HTTP proxy: Use CONNECT tunneling + manual TLS
                     self.handle_https_through_proxy(dst, intercept)
                 } else {
@@ -454,6 +456,44 @@ pub(crate) mod connect {
             })
         }
 
+        fn handle_through_socks_proxy(
+            &mut self,
+            dst: Uri,
+            intercept: hyper_util::client::proxy::matcher::Intercept,
+        ) -> Connecting {
+            use rustls_pki_types::ServerName as RustlsServerName;
+            // The SOCKS tunnel is transparent to hyper, so the connection isn't marked as proxied.
+            // For HTTPS, perform a manual TLS handshake over the tunneled stream.
+            let tunnel = connect_through_socks5(self.https.clone(), &intercept, dst.clone());
+            let tls_config = self.tls_config.clone();
+
+            Box::pin(async move {
+                let tunneled = tunnel.await?;
+                if dst.scheme() != Some(&Scheme::HTTPS) {
+                    return Ok(Conn {
+                        inner: Box::new(tunneled),
+                        is_proxy: false,
+                    });
+                }
+
+                let host = dst.host().ok_or("missing host in URI for TLS handshake")?;
+                let server_name = RustlsServerName::try_from(host.to_owned()).map_err(|e| {
+                    BoxError::from(format!("invalid server name for TLS handshake: {e}"))
+                })?;
+
+                let tls_connector = tokio_rustls::TlsConnector::from(tls_config)
+                    .connect(server_name, TokioIo::new(tunneled))
+                    .await?;
+
+                Ok(Conn {
+                    inner: Box::new(RustTlsConn {
+                        inner: TokioIo::new(tls_connector),
+                    }),
+                    is_proxy: false,
+                })
+            })
+        }
+
         fn handle_https_through_proxy(
             &mut self,
             dst: Uri,
@@ -236,13 +236,12 @@ pub(crate) mod build_connector {
 }
 
 pub(crate) mod connect {
-    use crate::client::connect::{Conn, Connecting};
-    use crate::client::proxy::ProxyConfig;
+    use crate::client::connect::{connect_through_socks5, is_socks_proxy, Conn, Connecting};
+    use crate::client::proxy::{ProxyConfig, ProxyMatcher};
     use aws_smithy_runtime_api::box_error::BoxError;
     use http_1x::uri::Scheme;
     use http_1x::Uri;
     use hyper_util::client::legacy::connect::{Connected, Connection, HttpConnector};
-    use hyper_util::client::proxy::matcher::Matcher;
     use hyper_util::rt::TokioIo;
     use std::error::Error;
     use std::sync::Arc;
@@ -257,7 +256,7 @@ pub(crate) mod connect {
     pub(crate) struct S2nTlsConnector<R> {
         https: s2n_tls_hyper::connector::HttpsConnector<HttpConnector<R>>,
         tls_config: s2n_tls::config::Config,
-        proxy_matcher: Option<Arc<Matcher>>, // Pre-computed for performance
+        proxy_matcher: Option<Arc<ProxyMatcher>>, // Pre-computed for performance
     }
 
     impl<R> S2nTlsConnector<R> {
@@ -270,7 +269,7 @@ pub(crate) mod connect {
             let proxy_matcher = if proxy_config.is_disabled() {
                 None
             } else {
-                Some(Arc::new(proxy_config.into_hyper_util_matcher()))
+                Some(Arc::new(proxy_config.into_proxy_matcher()))
             };
 
             Self {
@@ -306,7 +305,10 @@ pub(crate) mod connect {
             };
 
             if let Some(intercept) = proxy_intercept {
-                if dst.scheme() == Some(&Scheme::HTTPS) {
+                if is_socks_proxy(&intercept) {
+                    // HTTP or HTTPS through SOCKS proxy: Tunnel + manual TLS for HTTPS
+                    self.handle_through_socks_proxy(dst, intercept)
+                } else if dst.scheme() == Some(&Scheme::HTTPS) {
                     // HTTPS through HTTP proxy: Use CONNECT tunneling + manual TLS
                     self.handle_https_through_proxy(dst, intercept)
                 } else {
@@ -356,6 +358,41 @@ pub(crate) mod connect {
             })
         }
 
+        fn handle_through_socks_proxy(
+            &mut self,
+            dst: Uri,
+            intercept: hyper_util::client::proxy::matcher::Intercept,
+        ) -> Connecting {
+            // The SOCKS tunnel is transparent to hyper, so the connection isn't marked as proxied.
+            // For HTTPS, perform a manual TLS handshake over the tunneled stream.
+            let tunnel = connect_through_socks5(self.https.clone(), &intercept, dst.clone());
+            let tls_config = self.tls_config.clone();
+
+            Box::pin(async move {
+                let tunneled = tunnel.await?;
+                if dst.scheme() != Some(&Scheme::HTTPS) {
+                    return Ok(Conn {
+                        inner: Box::new(tunneled),
+                        is_proxy: false,
+                    });
+                }
+
+                let host = dst.host().ok_or("missing host in URI for TLS handshake")?;
+                let tls_connector = s2n_tls_tokio::TlsConnector::new(tls_config);
+                let tls_stream = tls_connector
+                    .connect(host, TokioIo::new(tunneled))
+                    .await
+                    .map_err(|e| BoxError::from(format!("s2n-tls handshake failed: {e}")))?;
+
+                Ok(Conn {
+                    inner: Box::new(S2nTlsConn {
+                        inner: TokioIo::new(tls_stream),
+                    }),
+                    is_proxy: false,
+                })
+            })
+        }
+
         fn handle_https_through_proxy(
             &mut self,
             dst: Uri,
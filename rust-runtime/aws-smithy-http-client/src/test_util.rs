@@ -27,6 +27,9 @@
 //! - [`infallible_client_fn`]: Allows you to create a client from an infallible function
 //!   that takes a request and returns a response.
 //! - [`NeverClient`]: Useful for testing timeouts, where you want the client to never respond.
+//! - [`VirtualTimeHarness`]: Useful for testing retries, timeouts, and anything else that sleeps.
+//!   It wires a virtual clock into a time source, a sleep implementation, and a
+//!   [`StaticReplayClient`] whose responses can be delayed, so that tests run without waiting.
 //!
 #![cfg_attr(
     any(feature = "hyper-014", feature = "default-client"),
@@ -59,6 +62,9 @@ pub mod legacy_infallible;
 mod never;
 pub use never::NeverClient;
 
+mod virtual_time;
+pub use virtual_time::VirtualTimeHarness;
+
 #[cfg(any(feature = "hyper-014", feature = "default-client"))]
 pub use never::NeverTcpConnector;
 
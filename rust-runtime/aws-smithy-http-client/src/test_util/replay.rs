@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_protocol_test::{assert_ok, validate_body, MediaType};
 use aws_smithy_runtime_api::client::connector_metadata::ConnectorMetadata;
 use aws_smithy_runtime_api::client::http::{
@@ -15,6 +16,7 @@ use aws_smithy_runtime_api::shared::IntoShared;
 use http_1x::header::CONTENT_TYPE;
 use std::ops::Deref;
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 type ReplayEvents = Vec<ReplayEvent>;
 
@@ -29,6 +31,7 @@ pub(crate) const DEFAULT_RELAXED_HEADERS: &[&str] =
 pub struct ReplayEvent {
     request: HttpRequest,
     response: HttpResponse,
+    latency: Duration,
 }
 
 impl ReplayEvent {
@@ -37,9 +40,25 @@ impl ReplayEvent {
         Self {
             request: request.try_into().ok().expect("invalid request"),
             response: response.try_into().ok().expect("invalid response"),
+            latency: Duration::ZERO,
         }
     }
 
+    /// Delays the response by `latency`.
+    ///
+    /// The delay uses the sleep implementation of the runtime components the client is used with,
+    /// so with a test sleep implementation, such as the one of
+    /// [`VirtualTimeHarness`](crate::test_util::VirtualTimeHarness), it doesn't take real time.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Returns the latency of the response.
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
     /// Returns the test request.
     pub fn request(&self) -> &HttpRequest {
         &self.request
@@ -155,6 +174,7 @@ impl ValidateRequest {
 pub struct StaticReplayClient {
     data: Arc<Mutex<ReplayEvents>>,
     requests: Arc<Mutex<Vec<ValidateRequest>>>,
+    sleep_impl: Option<SharedAsyncSleep>,
 }
 
 impl StaticReplayClient {
@@ -164,6 +184,7 @@ impl StaticReplayClient {
         StaticReplayClient {
             data: Arc::new(Mutex::new(data)),
             requests: Default::default(),
+            sleep_impl: None,
         }
     }
 
@@ -249,21 +270,39 @@ impl StaticReplayClient {
 
 impl HttpConnector for StaticReplayClient {
     fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
-        let res = if let Some(event) = self.data.lock().unwrap().pop() {
+        let (res, latency) = if let Some(event) = self.data.lock().unwrap().pop() {
             self.requests.lock().unwrap().push(ValidateRequest {
                 expected: event.request,
                 actual: request,
             });
 
-            Ok(event.response)
+            (Ok(event.response), event.latency)
         } else {
-            Err(ConnectorError::other(
-                "StaticReplayClient: no more test data available to respond with".into(),
-                None,
-            ))
+            (
+                Err(ConnectorError::other(
+                    "StaticReplayClient: no more test data available to respond with".into(),
+                    None,
+                )),
+                Duration::ZERO,
+            )
         };
 
-        HttpConnectorFuture::new(async move { res })
+        if latency.is_zero() {
+            return HttpConnectorFuture::new(async move { res });
+        }
+        match &self.sleep_impl {
+            Some(sleep_impl) => {
+                let sleep = sleep_impl.sleep(latency);
+                HttpConnectorFuture::new(async move {
+                    sleep.await;
+                    res
+                })
+            }
+            None => HttpConnectorFuture::ready(Err(ConnectorError::other(
+                "StaticReplayClient: a sleep implementation is required to simulate latency".into(),
+                None,
+            ))),
+        }
     }
 }
 
@@ -271,9 +310,11 @@ impl HttpClient for StaticReplayClient {
     fn http_connector(
         &self,
         _: &HttpConnectorSettings,
-        _: &RuntimeComponents,
+        components: &RuntimeComponents,
     ) -> SharedHttpConnector {
-        self.clone().into_shared()
+        let mut client = self.clone();
+        client.sleep_impl = components.sleep_impl();
+        client.into_shared()
     }
 
     fn connector_metadata(&self) -> Option<ConnectorMetadata> {
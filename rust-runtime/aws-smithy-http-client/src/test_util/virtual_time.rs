@@ -0,0 +1,232 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::test_util::{ReplayEvent, StaticReplayClient};
+use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+use aws_smithy_async::test_util::tick_advance_sleep::{
+    tick_advance_time_and_sleep_at, PendingSleep, TickAdvanceSleep, TickAdvanceTime,
+};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_runtime_api::client::http::SharedHttpClient;
+use aws_smithy_runtime_api::shared::IntoShared;
+use std::future::Future;
+use std::time::{Duration, SystemTime};
+
+/// Test harness that runs a client against a [`StaticReplayClient`] in virtual time.
+///
+/// The time source, sleep implementation and HTTP client of the harness share the same virtual
+/// clock: time only passes when the harness advances it, and sleeps (including the
+/// [latency](ReplayEvent::with_latency) of replayed responses) present as soon as time reaches them.
+/// This makes retries, timeouts, identity cache refreshes and waiters run to completion without
+/// waiting in real time, and deterministically.
+///
+/// # Example
+///
+/// ```no_run
+/// # use http_1x as http;
+/// use aws_smithy_http_client::test_util::{ReplayEvent, VirtualTimeHarness};
+/// use aws_smithy_types::body::SdkBody;
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let harness = VirtualTimeHarness::new(vec![ReplayEvent::new(
+///     http::Request::builder().uri("http://localhost:1234/foo").body(SdkBody::empty()).unwrap(),
+///     http::Response::builder().status(200).body(SdkBody::empty()).unwrap(),
+/// )
+/// .with_latency(Duration::from_secs(10))]);
+///
+/// # /*
+/// let config = my_generated_client::Config::builder()
+///     .http_client(harness.http_client())
+///     .sleep_impl(harness.sleep_impl())
+///     .time_source(harness.time_source())
+///     .build();
+/// let client = my_generated_client::Client::from_conf(config);
+///
+/// // Advances time to each sleep of the operation until it completes
+/// let output = harness.run(client.some_operation().send()).await;
+/// # */
+///
+/// harness.replay_client().assert_requests_match(&[]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct VirtualTimeHarness {
+    time: TickAdvanceTime,
+    sleep: TickAdvanceSleep,
+    client: StaticReplayClient,
+}
+
+impl VirtualTimeHarness {
+    /// Creates a harness replaying `events`, starting at `UNIX_EPOCH`.
+    pub fn new(events: Vec<ReplayEvent>) -> Self {
+        Self::starting_at(SystemTime::UNIX_EPOCH, events)
+    }
+
+    /// Creates a harness replaying `events`, starting at `start_time`.
+    ///
+    /// # Panics
+    /// This function panics if `start_time` is before `UNIX_EPOCH`.
+    pub fn starting_at(start_time: SystemTime, events: Vec<ReplayEvent>) -> Self {
+        let (time, sleep) = tick_advance_time_and_sleep_at(start_time);
+        Self {
+            time,
+            sleep,
+            client: StaticReplayClient::new(events),
+        }
+    }
+
+    /// Returns the virtual time source.
+    pub fn time_source(&self) -> SharedTimeSource {
+        SharedTimeSource::new(self.time.clone())
+    }
+
+    /// Returns the virtual sleep implementation.
+    pub fn sleep_impl(&self) -> SharedAsyncSleep {
+        SharedAsyncSleep::new(self.sleep.clone())
+    }
+
+    /// Returns the HTTP client replaying the events of the harness.
+    pub fn http_client(&self) -> SharedHttpClient {
+        self.client.clone().into_shared()
+    }
+
+    /// Returns the replay client, to inspect the requests that were made.
+    pub fn replay_client(&self) -> &StaticReplayClient {
+        &self.client
+    }
+
+    /// Returns the current virtual time.
+    pub fn now(&self) -> SystemTime {
+        self.time.now()
+    }
+
+    /// Returns the sleeps that haven't presented yet, in the order they will present.
+    pub fn pending_sleeps(&self) -> Vec<PendingSleep> {
+        self.time.pending_sleeps()
+    }
+
+    /// Advances time by `duration`, presenting every sleep along the way.
+    ///
+    /// See [`TickAdvanceTime::tick`].
+    pub async fn advance(&self, duration: Duration) {
+        self.time.tick(duration).await
+    }
+
+    /// Advances time to the next pending sleep, and returns the new time.
+    ///
+    /// Returns `None`, without advancing time, if there are no pending sleeps.
+    pub async fn advance_to_next_sleep(&self) -> Option<SystemTime> {
+        self.time.tick_to_next_sleep().await
+    }
+
+    /// Runs `future` to completion, advancing time whenever it's waiting on a sleep.
+    ///
+    /// See [`TickAdvanceTime::run_to_completion`].
+    pub async fn run<F: Future>(&self, future: F) -> F::Output {
+        self.time.run_to_completion(future).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{ReplayEvent, VirtualTimeHarness};
+    use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+    use aws_smithy_runtime_api::client::http::{HttpClient, HttpConnector, HttpConnectorSettings};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use std::time::{Duration, SystemTime};
+
+    fn event(status: u16, latency: Duration) -> ReplayEvent {
+        ReplayEvent::new(
+            http_1x::Request::builder()
+                .uri("http://localhost:1234/")
+                .body(SdkBody::empty())
+                .unwrap(),
+            http_1x::Response::builder()
+                .status(status)
+                .body(SdkBody::empty())
+                .unwrap(),
+        )
+        .with_latency(latency)
+    }
+
+    fn request() -> HttpRequest {
+        HttpRequest::get("http://localhost:1234/").unwrap()
+    }
+
+    #[tokio::test]
+    async fn responses_are_delayed_in_virtual_time() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let harness = VirtualTimeHarness::starting_at(
+            start,
+            vec![
+                event(200, Duration::from_secs(30)),
+                event(503, Duration::ZERO),
+            ],
+        );
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(harness.sleep_impl()))
+            .with_time_source(Some(harness.time_source()))
+            .build()
+            .unwrap();
+        let connector = harness
+            .http_client()
+            .http_connector(&HttpConnectorSettings::default(), &components);
+
+        let response = harness.run(connector.call(request())).await.unwrap();
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(start + Duration::from_secs(30), harness.now());
+
+        let response = harness.run(connector.call(request())).await.unwrap();
+        assert_eq!(503, response.status().as_u16());
+        assert_eq!(start + Duration::from_secs(30), harness.now());
+
+        harness.replay_client().assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn pending_responses_can_be_inspected() {
+        let harness = VirtualTimeHarness::new(vec![event(200, Duration::from_secs(5))]);
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(harness.sleep_impl()))
+            .build()
+            .unwrap();
+        let connector = harness
+            .http_client()
+            .http_connector(&HttpConnectorSettings::default(), &components);
+
+        let response = tokio::spawn(connector.call(request()));
+        let pending = harness.pending_sleeps();
+        assert_eq!(1, pending.len());
+        assert_eq!(Duration::from_secs(5), pending[0].duration());
+
+        harness.advance(Duration::from_secs(4)).await;
+        assert!(!response.is_finished());
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(5)),
+            harness.advance_to_next_sleep().await
+        );
+        assert_eq!(200, response.await.unwrap().unwrap().status().as_u16());
+    }
+
+    #[tokio::test]
+    async fn latency_requires_a_sleep_impl() {
+        let harness = VirtualTimeHarness::new(vec![event(200, Duration::from_secs(5))]);
+        let components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(None::<SharedAsyncSleep>)
+            .build()
+            .unwrap();
+        let connector = harness
+            .http_client()
+            .http_connector(&HttpConnectorSettings::default(), &components);
+        let err = connector.call(request()).await.expect_err("no sleep impl");
+        assert!(
+            format!("{err:?}").contains("sleep implementation"),
+            "{err:?}"
+        );
+    }
+}
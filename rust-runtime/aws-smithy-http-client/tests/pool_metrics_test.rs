@@ -0,0 +1,105 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "wire-mock", feature = "default-client"))]
+
+use aws_smithy_http_client::pool::HostPoolStats;
+use aws_smithy_http_client::test_util::wire::connection::{
+    ConnectionTestHarness, Http1Response, Http1Script,
+};
+use aws_smithy_http_client::Connector;
+use aws_smithy_runtime_api::client::http::HttpConnector;
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use http_body_util::BodyExt;
+use std::net::{IpAddr, Ipv4Addr};
+
+const IP1: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+fn host_stats(connector: &Connector) -> HostPoolStats {
+    let stats = connector.pool_stats().expect("pool metrics are enabled");
+    let mut hosts = stats.hosts();
+    let (_, host) = hosts.next().expect("one host was connected to");
+    assert!(hosts.next().is_none());
+    host.clone()
+}
+
+#[tokio::test]
+async fn pool_stats_are_disabled_by_default() {
+    let connector = Connector::builder().build_http();
+    assert!(connector.pool_stats().is_none());
+}
+
+#[tokio::test]
+async fn pool_stats_track_connection_reuse() {
+    let harness = ConnectionTestHarness::builder()
+        .endpoint(
+            IP1,
+            Http1Script::responses([
+                Http1Response::ok().body("first"),
+                Http1Response::ok().body("second"),
+                Http1Response::ok().body("third"),
+            ]),
+        )
+        .build()
+        .await
+        .expect("harness should start");
+    let connector = Connector::builder().pool_metrics(true).build_http();
+    assert_eq!(0, connector.pool_stats().unwrap().hosts().count());
+
+    for expected in [b"first".as_slice(), b"second", b"third"] {
+        let response = connector
+            .call(HttpRequest::get(harness.endpoint_url()).unwrap())
+            .await
+            .expect("request should succeed");
+
+        let stats = host_stats(&connector);
+        assert_eq!(1, stats.active());
+        assert_eq!(0, stats.idle());
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(expected, body.as_ref());
+
+        let stats = host_stats(&connector);
+        assert_eq!(0, stats.active());
+        assert_eq!(1, stats.idle());
+    }
+
+    let stats = host_stats(&connector);
+    assert_eq!(0, stats.pending());
+    assert_eq!(1, stats.created());
+    assert_eq!(2, stats.reused());
+    assert_eq!(1, harness.tcp_accepted_count());
+
+    drop(connector);
+    harness.shutdown().await.expect("clean harness shutdown");
+}
+
+#[tokio::test]
+async fn dropping_a_response_body_ends_the_active_request() {
+    let harness = ConnectionTestHarness::builder()
+        .endpoint(
+            IP1,
+            Http1Script::responses([Http1Response::ok().body("unread")]),
+        )
+        .build()
+        .await
+        .expect("harness should start");
+    let connector = Connector::builder().pool_metrics(true).build_http();
+
+    let response = connector
+        .call(HttpRequest::get(harness.endpoint_url()).unwrap())
+        .await
+        .expect("request should succeed");
+    assert_eq!(1, host_stats(&connector).active());
+
+    drop(response);
+    let stats = host_stats(&connector);
+    assert_eq!(0, stats.active());
+    assert_eq!(1, stats.created());
+    assert_eq!(0, stats.reused());
+
+    drop(connector);
+    harness.shutdown().await.expect("clean harness shutdown");
+}
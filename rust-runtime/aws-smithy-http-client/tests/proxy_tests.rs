@@ -1198,3 +1198,416 @@ async fn test_connect_uri_form_rustls() {
 async fn test_connect_uri_form_s2n_tls() {
     run_connect_uri_form_test(tls::Provider::S2nTls, "s2n-tls").await;
 }
+
+// ================================================================================================
+// SOCKS5 Proxy Tests
+// ================================================================================================
+
+/// A request received by the mock SOCKS5 proxy
+#[derive(Debug, Clone, PartialEq)]
+struct Socks5Request {
+    /// The destination the client asked to connect to, as `host:port`
+    target: String,
+    /// The request URI of the HTTP request tunneled to the destination, if any
+    http_uri: Option<String>,
+}
+
+/// Mock SOCKS5 proxy that answers tunneled HTTP requests itself instead of connecting to the
+/// destination. Tunneled HTTPS connections are closed after the SOCKS handshake.
+#[derive(Debug)]
+struct MockSocks5Proxy {
+    addr: SocketAddr,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    request_log: Arc<Mutex<Vec<Socks5Request>>>,
+}
+
+impl MockSocks5Proxy {
+    /// Create a new mock SOCKS5 proxy, requiring the given username and password if set
+    async fn new(credentials: Option<(&str, &str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        let request_log = Arc::new(Mutex::new(Vec::new()));
+        let server_request_log = request_log.clone();
+        let credentials =
+            credentials.map(|(user, pass)| (user.as_bytes().to_vec(), pass.as_bytes().to_vec()));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    result = listener.accept() => {
+                        let Ok((stream, _)) = result else { break };
+                        let request_log = server_request_log.clone();
+                        let credentials = credentials.clone();
+                        tokio::spawn(async move {
+                            if let Err(err) =
+                                Self::handle_connection(stream, credentials, request_log).await
+                            {
+                                eprintln!("Mock SOCKS5 proxy connection error: {}", err);
+                            }
+                        });
+                    }
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+        });
+
+        Self {
+            addr,
+            shutdown_tx: Some(shutdown_tx),
+            request_log,
+        }
+    }
+
+    async fn handle_connection(
+        mut stream: tokio::net::TcpStream,
+        credentials: Option<(Vec<u8>, Vec<u8>)>,
+        request_log: Arc<Mutex<Vec<Socks5Request>>>,
+    ) -> std::io::Result<()> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Method negotiation
+        let mut header = [0u8; 2];
+        stream.read_exact(&mut header).await?;
+        assert_eq!(header[0], 5, "SOCKS version");
+        let mut methods = vec![0u8; header[1] as usize];
+        stream.read_exact(&mut methods).await?;
+        match &credentials {
+            Some((user, pass)) => {
+                if !methods.contains(&2) {
+                    stream.write_all(&[5, 0xFF]).await?;
+                    return Ok(());
+                }
+                stream.write_all(&[5, 2]).await?;
+
+                // Username/password authentication (RFC 1929)
+                let mut len = [0u8; 2];
+                stream.read_exact(&mut len).await?;
+                let mut received_user = vec![0u8; len[1] as usize];
+                stream.read_exact(&mut received_user).await?;
+                stream.read_exact(&mut len[..1]).await?;
+                let mut received_pass = vec![0u8; len[0] as usize];
+                stream.read_exact(&mut received_pass).await?;
+                if (&received_user, &received_pass) != (user, pass) {
+                    stream.write_all(&[1, 1]).await?;
+                    return Ok(());
+                }
+                stream.write_all(&[1, 0]).await?;
+            }
+            None => stream.write_all(&[5, 0]).await?,
+        }
+
+        // Connect request
+        let mut request = [0u8; 4];
+        stream.read_exact(&mut request).await?;
+        assert_eq!(request[..2], [5, 1], "SOCKS5 CONNECT command");
+        let host = match request[3] {
+            1 => {
+                let mut ip = [0u8; 4];
+                stream.read_exact(&mut ip).await?;
+                std::net::Ipv4Addr::from(ip).to_string()
+            }
+            3 => {
+                let mut len = [0u8; 1];
+                stream.read_exact(&mut len).await?;
+                let mut domain = vec![0u8; len[0] as usize];
+                stream.read_exact(&mut domain).await?;
+                String::from_utf8(domain).unwrap()
+            }
+            4 => {
+                let mut ip = [0u8; 16];
+                stream.read_exact(&mut ip).await?;
+                format!("[{}]", std::net::Ipv6Addr::from(ip))
+            }
+            other => panic!("unexpected SOCKS5 address type {other}"),
+        };
+        let mut port = [0u8; 2];
+        stream.read_exact(&mut port).await?;
+        let port = u16::from_be_bytes(port);
+        stream.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await?;
+
+        let index = {
+            let mut log = request_log.lock().unwrap();
+            log.push(Socks5Request {
+                target: format!("{host}:{port}"),
+                http_uri: None,
+            });
+            log.len() - 1
+        };
+        if port == 443 {
+            // Don't bother with TLS, the handshake is what's being tested
+            return Ok(());
+        }
+
+        let service = service_fn(move |req: Request<Incoming>| {
+            let request_log = request_log.clone();
+            async move {
+                request_log.lock().unwrap()[index].http_uri = Some(req.uri().to_string());
+                Ok::<_, Infallible>(Response::new("via socks5".to_string()))
+            }
+        });
+        hyper::server::conn::http1::Builder::new()
+            .serve_connection(TokioIo::new(stream), service)
+            .await
+            .map_err(std::io::Error::other)
+    }
+
+    /// Get the address this server is listening on
+    fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Get all requests received by this server
+    fn requests(&self) -> Vec<Socks5Request> {
+        self.request_log.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockSocks5Proxy {
+    fn drop(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// Tests that HTTP requests are tunneled through a SOCKS5 proxy, which resolves the host name
+#[tokio::test]
+async fn test_socks5h_proxy_http_request() {
+    let mock_proxy = MockSocks5Proxy::new(None).await;
+    let proxy_config = ProxyConfig::socks5(format!("socks5h://{}", mock_proxy.addr())).unwrap();
+
+    let target_url = "http://aws.amazon.com/api/data";
+    let (status, body) = make_http_request_through_proxy(proxy_config, target_url)
+        .await
+        .expect("HTTP request through SOCKS5 proxy should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "via socks5");
+
+    // The host name is sent to the proxy, and the tunneled request uses origin-form
+    assert_eq!(
+        mock_proxy.requests(),
+        vec![Socks5Request {
+            target: "aws.amazon.com:80".to_string(),
+            http_uri: Some("/api/data".to_string()),
+        }]
+    );
+}
+
+/// Tests that the `socks5` scheme resolves host names locally and that credentials are sent
+#[tokio::test]
+async fn test_socks5_proxy_local_dns_with_auth() {
+    let mock_proxy = MockSocks5Proxy::new(Some(("socksuser", "sockspass"))).await;
+    let proxy_config = ProxyConfig::socks5(format!("socks5://{}", mock_proxy.addr()))
+        .unwrap()
+        .with_basic_auth("socksuser", "sockspass");
+
+    let target_url = "http://localhost:8080/api/data";
+    let (status, _body) = make_http_request_through_proxy(proxy_config, target_url)
+        .await
+        .expect("HTTP request through SOCKS5 proxy should succeed");
+    assert_eq!(status, StatusCode::OK);
+
+    let requests = mock_proxy.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(
+        requests[0].target == "127.0.0.1:8080" || requests[0].target == "[::1]:8080",
+        "the proxy should be given a resolved address, got {}",
+        requests[0].target
+    );
+}
+
+/// Tests that SOCKS5 authentication failures fail the request
+#[tokio::test]
+async fn test_socks5_proxy_authentication_failure() {
+    let mock_proxy = MockSocks5Proxy::new(Some(("socksuser", "sockspass"))).await;
+    let proxy_config = ProxyConfig::socks5(format!("socks5h://{}", mock_proxy.addr()))
+        .unwrap()
+        .with_basic_auth("socksuser", "wrongpass");
+
+    let result = make_http_request_through_proxy(proxy_config, "http://aws.amazon.com/").await;
+    assert!(result.is_err(), "SOCKS5 authentication should fail");
+    assert!(mock_proxy.requests().is_empty());
+}
+
+/// Tests that `ALL_PROXY=socks5h://` is used as a SOCKS5 proxy, with embedded credentials
+#[tokio::test]
+async fn test_socks5_proxy_from_environment_variables() {
+    let mock_proxy = MockSocks5Proxy::new(Some(("envuser", "envpass"))).await;
+
+    with_env_vars(
+        &[(
+            "ALL_PROXY",
+            &format!("socks5h://envuser:envpass@{}", mock_proxy.addr()),
+        )],
+        || async {
+            let target_url = "http://aws.amazon.com/v1/data";
+            let (status, body) =
+                make_http_request_through_proxy(ProxyConfig::from_env(), target_url)
+                    .await
+                    .expect("Environment SOCKS5 proxy request should succeed");
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body, "via socks5");
+
+            let requests = mock_proxy.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].target, "aws.amazon.com:80");
+        },
+    )
+    .await;
+}
+
+/// Generic test function for HTTPS through a SOCKS5 proxy
+/// Tests that the tunnel targets the destination's host and HTTPS port
+#[cfg(any(feature = "rustls-ring", feature = "s2n-tls"))]
+async fn run_https_through_socks5_test(tls_provider: tls::Provider, provider_name: &str) {
+    let mock_proxy = MockSocks5Proxy::new(None).await;
+    let proxy_config = ProxyConfig::socks5(format!("socks5h://{}", mock_proxy.addr())).unwrap();
+
+    let target_url = "https://secure.aws.amazon.com/api/secure";
+    let result = make_https_request_through_proxy(proxy_config, target_url, tls_provider).await;
+
+    // The mock proxy closes the tunnel instead of completing the TLS handshake
+    assert!(
+        result.is_err(),
+        "TLS handshake should fail for {}",
+        provider_name
+    );
+    assert_eq!(
+        mock_proxy.requests(),
+        vec![Socks5Request {
+            target: "secure.aws.amazon.com:443".to_string(),
+            http_uri: None,
+        }],
+        "Proxy should have received exactly one SOCKS5 CONNECT for {}",
+        provider_name
+    );
+}
+
+/// Tests HTTPS tunneling through a SOCKS5 proxy - rustls provider
+#[cfg(feature = "rustls-ring")]
+#[tokio::test]
+async fn test_https_through_socks5_rustls() {
+    run_https_through_socks5_test(
+        tls::Provider::rustls(tls::rustls_provider::CryptoMode::Ring),
+        "rustls",
+    )
+    .await;
+}
+
+/// Tests HTTPS tunneling through a SOCKS5 proxy - s2n-tls provider
+#[cfg(feature = "s2n-tls")]
+#[tokio::test]
+async fn test_https_through_socks5_s2n_tls() {
+    run_https_through_socks5_test(tls::Provider::S2nTls, "s2n-tls").await;
+}
+
+/// Tests that a proxy selector routes each destination through the proxy it selects
+#[tokio::test]
+async fn test_proxy_selector_per_destination() {
+    let http_proxy = MockProxyServer::with_response(StatusCode::OK, "via http proxy").await;
+    let socks_proxy = MockSocks5Proxy::new(None).await;
+    let direct_server = MockProxyServer::with_response(StatusCode::OK, "direct connection").await;
+
+    let http_proxy_url = format!("http://{}", http_proxy.addr());
+    let socks_proxy_url = format!("socks5h://{}", socks_proxy.addr());
+    let proxy_config = ProxyConfig::with_selector(move |uri| match uri.host() {
+        Some("aws.amazon.com") => ProxyConfig::http(http_proxy_url.clone()).unwrap(),
+        Some("s3.amazonaws.com") => ProxyConfig::socks5(socks_proxy_url.clone()).unwrap(),
+        _ => ProxyConfig::disabled(),
+    });
+
+    let (status, body) =
+        make_http_request_through_proxy(proxy_config.clone(), "http://aws.amazon.com/api/data")
+            .await
+            .expect("request through the selected HTTP proxy should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "via http proxy");
+    assert_eq!(
+        http_proxy.requests()[0].uri,
+        "http://aws.amazon.com/api/data"
+    );
+
+    let (status, body) =
+        make_http_request_through_proxy(proxy_config.clone(), "http://s3.amazonaws.com/bucket")
+            .await
+            .expect("request through the selected SOCKS5 proxy should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "via socks5");
+    assert_eq!(
+        socks_proxy.requests(),
+        vec![Socks5Request {
+            target: "s3.amazonaws.com:80".to_string(),
+            http_uri: Some("/bucket".to_string()),
+        }]
+    );
+
+    let (status, body) = make_http_request_through_proxy(
+        proxy_config,
+        &format!("http://{}/get", direct_server.addr()),
+    )
+    .await
+    .expect("direct request should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "direct connection");
+    assert_eq!(direct_server.requests().len(), 1);
+    assert_eq!(http_proxy.requests().len(), 1);
+    assert_eq!(socks_proxy.requests().len(), 1);
+}
+
+/// Tests that the authentication and NO_PROXY rules of a selector apply to the selected proxies
+#[tokio::test]
+async fn test_proxy_selector_auth_and_no_proxy() {
+    let http_proxy = MockProxyServer::with_response(StatusCode::OK, "via http proxy").await;
+    let direct_server = MockProxyServer::with_response(StatusCode::OK, "direct connection").await;
+
+    let http_proxy_url = format!("http://{}", http_proxy.addr());
+    let proxy_config =
+        ProxyConfig::with_selector(move |_uri| ProxyConfig::http(http_proxy_url.clone()).unwrap())
+            .with_basic_auth("user", "pass")
+            .no_proxy("127.0.0.1");
+
+    let (status, body) =
+        make_http_request_through_proxy(proxy_config.clone(), "http://aws.amazon.com/api/data")
+            .await
+            .expect("request through the selected HTTP proxy should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "via http proxy");
+    let expected_auth = format!(
+        "Basic {}",
+        base64::prelude::BASE64_STANDARD.encode("user:pass")
+    );
+    assert_eq!(
+        http_proxy.requests()[0].headers.get("proxy-authorization"),
+        Some(&expected_auth)
+    );
+
+    let (status, body) = make_http_request_through_proxy(
+        proxy_config,
+        &format!("http://{}/get", direct_server.addr()),
+    )
+    .await
+    .expect("direct request should succeed");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "direct connection");
+    assert_eq!(http_proxy.requests().len(), 1);
+}
+
+/// Tests that an HTTPS proxy chosen by a selector isn't connected to without TLS
+#[tokio::test]
+async fn test_proxy_selector_https_proxy_requires_tls() {
+    let https_proxy = MockProxyServer::with_response(StatusCode::OK, "unencrypted").await;
+
+    let https_proxy_url = format!("https://{}", https_proxy.addr());
+    let proxy_config =
+        ProxyConfig::with_selector(move |_uri| ProxyConfig::http(https_proxy_url.clone()).unwrap());
+
+    let err = make_http_request_through_proxy(proxy_config, "http://aws.amazon.com/api/data")
+        .await
+        .expect_err("connecting to an HTTPS proxy requires a TLS provider");
+    assert!(
+        format!("{:?}", err).contains("without a TLS provider"),
+        "unexpected error: {err:?}"
+    );
+    assert!(https_proxy.requests().is_empty());
+}
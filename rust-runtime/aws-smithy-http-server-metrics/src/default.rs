@@ -83,6 +83,8 @@ pub struct DefaultResponseMetrics {
     pub(crate) server_error: Option<bool>,
     /// Wallclock time from pre-deserialization of the model input to post-serialization of the model output
     pub(crate) operation_time: Option<Duration>,
+    /// Timeout indicator, only present (and true) if the operation timed out
+    pub(crate) timed_out: Option<bool>,
 }
 
 /// Configuration for disabling specific default request metrics.
@@ -109,6 +111,7 @@ pub struct DefaultResponseMetricsConfig {
     pub(crate) disable_client_error: bool,
     pub(crate) disable_server_error: bool,
     pub(crate) disable_operation_time: bool,
+    pub(crate) disable_timed_out: bool,
 }
 
 /// Extension for accessing default request metrics.
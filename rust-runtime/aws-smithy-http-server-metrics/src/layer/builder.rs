@@ -103,6 +103,12 @@ macro_rules! impl_disable_methods {
             self.default_res_metrics_config.disable_operation_time = true;
             self
         }
+
+        /// Disable the default `timed_out` metric
+        pub fn disable_default_timed_out_metric(mut self) -> Self {
+            self.default_res_metrics_config.disable_timed_out = true;
+            self
+        }
     };
 }
 
@@ -177,6 +177,20 @@
 //! | `client_error` | Client error indicator (4xx status code) |
 //! | `server_error` | Server error indicator (5xx status code) |
 //! | `operation_time` | Timestamp that denotes operation time from pre-deserialization to post-serialization |
+//! | `timed_out` | Timeout indicator, only present if the operation timed out (see below) |
+//!
+//! The `timed_out` metric is recorded for the operations timed out by the
+//! [`TimeoutPlugin`](aws_smithy_http_server::timeout::TimeoutPlugin) of `aws-smithy-http-server`.
+//! To be recorded, the [`DefaultMetricsPlugin`] must run before the `TimeoutPlugin`, i.e. be
+//! pushed to the service's HTTP plugins first:
+//!
+//! ```rust, ignore
+//! use aws_smithy_http_server::timeout::TimeoutPlugin;
+//!
+//! let http_plugins = HttpPlugins::new()
+//!     .push(DefaultMetricsPlugin)
+//!     .push(TimeoutPlugin::new(Duration::from_secs(30)));
+//! ```
 //!
 //! # Platform support
 //!
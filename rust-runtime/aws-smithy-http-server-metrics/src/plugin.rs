@@ -25,6 +25,7 @@ use crate::default::DefaultRequestMetrics;
 use crate::default::DefaultResponseMetrics;
 use crate::default::DefaultResponseMetricsConfig;
 use crate::default::DefaultResponseMetricsExtension;
+use crate::types::aws_smithy_http_server::extension::RuntimeErrorExtension;
 use crate::types::aws_smithy_http_server::operation::OperationShape;
 use crate::types::aws_smithy_http_server::plugin::HttpMarker;
 use crate::types::aws_smithy_http_server::plugin::Plugin;
@@ -326,12 +327,20 @@ fn get_default_response_metrics(
         Some(false)
     };
 
+    // Set by the server's `TimeoutPlugin` when the operation doesn't respond in time
+    let timed_out = res
+        .extensions()
+        .get::<RuntimeErrorExtension>()
+        .filter(|error| error.as_str() == "TimeoutException")
+        .map(|_| true);
+
     DefaultResponseMetrics {
         http_status_code: Some(status.as_str().into()),
         success,
         client_error,
         server_error,
         operation_time,
+        timed_out,
     }
 }
 
@@ -381,5 +390,36 @@ fn configure_default_response_metrics(
         operation_time: metrics
             .operation_time
             .filter(|_| !config.disable_operation_time),
+        timed_out: metrics.timed_out.filter(|_| !config.disable_timed_out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::empty_response_body;
+
+    #[test]
+    fn timed_out_is_only_present_for_timeouts() {
+        let mut res = HttpResponse::new(empty_response_body());
+        assert_eq!(None, get_default_response_metrics(&res, None).timed_out);
+
+        res.extensions_mut()
+            .insert(RuntimeErrorExtension::new("ValidationException".to_owned()));
+        assert_eq!(None, get_default_response_metrics(&res, None).timed_out);
+
+        res.extensions_mut()
+            .insert(RuntimeErrorExtension::new("TimeoutException".to_owned()));
+        let metrics = get_default_response_metrics(&res, None);
+        assert_eq!(Some(true), metrics.timed_out);
+
+        let config = DefaultResponseMetricsConfig {
+            disable_timed_out: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            None,
+            configure_default_response_metrics(metrics, &config).timed_out
+        );
     }
 }
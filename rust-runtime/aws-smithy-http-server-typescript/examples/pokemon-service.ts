@@ -14,6 +14,7 @@ import {
     Language,
     DoNothingInput,
     DoNothingOutput,
+    TsContext,
     TsSocket,
     CheckHealthOutput,
     CheckHealthInput,
@@ -38,7 +39,9 @@ class HandlerImpl implements TsHandlers {
     }
     async getPokemonSpecies(
         input: GetPokemonSpeciesInput,
+        context: TsContext,
     ): Promise<GetPokemonSpeciesOutput> {
+        console.log(`handling request ${context.requestId}`);
         return {
             name: input.name,
             flavorTextEntries: [
@@ -0,0 +1,102 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript context definition.
+
+use std::collections::HashMap;
+
+use aws_smithy_legacy_http_server::request::request_id::ServerRequestId;
+use http::Request;
+use napi_derive::napi;
+
+pub mod layer;
+
+/// TsContext carries request-scoped values to the Typescript handlers.
+///
+/// Every handler is invoked with the operation input and a [TsContext] as its second argument:
+///
+/// ```typescript
+/// async getPokemonSpecies(input: GetPokemonSpeciesInput, context: TsContext): Promise<GetPokemonSpeciesOutput> {
+///     console.log(`handling request ${context.requestId}`);
+///     // ...
+/// }
+/// ```
+///
+/// State shared between requests doesn't need to be carried by the context: it can be stored in the
+/// object implementing the handlers.
+///
+/// [layer::AddTsContextLayer], a [tower::Layer], populates a [TsContext] from every incoming request
+/// and inserts it in the request extensions, where the generated operation handlers find it.
+#[napi(object)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TsContext {
+    /// The unique ID the server assigned to the request, if any.
+    pub request_id: Option<String>,
+    /// The headers of the request, keyed by their lowercase name.
+    ///
+    /// Values of headers appearing more than once are joined with `, `. Headers whose value
+    /// isn't valid UTF-8 are omitted.
+    pub headers: HashMap<String, String>,
+}
+
+impl TsContext {
+    /// Populate a [TsContext] from a [Request].
+    pub fn from_request<B>(req: &Request<B>) -> Self {
+        let mut headers: HashMap<String, String> = HashMap::new();
+        for (name, value) in req.headers() {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            headers
+                .entry(name.as_str().to_owned())
+                .and_modify(|existing| {
+                    existing.push_str(", ");
+                    existing.push_str(value);
+                })
+                .or_insert_with(|| value.to_owned());
+        }
+        Self {
+            request_id: req
+                .extensions()
+                .get::<ServerRequestId>()
+                .map(ToString::to_string),
+            headers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_smithy_legacy_http_server::request::request_id::ServerRequestId;
+    use http::Request;
+    use pretty_assertions::assert_eq;
+
+    use super::TsContext;
+
+    #[test]
+    fn populates_from_request() {
+        let request_id = ServerRequestId::new();
+        let mut req = Request::builder()
+            .header("X-Custom", "a")
+            .header("x-custom", "b")
+            .header("Content-Type", "application/json")
+            .header("x-binary", &[0xff_u8][..])
+            .body(())
+            .unwrap();
+        req.extensions_mut().insert(request_id.clone());
+
+        let ctx = TsContext::from_request(&req);
+        assert_eq!(Some(request_id.to_string()), ctx.request_id);
+        assert_eq!(2, ctx.headers.len());
+        assert_eq!("a, b", ctx.headers["x-custom"]);
+        assert_eq!("application/json", ctx.headers["content-type"]);
+    }
+
+    #[test]
+    fn request_id_is_optional() {
+        let ctx = TsContext::from_request(&Request::new(()));
+        assert_eq!(TsContext::default(), ctx);
+    }
+}
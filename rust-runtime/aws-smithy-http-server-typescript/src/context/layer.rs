@@ -0,0 +1,89 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [tower::Layer] for injecting and populating [TsContext].
+
+use std::task::{Context, Poll};
+
+use http::{Request, Response};
+use tower::{Layer, Service};
+
+use super::TsContext;
+
+/// AddTsContextLayer is a [tower::Layer] that populates a [TsContext] from the [Request]
+/// and injects it to the [Request] as an extension.
+#[derive(Debug, Clone, Default)]
+pub struct AddTsContextLayer {
+    _private: (),
+}
+
+impl AddTsContextLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for AddTsContextLayer {
+    type Service = AddTsContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AddTsContextService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AddTsContextService<S> {
+    inner: S,
+}
+
+impl<ResBody, ReqBody, S> Service<Request<ReqBody>> for AddTsContextService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let ctx = TsContext::from_request(&req);
+        req.extensions_mut().insert(ctx);
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::{Request, Response};
+    use hyper::Body;
+    use tower::{service_fn, ServiceBuilder, ServiceExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn injects_context() {
+        let svc = ServiceBuilder::new()
+            .layer(AddTsContextLayer::new())
+            .service(service_fn(|req: Request<Body>| async move {
+                let ctx = req.extensions().get::<TsContext>().unwrap();
+                let user_agent = ctx.headers["user-agent"].clone();
+                Ok::<_, Infallible>(Response::new(Body::from(user_agent)))
+            }));
+
+        let req = Request::builder()
+            .header("User-Agent", "smithy-rs")
+            .body(Body::empty())
+            .unwrap();
+        let res = svc.oneshot(req).await.unwrap();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!("smithy-rs", body);
+    }
+}
@@ -0,0 +1,47 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript error definition.
+
+use aws_smithy_types::date_time::{ConversionError, DateTimeParseError};
+use thiserror::Error;
+
+/// Typescript error that implements foreign errors.
+///
+/// It converts into a [napi::Error], which is thrown as a Javascript `Error` when returned
+/// from a function called by Typescript.
+#[derive(Error, Debug)]
+pub enum TsError {
+    /// Implements `From<aws_smithy_types::date_time::ConversionError>`.
+    #[error("DateTimeConversion: {0}")]
+    DateTimeConversion(#[from] ConversionError),
+    /// Implements `From<aws_smithy_types::date_time::DateTimeParseError>`.
+    #[error("DateTimeParse: {0}")]
+    DateTimeParse(#[from] DateTimeParseError),
+    /// Implements `From<aws_smithy_types::byte_stream::error::Error>`.
+    #[error("ByteStream: {0}")]
+    ByteStream(#[from] aws_smithy_types::byte_stream::error::Error),
+    /// Implements `From<std::io::Error>`.
+    #[error("Io: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<TsError> for napi::Error {
+    fn from(other: TsError) -> napi::Error {
+        napi::Error::from_reason(other.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TsError;
+
+    #[test]
+    fn converts_into_napi_error() {
+        let err: napi::Error = TsError::from(std::io::Error::other("boom")).into();
+        assert_eq!(napi::Status::GenericFailure, err.status);
+        assert_eq!("Io: boom", err.reason);
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Invocation of Typescript handlers from Rust.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use napi::bindgen_prelude::{FromNapiValue, JsValuesTupleIntoVec, Promise, ToNapiValue};
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction};
+use napi::JsFunction;
+
+use crate::TsContext;
+
+/// A Typescript handler function representation.
+///
+/// Typescript handlers are `async` functions receiving the operation input and a [TsContext],
+/// and resolving to the operation output:
+///
+/// ```typescript
+/// (input: I, context: TsContext) => Promise<O>
+/// ```
+///
+/// Javascript functions can only be called from the Node.js main thread, while operations are
+/// handled by the Tokio runtime. The handler wraps the function in a [ThreadsafeFunction], which
+/// queues the calls on the Node.js event loop and sends the resolved value back to Rust.
+pub struct TsHandler<I: 'static, O> {
+    func: ThreadsafeFunction<(I, TsContext), ErrorStrategy::Fatal>,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<I, O> TsHandler<I, O>
+where
+    I: ToNapiValue + 'static,
+    O: FromNapiValue + Send + 'static,
+{
+    /// Create a new [TsHandler] from a Javascript function.
+    ///
+    /// This method has to be called from the Node.js main thread, usually when the application
+    /// is constructed from Typescript.
+    pub fn new(func: &JsFunction) -> napi::Result<Self> {
+        let func = func
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(I, TsContext)>| {
+                ctx.value.into_vec(ctx.env.raw())
+            })?;
+        Ok(Self {
+            func,
+            _output: PhantomData,
+        })
+    }
+
+    /// Call the Typescript handler and wait for the returned promise to settle.
+    ///
+    /// Exceptions thrown by the handler, and rejections of the returned promise, are returned as
+    /// a [napi::Error] whose reason is `String(error)`, i.e. `"${error.name}: ${error.message}"`
+    /// for Javascript errors. The generated code converts it into the modeled error of the
+    /// operation named `error.name`, if any.
+    pub async fn call(&self, input: I, context: TsContext) -> napi::Result<O> {
+        let result = match self.func.call_async::<Promise<O>>((input, context)).await {
+            Ok(promise) => promise.await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = &result {
+            tracing::error!(error = %err, "typescript handler failed");
+        }
+        result
+    }
+}
+
+impl<I, O> Clone for TsHandler<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            func: self.func.clone(),
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<I, O> fmt::Debug for TsHandler<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TsHandler")
+            .field("input", &std::any::type_name::<I>())
+            .field("output", &std::any::type_name::<O>())
+            .finish()
+    }
+}
@@ -6,3 +6,29 @@
 /* Automatically managed default lints */
 #![cfg_attr(docsrs, feature(doc_cfg))]
 /* End of automatically managed default lints */
+
+//! Rust/Typescript bindings, runtime and utilities.
+//!
+//! This crates implements all the generic code needed to start and manage
+//! a Smithy Rust HTTP server where the business logic is implemented in Typescript,
+//! leveraging [napi-rs].
+//!
+//! [napi-rs]: https://napi.rs/
+
+pub mod context;
+mod error;
+mod handler;
+mod server;
+mod socket;
+pub mod types;
+
+#[doc(inline)]
+pub use context::TsContext;
+#[doc(inline)]
+pub use error::TsError;
+#[doc(inline)]
+pub use handler::TsHandler;
+#[doc(inline)]
+pub use server::{TsServer, TsServerBuilder};
+#[doc(inline)]
+pub use socket::TsSocket;
@@ -0,0 +1,206 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::convert::Infallible;
+use std::fmt;
+use std::net::TcpListener as StdTcpListener;
+
+use aws_smithy_legacy_http_server::{
+    body::{Body, BoxBody},
+    request::request_id::ServerRequestIdProviderLayer,
+    routing::IntoMakeService,
+};
+use http::header::HeaderName;
+use http::{Request, Response};
+use hyper::server::conn::AddrIncoming;
+use socket2::Socket;
+use tokio::net::TcpListener;
+use tower::{util::BoxCloneService, Layer, ServiceBuilder, ServiceExt};
+
+use crate::{context::layer::AddTsContextLayer, TsError, TsSocket};
+
+// A `BoxCloneService` with default `Request`, `Response` and `Error`.
+type Service = BoxCloneService<Request<Body>, Response<BoxBody>, Infallible>;
+
+/// Builder for [TsServer].
+///
+/// The builder is used by the code generated by the `TsApplicationGenerator` Kotlin class to wrap
+/// the service of the application, built from the Typescript handlers, into a [TsServer]:
+///
+/// ```no_run
+/// # use std::convert::Infallible;
+/// # use aws_smithy_legacy_http_server::body::{Body, BoxBody};
+/// # use http::{Request, Response};
+/// # use tower::util::BoxCloneService;
+/// use aws_smithy_http_server_typescript::{TsServer, TsSocket};
+///
+/// # fn start(service: BoxCloneService<Request<Body>, Response<BoxBody>, Infallible>) -> napi::Result<()> {
+/// let socket = TsSocket::new("127.0.0.1".to_owned(), 9090, None)?;
+/// TsServer::builder(service)
+///     .request_id_header(http::header::HeaderName::from_static("x-request-id"))
+///     .build()
+///     .start(&socket)
+/// # }
+/// ```
+pub struct TsServerBuilder {
+    service: Service,
+    request_id_header: Option<HeaderName>,
+}
+
+impl TsServerBuilder {
+    /// Wrap the service of the application in a [tower::Layer].
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Service>,
+        L::Service: tower::Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+            + Clone
+            + Send
+            + 'static,
+        <L::Service as tower::Service<Request<Body>>>::Future: Send + 'static,
+    {
+        self.service = layer.layer(self.service).boxed_clone();
+        self
+    }
+
+    /// Add the unique ID assigned to every request to the responses, as the value of
+    /// `header_name`.
+    pub fn request_id_header(mut self, header_name: HeaderName) -> Self {
+        self.request_id_header = Some(header_name);
+        self
+    }
+
+    /// Build the [TsServer].
+    ///
+    /// Every request is assigned a unique ID, and a [TsContext](crate::TsContext) populated
+    /// from the request is inserted in its extensions before the request reaches the service.
+    pub fn build(self) -> TsServer {
+        let request_id_layer = match self.request_id_header {
+            Some(header_name) => {
+                ServerRequestIdProviderLayer::new_with_response_header(header_name)
+            }
+            None => ServerRequestIdProviderLayer::new(),
+        };
+        let service = ServiceBuilder::new()
+            .boxed_clone()
+            .layer(request_id_layer)
+            .layer(AddTsContextLayer::new())
+            .service(self.service);
+        TsServer { service }
+    }
+}
+
+impl fmt::Debug for TsServerBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TsServerBuilder")
+            .field("request_id_header", &self.request_id_header)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [hyper] server running the service of a Typescript application.
+///
+/// Node.js applications scale to multiple cores by forking workers with the [cluster] module:
+/// every worker starts its own [TsServer] on a clone of a shared [TsSocket].
+///
+/// [cluster]: https://nodejs.org/api/cluster.html
+#[derive(Clone)]
+pub struct TsServer {
+    service: Service,
+}
+
+impl TsServer {
+    /// Create a new [TsServerBuilder] for the given service.
+    pub fn builder<S>(service: S) -> TsServerBuilder
+    where
+        S: tower::Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+            + Clone
+            + Send
+            + 'static,
+        S::Future: Send + 'static,
+    {
+        TsServerBuilder {
+            service: service.boxed_clone(),
+            request_id_header: None,
+        }
+    }
+
+    /// Start serving requests accepted on a clone of `socket`.
+    ///
+    /// The server runs in the background on the Tokio runtime managed by napi-rs, so this method
+    /// returns as soon as the server is started and doesn't block the Node.js event loop.
+    pub fn start(&self, socket: &TsSocket) -> napi::Result<()> {
+        let raw_socket = socket.get_socket().map_err(TsError::from)?;
+        let service = self.service.clone();
+        napi::bindgen_prelude::spawn(serve(raw_socket, service));
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TsServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TsServer").finish_non_exhaustive()
+    }
+}
+
+async fn serve(socket: Socket, service: Service) {
+    let addr = addr_incoming_from_socket(socket);
+    let server = hyper::Server::builder(addr).serve(IntoMakeService::new(service));
+
+    tracing::trace!("started hyper server from shared socket");
+    // Run forever-ish...
+    if let Err(err) = server.await {
+        tracing::error!(error = ?err, "server error");
+    }
+}
+
+fn addr_incoming_from_socket(socket: Socket) -> AddrIncoming {
+    let std_listener: StdTcpListener = socket.into();
+    // StdTcpListener::from_std doesn't set O_NONBLOCK
+    std_listener
+        .set_nonblocking(true)
+        .expect("unable to set `O_NONBLOCK=true` on `std::net::TcpListener`");
+    let listener = TcpListener::from_std(std_listener)
+        .expect("unable to create `tokio::net::TcpListener` from `std::net::TcpListener`");
+    AddrIncoming::from_listener(listener)
+        .expect("unable to create `AddrIncoming` from `TcpListener`")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use aws_smithy_legacy_http_server::body::{to_boxed, Body};
+    use http::{header::HeaderName, Request, Response};
+    use tower::service_fn;
+
+    use super::{serve, TsServer};
+    use crate::{TsContext, TsSocket};
+
+    #[tokio::test]
+    async fn serves_requests_with_context() {
+        let service = service_fn(|req: Request<Body>| async move {
+            let ctx = req.extensions().get::<TsContext>().unwrap();
+            let body = format!("{} {}", ctx.request_id.is_some(), ctx.headers["x-greeting"]);
+            Ok::<_, Infallible>(Response::new(to_boxed(body)))
+        });
+        let server = TsServer::builder(service)
+            .request_id_header(HeaderName::from_static("x-request-id"))
+            .build();
+
+        let socket = TsSocket::new("127.0.0.1".to_owned(), 0, None).unwrap();
+        let addr = socket.inner.local_addr().unwrap().as_socket().unwrap();
+        tokio::spawn(serve(socket.get_socket().unwrap(), server.service.clone()));
+
+        let req = Request::builder()
+            .uri(format!("http://{addr}/"))
+            .header("x-greeting", "hello")
+            .body(hyper::Body::empty())
+            .unwrap();
+        let res = hyper::Client::new().request(req).await.unwrap();
+        assert!(res.headers().contains_key("x-request-id"));
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!("true hello", body);
+    }
+}
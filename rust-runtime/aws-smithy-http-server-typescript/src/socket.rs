@@ -0,0 +1,109 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Socket implementation that can be shared between multiple Node.js processes.
+
+use napi_derive::napi;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::SocketAddr;
+
+use crate::TsError;
+
+/// Socket implementation that can be shared between multiple Node.js processes.
+///
+/// Node.js runs the Javascript code of an application on a single thread. To use all the
+/// available computing capacity of the host, Node.js applications usually fork multiple workers
+/// with the [cluster] module that share a socket created with `SO_REUSEADDR` and `SO_REUSEPORT`
+/// enabled.
+///
+/// [cluster]: https://nodejs.org/api/cluster.html
+#[napi]
+#[derive(Debug)]
+pub struct TsSocket {
+    pub(crate) inner: Socket,
+}
+
+#[napi]
+impl TsSocket {
+    /// Create a new UNIX `Socket` from an address, port and backlog.
+    /// If not specified, the backlog defaults to 1024 connections.
+    #[napi(constructor)]
+    pub fn new(address: String, port: i32, backlog: Option<i32>) -> napi::Result<Self> {
+        let address: SocketAddr = format!("{address}:{port}")
+            .parse()
+            .map_err(|e: std::net::AddrParseError| napi::Error::from_reason(e.to_string()))?;
+        let (domain, ip_version) = TsSocket::socket_domain(address);
+        tracing::trace!(address = %address, ip_version, "shared socket listening");
+        let socket =
+            Socket::new(domain, Type::STREAM, Some(Protocol::TCP)).map_err(TsError::from)?;
+        // Set value for the `SO_REUSEPORT` and `SO_REUSEADDR` options on this socket.
+        // This indicates that further calls to `bind` may allow reuse of local
+        // addresses. For IPv4 sockets this means that a socket may bind even when
+        // there's a socket already listening on this port.
+        socket.set_reuse_port(true).map_err(TsError::from)?;
+        socket.set_reuse_address(true).map_err(TsError::from)?;
+        socket.bind(&address.into()).map_err(TsError::from)?;
+        socket
+            .listen(backlog.unwrap_or(1024))
+            .map_err(TsError::from)?;
+        Ok(TsSocket { inner: socket })
+    }
+
+    /// Clone the inner socket allowing it to be shared between multiple
+    /// Node.js processes.
+    #[napi]
+    pub fn try_clone(&self) -> napi::Result<TsSocket> {
+        let copied = self.inner.try_clone().map_err(TsError::from)?;
+        Ok(TsSocket { inner: copied })
+    }
+}
+
+impl TsSocket {
+    /// Get a cloned inner socket.
+    pub fn get_socket(&self) -> Result<Socket, std::io::Error> {
+        self.inner.try_clone()
+    }
+
+    /// Find the socket domain
+    fn socket_domain(address: SocketAddr) -> (Domain, &'static str) {
+        if address.is_ipv6() {
+            (Domain::IPV6, "6")
+        } else {
+            (Domain::IPV4, "4")
+        }
+    }
+}
+
+#[cfg(test)]
+// `is_listener` on `Socket` is only available on certain platforms.
+// In particular, this fails to compile on MacOS.
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "fuchsia",
+    target_os = "linux",
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn socket_can_bind_on_random_port() {
+        let socket = TsSocket::new("127.0.0.1".to_owned(), 0, None).unwrap();
+        assert!(socket.inner.is_listener().is_ok());
+    }
+
+    #[test]
+    fn socket_can_be_cloned() {
+        let socket = TsSocket::new("127.0.0.1".to_owned(), 0, None).unwrap();
+        let cloned_socket = socket.try_clone().unwrap();
+        assert!(cloned_socket.inner.is_listener().is_ok());
+    }
+
+    #[test]
+    fn invalid_address_is_rejected() {
+        let err = TsSocket::new("not an address".to_owned(), 0, None).unwrap_err();
+        assert!(err.reason.contains("invalid socket address"), "{err}");
+    }
+}
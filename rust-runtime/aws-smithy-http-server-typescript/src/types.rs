@@ -0,0 +1,479 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript wrapped types from aws-smithy-types.
+//!
+//! ## `Deref`
+//! Some of the types implement [Deref] to make the original Rust type accessible.
+//! These implementations are not ideal and can be abused, **please use them carefully**.
+
+use std::{
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+    ptr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use napi::bindgen_prelude::{
+    Buffer, FromNapiRef, FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue,
+};
+use napi::{check_status, sys, ValueType};
+use napi_derive::napi;
+use tokio::sync::Mutex;
+
+use crate::TsError;
+
+/// Typescript Wrapper for [aws_smithy_types::Blob].
+///
+/// Blobs are exchanged with Typescript as `Buffer`s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blob(aws_smithy_types::Blob);
+
+impl Blob {
+    /// Creates a new blob from the given `input`.
+    pub fn new<T: Into<Vec<u8>>>(input: T) -> Self {
+        Self(aws_smithy_types::Blob::new(input))
+    }
+
+    /// Consumes the `Blob` and returns a `Vec<u8>` with its contents.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0.into_inner()
+    }
+}
+
+impl AsRef<[u8]> for Blob {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<aws_smithy_types::Blob> for Blob {
+    fn from(other: aws_smithy_types::Blob) -> Blob {
+        Blob(other)
+    }
+}
+
+impl From<Blob> for aws_smithy_types::Blob {
+    fn from(other: Blob) -> aws_smithy_types::Blob {
+        other.0
+    }
+}
+
+impl<'blob> From<&'blob Blob> for &'blob aws_smithy_types::Blob {
+    fn from(other: &'blob Blob) -> &'blob aws_smithy_types::Blob {
+        &other.0
+    }
+}
+
+impl TypeName for Blob {
+    fn type_name() -> &'static str {
+        "Blob"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+impl ValidateNapiValue for Blob {
+    unsafe fn validate(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+    ) -> napi::Result<sys::napi_value> {
+        <Buffer as ValidateNapiValue>::validate(env, napi_val)
+    }
+}
+
+impl ToNapiValue for Blob {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        Buffer::to_napi_value(env, Buffer::from(val.into_inner()))
+    }
+}
+
+impl FromNapiValue for Blob {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        let buffer = Buffer::from_napi_value(env, napi_val)?;
+        Ok(Blob::new(Vec::from(buffer)))
+    }
+}
+
+/// Typescript Wrapper for [aws_smithy_types::date_time::DateTime].
+///
+/// Date times are exchanged with Typescript as `Date`s, which have a millisecond precision: the
+/// sub-millisecond part of a date time is truncated when it is converted to a `Date`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateTime(aws_smithy_types::date_time::DateTime);
+
+impl From<aws_smithy_types::DateTime> for DateTime {
+    fn from(other: aws_smithy_types::DateTime) -> DateTime {
+        DateTime(other)
+    }
+}
+
+impl From<DateTime> for aws_smithy_types::DateTime {
+    fn from(other: DateTime) -> aws_smithy_types::DateTime {
+        other.0
+    }
+}
+
+impl Deref for DateTime {
+    type Target = aws_smithy_types::DateTime;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TypeName for DateTime {
+    fn type_name() -> &'static str {
+        "Date"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+impl ValidateNapiValue for DateTime {
+    unsafe fn validate(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+    ) -> napi::Result<sys::napi_value> {
+        let mut is_date = false;
+        check_status!(unsafe { sys::napi_is_date(env, napi_val, &mut is_date) })?;
+        if !is_date {
+            return Err(napi::Error::new(
+                napi::Status::InvalidArg,
+                "Expected a Date object".to_owned(),
+            ));
+        }
+        Ok(ptr::null_mut())
+    }
+}
+
+impl ToNapiValue for DateTime {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        let millis = val.0.to_millis().map_err(TsError::from)?;
+        let mut date = ptr::null_mut();
+        check_status!(
+            unsafe { sys::napi_create_date(env, millis as f64, &mut date) },
+            "Failed to convert `DateTime` into a Date"
+        )?;
+        Ok(date)
+    }
+}
+
+impl FromNapiValue for DateTime {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        unsafe { Self::validate(env, napi_val)? };
+        let mut millis = 0.0;
+        check_status!(
+            unsafe { sys::napi_get_date_value(env, napi_val, &mut millis) },
+            "Failed to convert a Date into `DateTime`"
+        )?;
+        Ok(DateTime(aws_smithy_types::DateTime::from_millis(
+            millis as i64,
+        )))
+    }
+}
+
+/// Typescript Wrapper for [aws_smithy_types::byte_stream::ByteStream].
+///
+/// ByteStream provides misuse-resistant primitives to make it easier to handle common patterns with streaming data.
+///
+/// On the Rust side, The Typescript implementation wraps the original [ByteStream](aws_smithy_types::byte_stream::ByteStream)
+/// in a clonable structure and implements the [Stream](futures::stream::Stream) trait for it to
+/// allow Rust to handle the type transparently.
+///
+/// On the Typescript side, chunks are read one at a time with `next()`, which resolves to `null`
+/// once the stream is exhausted, or all at once with `collect()`:
+///
+/// ```typescript
+/// const stream = await ByteStream.fromPath("/tmp/music.mp3");
+/// for (let chunk = await stream.next(); chunk !== null; chunk = await stream.next()) {
+///     console.log(chunk.length);
+/// }
+/// ```
+///
+/// The original Rust [ByteStream](aws_smithy_types::byte_stream::ByteStream) is wrapped inside a `Arc<Mutex>` to allow the type to be
+/// [Clone] (required to share it with Typescript) and to allow internal mutability, required to fetch the next chunk of data.
+#[napi]
+#[derive(Debug, Clone)]
+pub struct ByteStream(Arc<Mutex<aws_smithy_types::byte_stream::ByteStream>>);
+
+impl futures::stream::Stream for ByteStream {
+    type Item = Result<Bytes, aws_smithy_types::byte_stream::error::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let stream = self.0.lock();
+        tokio::pin!(stream);
+        match stream.poll(cx) {
+            Poll::Ready(mut stream) => Pin::new(&mut *stream).poll_next(cx),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Return a new data chunk from the stream.
+async fn yield_data_chunk(
+    body: Arc<Mutex<aws_smithy_types::byte_stream::ByteStream>>,
+) -> napi::Result<Option<Bytes>> {
+    let mut stream = body.lock().await;
+    Ok(stream.next().await.transpose().map_err(TsError::from)?)
+}
+
+impl ByteStream {
+    /// Construct a new [`ByteStream`](aws_smithy_types::byte_stream::ByteStream) from a
+    /// [`SdkBody`](aws_smithy_types::body::SdkBody).
+    ///
+    /// This method is available only to Rust and it is required to comply with the
+    /// interface required by the code generator.
+    pub fn new(body: aws_smithy_types::body::SdkBody) -> Self {
+        Self(Arc::new(Mutex::new(
+            aws_smithy_types::byte_stream::ByteStream::new(body),
+        )))
+    }
+}
+
+impl Default for ByteStream {
+    fn default() -> Self {
+        Self::new(aws_smithy_types::body::SdkBody::from(""))
+    }
+}
+
+#[napi]
+impl ByteStream {
+    /// Create a new [ByteStream](aws_smithy_types::byte_stream::ByteStream) from a `Buffer`.
+    #[napi(constructor)]
+    pub fn tsnew(input: Buffer) -> Self {
+        Self::new(aws_smithy_types::body::SdkBody::from(Vec::from(input)))
+    }
+
+    /// Create a new [ByteStream](aws_smithy_types::byte_stream::ByteStream) streaming the
+    /// content of the file at `path`.
+    #[napi]
+    pub async fn from_path(path: String) -> napi::Result<ByteStream> {
+        let byte_stream = aws_smithy_types::byte_stream::ByteStream::from_path(path)
+            .await
+            .map_err(TsError::from)?;
+        Ok(Self(Arc::new(Mutex::new(byte_stream))))
+    }
+
+    /// Return the next chunk of data, or `null` if the stream is exhausted.
+    #[napi(ts_return_type = "Promise<Buffer | null>")]
+    pub async fn next(&self) -> napi::Result<Option<Buffer>> {
+        let data = yield_data_chunk(self.0.clone()).await?;
+        Ok(data.map(|data| Buffer::from(data.to_vec())))
+    }
+
+    /// Read the remaining data of the stream into a single `Buffer`.
+    #[napi]
+    pub async fn collect(&self) -> napi::Result<Buffer> {
+        let mut stream = self.0.lock().await;
+        let mut data = Vec::new();
+        while let Some(chunk) = stream.next().await.transpose().map_err(TsError::from)? {
+            data.extend_from_slice(&chunk);
+        }
+        Ok(Buffer::from(data))
+    }
+}
+
+// Classes are passed to Rust by reference: the stream shares its state with the Typescript object.
+impl FromNapiValue for ByteStream {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        let stream: &ByteStream = unsafe { ByteStream::from_napi_ref(env, napi_val)? };
+        Ok(stream.clone())
+    }
+}
+
+/// Typescript Wrapper for [aws_smithy_types::Document].
+///
+/// Documents are exchanged with Typescript as JSON values: objects, arrays, numbers, strings,
+/// booleans and `null`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document(aws_smithy_types::Document);
+
+impl Deref for Document {
+    type Target = aws_smithy_types::Document;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<aws_smithy_types::Document> for Document {
+    fn from(other: aws_smithy_types::Document) -> Document {
+        Document(other)
+    }
+}
+
+impl From<Document> for aws_smithy_types::Document {
+    fn from(other: Document) -> aws_smithy_types::Document {
+        other.0
+    }
+}
+
+impl From<Document> for serde_json::Value {
+    fn from(other: Document) -> serde_json::Value {
+        use aws_smithy_types::{Document as D, Number};
+        use serde_json::Value as V;
+
+        match other.0 {
+            D::Object(obj) => V::Object(
+                obj.into_iter()
+                    .map(|(k, v)| (k, Document(v).into()))
+                    .collect(),
+            ),
+            D::Array(vec) => V::Array(vec.into_iter().map(|d| Document(d).into()).collect()),
+            D::Number(Number::PosInt(pi)) => V::from(pi),
+            D::Number(Number::NegInt(ni)) => V::from(ni),
+            // JSON can't represent `NaN` and infinite numbers, which become `null`.
+            D::Number(Number::Float(f)) => serde_json::Number::from_f64(f)
+                .map(V::Number)
+                .unwrap_or(V::Null),
+            D::String(str) => V::String(str),
+            D::Bool(bool) => V::Bool(bool),
+            D::Null => V::Null,
+        }
+    }
+}
+
+impl From<serde_json::Value> for Document {
+    fn from(other: serde_json::Value) -> Document {
+        use aws_smithy_types::{Document as D, Number};
+        use serde_json::Value as V;
+
+        Document(match other {
+            V::Object(obj) => D::Object(
+                obj.into_iter()
+                    .map(|(k, v)| (k, Document::from(v).0))
+                    .collect(),
+            ),
+            V::Array(vec) => D::Array(vec.into_iter().map(|v| Document::from(v).0).collect()),
+            V::Number(number) => {
+                if let Some(pi) = number.as_u64() {
+                    D::Number(Number::PosInt(pi))
+                } else if let Some(ni) = number.as_i64() {
+                    D::Number(Number::NegInt(ni))
+                } else {
+                    D::Number(Number::Float(number.as_f64().unwrap_or(f64::NAN)))
+                }
+            }
+            V::String(str) => D::String(str),
+            V::Bool(bool) => D::Bool(bool),
+            V::Null => D::Null,
+        })
+    }
+}
+
+impl TypeName for Document {
+    fn type_name() -> &'static str {
+        "Document"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Unknown
+    }
+}
+
+impl ValidateNapiValue for Document {}
+
+impl ToNapiValue for Document {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        serde_json::Value::to_napi_value(env, val.into())
+    }
+}
+
+impl FromNapiValue for Document {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        serde_json::Value::from_napi_value(env, napi_val).map(Document::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::StreamExt;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn blob_conversions() {
+        let blob = Blob::new("some data");
+        assert_eq!(b"some data", blob.as_ref());
+        let inner: aws_smithy_types::Blob = blob.clone().into();
+        assert_eq!(blob, Blob::from(inner));
+    }
+
+    #[test]
+    fn document_to_json_and_back() {
+        use aws_smithy_types::{Document as D, Number};
+
+        let document = Document(D::Object(HashMap::from([
+            ("string".to_owned(), D::String("value".to_owned())),
+            (
+                "array".to_owned(),
+                D::Array(vec![
+                    D::Number(Number::PosInt(1)),
+                    D::Number(Number::NegInt(-1)),
+                    D::Number(Number::Float(1.5)),
+                ]),
+            ),
+            ("bool".to_owned(), D::Bool(true)),
+            ("null".to_owned(), D::Null),
+        ])));
+
+        let json = serde_json::Value::from(document.clone());
+        assert_eq!(
+            serde_json::json!({
+                "string": "value",
+                "array": [1, -1, 1.5],
+                "bool": true,
+                "null": null,
+            }),
+            json
+        );
+        assert_eq!(document, Document::from(json));
+    }
+
+    #[test]
+    fn non_finite_numbers_become_null() {
+        use aws_smithy_types::{Document as D, Number};
+
+        let json = serde_json::Value::from(Document(D::Number(Number::Float(f64::NAN))));
+        assert_eq!(serde_json::Value::Null, json);
+    }
+
+    #[tokio::test]
+    async fn bytestream_can_be_streamed_from_rust() {
+        let stream = ByteStream::tsnew(Buffer::from(b"some data".to_vec()));
+        let chunks: Vec<_> = stream.clone().collect().await;
+        assert_eq!(1, chunks.len());
+        assert_eq!(&b"some data"[..], chunks[0].as_ref().unwrap());
+
+        // The stream is shared between clones
+        assert!(stream.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn bytestream_from_path() {
+        let path = std::env::temp_dir().join("aws-smithy-http-server-typescript-bytestream");
+        std::fs::write(&path, "file content").unwrap();
+
+        let stream = ByteStream::from_path(path.to_string_lossy().into_owned())
+            .await
+            .unwrap();
+        assert_eq!(
+            &b"file content"[..],
+            &*ByteStream::collect(&stream).await.unwrap()
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -136,6 +136,24 @@ impl fmt::Display for BodyLimitExceeded {
 
 impl std::error::Error for BodyLimitExceeded {}
 
+impl BodyLimitExceeded {
+    /// Returns the `BodyLimitExceeded` in the chain of sources of `err`, if any.
+    ///
+    /// Body wrappers, like the one decompressing requests in [`crate::layer`], report an exceeded
+    /// limit with this error so that the deserializer can reject the request with the protocol's
+    /// `413 Payload Too Large` error.
+    pub(crate) fn find(err: &(dyn std::error::Error + 'static)) -> Option<Self> {
+        let mut source = Some(err);
+        while let Some(err) = source {
+            if let Some(exceeded) = err.downcast_ref::<Self>() {
+                return Some(*exceeded);
+            }
+            source = err.source();
+        }
+        None
+    }
+}
+
 /// The error returned by [`collect_body_limited`].
 ///
 /// Either the underlying body produced an error, or the configured size limit was
@@ -0,0 +1,108 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use http::HeaderValue;
+
+use crate::shape_id::ShapeId;
+
+/// The origins allowed to make cross-origin requests to an operation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AllowedOrigins(Origins);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum Origins {
+    #[default]
+    None,
+    Any,
+    List(HashSet<String>),
+}
+
+impl AllowedOrigins {
+    /// Allows no origin.
+    pub fn none() -> Self {
+        Self(Origins::None)
+    }
+
+    /// Allows any origin.
+    pub fn any() -> Self {
+        Self(Origins::Any)
+    }
+
+    /// Allows the origins in `origins`, e.g. `https://www.example.com`.
+    pub fn list<I>(origins: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self(Origins::List(origins.into_iter().map(Into::into).collect()))
+    }
+
+    pub(crate) fn is_any(&self) -> bool {
+        self.0 == Origins::Any
+    }
+
+    pub(crate) fn allows(&self, origin: &HeaderValue) -> bool {
+        match &self.0 {
+            Origins::None => false,
+            Origins::Any => true,
+            Origins::List(origins) => origin.to_str().is_ok_and(|origin| origins.contains(origin)),
+        }
+    }
+}
+
+/// The configuration of a [`CorsPlugin`](super::CorsPlugin).
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    pub(crate) origins: AllowedOrigins,
+    pub(crate) operations: HashMap<ShapeId, AllowedOrigins>,
+    pub(crate) allow_credentials: bool,
+    pub(crate) max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Allows `origins` to make cross-origin requests to the operations without
+    /// [`operation`](CorsConfig::operation) origins.
+    pub fn new(origins: AllowedOrigins) -> Self {
+        Self {
+            origins,
+            operations: HashMap::new(),
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Allows `origins`, instead of the default origins, to make cross-origin requests to the
+    /// operation identified by `operation`.
+    pub fn operation(mut self, operation: ShapeId, origins: AllowedOrigins) -> Self {
+        self.operations.insert(operation, origins);
+        self
+    }
+
+    /// Allows cross-origin requests to include credentials, such as cookies.
+    ///
+    /// When credentials are allowed, [`AllowedOrigins::any`] allows the origin of each request
+    /// rather than `*`, which browsers reject for requests with credentials.
+    pub fn allow_credentials(mut self, allow_credentials: bool) -> Self {
+        self.allow_credentials = allow_credentials;
+        self
+    }
+
+    /// Sets how long the responses to preflight requests can be cached for.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns the origins allowed to call `operation`, or the default origins if the operation
+    /// isn't known.
+    pub(crate) fn origins(&self, operation: Option<&ShapeId>) -> &AllowedOrigins {
+        operation
+            .and_then(|operation| self.operations.get(operation))
+            .unwrap_or(&self.origins)
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+//! Provides [`CorsPlugin`], an HTTP plugin implementing [Cross-Origin Resource Sharing] (CORS)
+//! with an awareness of the Smithy model.
+//!
+//! Unlike a generic CORS middleware, [`CorsPlugin`]:
+//!
+//! - answers preflight `OPTIONS` requests with the methods the router has operations for at the
+//!   requested path, which the requesting origin is allowed to call;
+//! - allows different origins for different operations; and
+//! - exposes the response headers bound with [`@httpHeader`] in the output and errors of each
+//!   operation, see [`CorsOperation`].
+//!
+//! # Preflight requests
+//!
+//! A preflight request is an `OPTIONS` request with an `Origin` and an
+//! `Access-Control-Request-Method` header. When no operation is modeled with the `OPTIONS` method
+//! at the requested path, the [`RestRouter`](crate::protocol::rest::router::RestRouter) routes
+//! preflight requests to the operation with the requested method, with a [`CorsPreflight`]
+//! extension listing the methods allowed at the path. The [`CorsService`] of the operation answers
+//! them without calling the operation. Preflight requests to operations without a [`CorsService`]
+//! are rejected with `405 Method Not Allowed`, as if they weren't routed.
+//!
+//! Preflight requests go through the HTTP plugins of the operation like any other request, and
+//! browsers don't send credentials with them. [`CorsPlugin`] must therefore be the first HTTP
+//! plugin registered, so that the plugins registered after it, such as authentication, rate
+//! limiting, timeouts or metrics, never see preflight requests:
+//!
+//! ```
+//! # use aws_smithy_http_server::cors::{AllowedOrigins, CorsConfig, CorsPlugin};
+//! # use aws_smithy_http_server::plugin::{HttpPlugins, IdentityPlugin};
+//! # let config = CorsConfig::new(AllowedOrigins::any());
+//! # let auth_plugin = IdentityPlugin;
+//! let http_plugins = HttpPlugins::new()
+//!     // Preflight requests are answered here...
+//!     .push(CorsPlugin::new(config))
+//!     // ...and are never seen by this plugin.
+//!     .push(auth_plugin);
+//! ```
+//!
+//! Only the restJson1 and restXml protocols route preflight requests: operations of the RPC
+//! protocols can't be identified from a preflight request.
+//!
+//! # Example
+//!
+//! ```
+//! # use aws_smithy_http_server::shape_id::ShapeId;
+//! # struct GetStorage;
+//! # impl GetStorage { const ID: ShapeId = ShapeId::new("namespace#GetStorage", "namespace", "GetStorage"); }
+//! use aws_smithy_http_server::cors::{AllowedOrigins, CorsConfig, CorsPlugin};
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! use std::time::Duration;
+//!
+//! let config = CorsConfig::new(AllowedOrigins::list(["https://www.example.com"]))
+//!     // Only the admin console may call `GetStorage`.
+//!     .operation(GetStorage::ID, AllowedOrigins::list(["https://admin.example.com"]))
+//!     .allow_credentials(true)
+//!     .max_age(Duration::from_secs(600));
+//!
+//! let http_plugins = HttpPlugins::new().push(CorsPlugin::new(config));
+//! ```
+//!
+//! [Cross-Origin Resource Sharing]: https://fetch.spec.whatwg.org/#http-cors-protocol
+//! [`@httpHeader`]: https://smithy.io/2.0/spec/http-bindings.html#httpheader-trait
+
+mod config;
+mod plugin;
+mod service;
+
+pub use config::*;
+pub use plugin::*;
+pub use service::*;
+
+use crate::shape_id::ShapeId;
+
+/// Model information about an operation used to answer CORS requests.
+///
+/// This trait is implemented for every operation by the generated code.
+pub trait CorsOperation {
+    /// The lowercase names of the headers bound with `@httpHeader` in the output and errors of the
+    /// operation, which are exposed to the caller's script.
+    ///
+    /// Headers bound with `@httpPrefixHeaders` can't be listed, and aren't exposed.
+    const EXPOSED_HEADERS: &'static [&'static str];
+}
+
+/// A request extension marking a CORS preflight request routed to an operation.
+///
+/// Inserted by the [`RoutingService`](crate::routing::RoutingService), see the
+/// [module](crate::cors) documentation.
+#[derive(Clone, Debug)]
+pub struct CorsPreflight {
+    routes: Vec<(http::Method, Option<ShapeId>)>,
+}
+
+impl CorsPreflight {
+    /// Creates a `CorsPreflight` for a path at which the router has `routes`, given by their method
+    /// and the ID of the operation they route to, if known.
+    pub fn new(routes: Vec<(http::Method, Option<ShapeId>)>) -> Self {
+        Self { routes }
+    }
+
+    /// Returns the methods the router has operations for at the requested path, and the IDs of
+    /// these operations, if known.
+    pub fn routes(&self) -> &[(http::Method, Option<ShapeId>)] {
+        &self.routes
+    }
+
+    /// Returns the methods the router has operations for at the requested path.
+    pub fn allowed_methods(&self) -> impl Iterator<Item = &http::Method> {
+        self.routes.iter().map(|(method, _operation)| method)
+    }
+}
+
+/// Returns true if `request` is a CORS preflight request.
+pub(crate) fn is_preflight<B>(request: &http::Request<B>) -> bool {
+    request.method() == http::Method::OPTIONS
+        && request.headers().contains_key(http::header::ORIGIN)
+        && request
+            .headers()
+            .contains_key(http::header::ACCESS_CONTROL_REQUEST_METHOD)
+}
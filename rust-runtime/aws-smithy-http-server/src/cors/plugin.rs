@@ -0,0 +1,43 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::sync::Arc;
+
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, Plugin};
+
+use super::service::Policy;
+use super::{CorsConfig, CorsOperation, CorsService};
+
+/// A [`Plugin`] which applies [`CorsService`] to every operation.
+///
+/// It must be the first HTTP plugin registered, see the [module](crate::cors) documentation for
+/// more information.
+#[derive(Clone, Debug)]
+pub struct CorsPlugin {
+    config: Arc<CorsConfig>,
+}
+
+impl CorsPlugin {
+    /// Creates a `CorsPlugin` with `config`.
+    pub fn new(config: CorsConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for CorsPlugin
+where
+    Op: OperationShape + CorsOperation,
+{
+    type Output = CorsService<T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        CorsService::new(inner, Policy::new(&self.config, &Op::ID, Op::EXPOSED_HEADERS))
+    }
+}
+
+impl HttpMarker for CorsPlugin {}
@@ -0,0 +1,440 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [`Service`] and its associated [`Future`] implementing CORS for an operation.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::header::{
+    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS,
+    ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY,
+};
+use http::{HeaderMap, HeaderValue, StatusCode};
+use tower::Service;
+
+use crate::body::BoxBody;
+use crate::shape_id::ShapeId;
+
+use super::{AllowedOrigins, CorsConfig, CorsPreflight};
+
+/// The CORS policy of an operation.
+#[derive(Debug)]
+pub(crate) struct Policy {
+    origins: AllowedOrigins,
+    // Used to find the origins allowed to call the other operations at the path of a preflight request.
+    config: Arc<CorsConfig>,
+    allow_credentials: bool,
+    max_age: Option<HeaderValue>,
+    expose_headers: Option<HeaderValue>,
+}
+
+impl Policy {
+    pub(crate) fn new(config: &Arc<CorsConfig>, operation: &ShapeId, exposed_headers: &[&str]) -> Self {
+        let expose_headers = (!exposed_headers.is_empty())
+            .then(|| HeaderValue::from_str(&exposed_headers.join(", ")).expect("header names are valid header values"));
+        Self {
+            origins: config.origins(Some(operation)).clone(),
+            config: config.clone(),
+            allow_credentials: config.allow_credentials,
+            max_age: config.max_age.map(|max_age| max_age.as_secs().into()),
+            expose_headers,
+        }
+    }
+
+    /// Returns the `Access-Control-Allow-Origin` for `origin`, or `None` if it isn't allowed.
+    fn allow_origin(&self, origin: &HeaderValue) -> Option<HeaderValue> {
+        if !self.origins.allows(origin) {
+            None
+        } else if self.origins.is_any() && !self.allow_credentials {
+            Some(HeaderValue::from_static("*"))
+        } else {
+            Some(origin.clone())
+        }
+    }
+
+    fn insert_common_headers(&self, headers: &mut HeaderMap, allow_origin: HeaderValue) {
+        if allow_origin != "*" {
+            headers.append(VARY, HeaderValue::from_static("origin"));
+        }
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+        if self.allow_credentials {
+            headers.insert(ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true"));
+        }
+    }
+
+    fn preflight(
+        &self,
+        origin: &HeaderValue,
+        preflight: &CorsPreflight,
+        request: &HeaderMap,
+    ) -> http::Response<BoxBody> {
+        let Some(allow_origin) = self.allow_origin(origin) else {
+            let mut response = http::Response::new(crate::body::empty());
+            *response.status_mut() = StatusCode::FORBIDDEN;
+            return response;
+        };
+
+        let mut response = http::Response::new(crate::body::empty());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        let headers = response.headers_mut();
+        self.insert_common_headers(headers, allow_origin);
+        // Only the methods of the operations `origin` is allowed to call are listed, so that the other
+        // operations at the path aren't disclosed to it. The requested method is the method of this
+        // operation, which `origin` was allowed to call above.
+        let requested_method = request.get(ACCESS_CONTROL_REQUEST_METHOD);
+        let allow_methods = preflight
+            .routes()
+            .iter()
+            .filter(|(method, operation)| {
+                requested_method.is_some_and(|requested| requested == method.as_str())
+                    || self.config.origins(operation.as_ref()).allows(origin)
+            })
+            .map(|(method, _operation)| method.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        headers.insert(
+            ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_str(&allow_methods).expect("methods are valid header values"),
+        );
+        // Any request header may be bound to the input of the operation.
+        if let Some(request_headers) = request.get(ACCESS_CONTROL_REQUEST_HEADERS) {
+            headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, request_headers.clone());
+        }
+        if let Some(max_age) = &self.max_age {
+            headers.insert(ACCESS_CONTROL_MAX_AGE, max_age.clone());
+        }
+        headers.append(VARY, HeaderValue::from_static("access-control-request-method"));
+        headers.append(VARY, HeaderValue::from_static("access-control-request-headers"));
+        response
+    }
+
+    fn decorate(&self, headers: &mut HeaderMap, allow_origin: HeaderValue) {
+        self.insert_common_headers(headers, allow_origin);
+        if let Some(expose_headers) = &self.expose_headers {
+            headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, expose_headers.clone());
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    #[project = InnerProj]
+    enum Inner<Fut> {
+        // A request which isn't a preflight request, whose response is decorated with the CORS
+        // headers if its origin is allowed.
+        Actual {
+            #[pin]
+            inner: Fut,
+            allow_origin: Option<HeaderValue>,
+            policy: Arc<Policy>,
+        },
+        Preflight {
+            response: Option<http::Response<BoxBody>>,
+        },
+    }
+}
+
+// This is to provide type erasure.
+pin_project_lite::pin_project! {
+    /// The [`Future`] of [`CorsService`].
+    pub struct CorsFuture<Fut> {
+        #[pin]
+        inner: Inner<Fut>,
+    }
+}
+
+impl<Fut, E> Future for CorsFuture<Fut>
+where
+    Fut: Future<Output = Result<http::Response<BoxBody>, E>>,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().inner.project() {
+            InnerProj::Actual {
+                inner,
+                allow_origin,
+                policy,
+            } => {
+                let mut response = futures_util::ready!(inner.poll(cx))?;
+                if let Some(allow_origin) = allow_origin.take() {
+                    policy.decorate(response.headers_mut(), allow_origin);
+                }
+                Poll::Ready(Ok(response))
+            }
+            InnerProj::Preflight { response } => Poll::Ready(Ok(response
+                .take()
+                .expect("futures must not be polled after completion"))),
+        }
+    }
+}
+
+/// A middleware [`Service`] answering the CORS preflight requests routed to an operation, and
+/// adding the CORS headers to the responses to cross-origin requests.
+///
+/// See the [module](crate::cors) documentation for more information.
+#[derive(Clone, Debug)]
+pub struct CorsService<S> {
+    inner: S,
+    policy: Arc<Policy>,
+}
+
+impl<S> CorsService<S> {
+    pub(crate) fn new(inner: S, policy: Policy) -> Self {
+        Self {
+            inner,
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<B, S> Service<http::Request<B>> for CorsService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = CorsFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let origin = request.headers().get(ORIGIN);
+        if let (Some(origin), Some(preflight)) = (origin, request.extensions().get::<CorsPreflight>()) {
+            return CorsFuture {
+                inner: Inner::Preflight {
+                    response: Some(self.policy.preflight(origin, preflight, request.headers())),
+                },
+            };
+        }
+
+        let allow_origin = origin.and_then(|origin| self.policy.allow_origin(origin));
+        CorsFuture {
+            inner: Inner::Actual {
+                inner: self.inner.call(request),
+                allow_origin,
+                policy: self.policy.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::Method;
+    use tower::util::ServiceFn;
+    use tower::{service_fn, Layer, ServiceExt};
+
+    use super::*;
+    use crate::cors::{CorsOperation, CorsPlugin};
+    use crate::operation::OperationShape;
+    use crate::plugin::{HttpPlugins, LayerPlugin, Plugin};
+    use crate::protocol::rest::router::RestRouter;
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::routing::request_spec::{PathSegment, RequestSpec};
+    use crate::routing::{Route, RoutingService};
+
+    const ORIGIN_A: &str = "https://a.example.com";
+    const ORIGIN_B: &str = "https://b.example.com";
+
+    struct GetItem;
+    impl OperationShape for GetItem {
+        const ID: ShapeId = ShapeId::new("namespace#GetItem", "namespace", "GetItem");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+    impl CorsOperation for GetItem {
+        const EXPOSED_HEADERS: &'static [&'static str] = &["x-item-version", "etag"];
+    }
+
+    struct PutItem;
+    impl OperationShape for PutItem {
+        const ID: ShapeId = ShapeId::new("namespace#PutItem", "namespace", "PutItem");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+    impl CorsOperation for PutItem {
+        const EXPOSED_HEADERS: &'static [&'static str] = &[];
+    }
+
+    type HandlerFn = fn(http::Request<()>) -> std::future::Ready<Result<http::Response<BoxBody>, Infallible>>;
+
+    fn handler(_request: http::Request<()>) -> std::future::Ready<Result<http::Response<BoxBody>, Infallible>> {
+        std::future::ready(Ok(http::Response::new(crate::body::empty())))
+    }
+
+    fn unauthorized(_request: http::Request<()>) -> std::future::Ready<Result<http::Response<BoxBody>, Infallible>> {
+        let mut response = http::Response::new(crate::body::empty());
+        *response.status_mut() = StatusCode::UNAUTHORIZED;
+        std::future::ready(Ok(response))
+    }
+
+    /// Rejects every request, as authentication would reject requests without credentials.
+    struct Unauthorized;
+    impl<S> Layer<S> for Unauthorized {
+        type Service = ServiceFn<HandlerFn>;
+
+        fn layer(&self, _inner: S) -> Self::Service {
+            service_fn(unauthorized)
+        }
+    }
+
+    fn spec(method: Method, operation: ShapeId) -> RequestSpec {
+        RequestSpec::from_parts(
+            method,
+            vec![PathSegment::Literal(String::from("items")), PathSegment::Label],
+            vec![],
+        )
+        .with_operation(operation)
+    }
+
+    fn routing_service(get: Route<()>, put: Route<()>) -> RoutingService<RestRouter<Route<()>>, RestJson1> {
+        RoutingService::new(
+            [
+                (spec(Method::GET, GetItem::ID), get),
+                (spec(Method::PUT, PutItem::ID), put),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+
+    fn router(config: CorsConfig) -> RoutingService<RestRouter<Route<()>>, RestJson1> {
+        let plugin = CorsPlugin::new(config);
+        let get = Plugin::<(), GetItem, _>::apply(&plugin, service_fn(handler));
+        let put = Plugin::<(), PutItem, _>::apply(&plugin, service_fn(handler));
+        routing_service(Route::new(get), Route::new(put))
+    }
+
+    fn request(method: Method, origin: Option<&'static str>) -> http::Request<()> {
+        let mut request = http::Request::builder().method(method).uri("/items/1");
+        if let Some(origin) = origin {
+            request = request.header(ORIGIN, origin);
+        }
+        request.body(()).unwrap()
+    }
+
+    fn preflight(method: &'static str, origin: &'static str) -> http::Request<()> {
+        let mut request = request(Method::OPTIONS, Some(origin));
+        let headers = request.headers_mut();
+        headers.insert(ACCESS_CONTROL_REQUEST_METHOD, HeaderValue::from_static(method));
+        headers.insert(ACCESS_CONTROL_REQUEST_HEADERS, HeaderValue::from_static("content-type"));
+        request
+    }
+
+    #[tokio::test]
+    async fn preflight_requests_are_answered_per_operation() {
+        let config = CorsConfig::new(AllowedOrigins::list([ORIGIN_A]))
+            .operation(PutItem::ID, AllowedOrigins::list([ORIGIN_B]))
+            .max_age(std::time::Duration::from_secs(600));
+        let svc = router(config);
+
+        let response = svc.clone().oneshot(preflight("GET", ORIGIN_A)).await.unwrap();
+        assert_eq!(StatusCode::NO_CONTENT, response.status());
+        let headers = response.headers();
+        assert_eq!(ORIGIN_A, headers[ACCESS_CONTROL_ALLOW_ORIGIN]);
+        // `PutItem` only allows `ORIGIN_B`.
+        assert_eq!("GET", headers[ACCESS_CONTROL_ALLOW_METHODS]);
+        assert_eq!("content-type", headers[ACCESS_CONTROL_ALLOW_HEADERS]);
+        assert_eq!("600", headers[ACCESS_CONTROL_MAX_AGE]);
+        assert!(headers.get_all(VARY).iter().any(|vary| vary == "origin"));
+        assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_CREDENTIALS));
+
+        let response = svc.clone().oneshot(preflight("PUT", ORIGIN_A)).await.unwrap();
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        let response = svc.clone().oneshot(preflight("PUT", ORIGIN_B)).await.unwrap();
+        assert_eq!(StatusCode::NO_CONTENT, response.status());
+        assert_eq!(ORIGIN_B, response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN]);
+        assert_eq!("PUT", response.headers()[ACCESS_CONTROL_ALLOW_METHODS]);
+
+        let svc = router(CorsConfig::new(AllowedOrigins::list([ORIGIN_A, ORIGIN_B])));
+        let response = svc.oneshot(preflight("PUT", ORIGIN_B)).await.unwrap();
+        assert_eq!("GET, PUT", response.headers()[ACCESS_CONTROL_ALLOW_METHODS]);
+
+        // No operation is routed to.
+        let svc = router(CorsConfig::new(AllowedOrigins::any()));
+        let response = svc.oneshot(preflight("DELETE", ORIGIN_A)).await.unwrap();
+        assert_eq!(StatusCode::METHOD_NOT_ALLOWED, response.status());
+    }
+
+    #[tokio::test]
+    async fn responses_expose_the_headers_of_the_operation() {
+        let svc = router(CorsConfig::new(AllowedOrigins::any()));
+
+        let response = svc.clone().oneshot(request(Method::GET, Some(ORIGIN_A))).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let headers = response.headers();
+        assert_eq!("*", headers[ACCESS_CONTROL_ALLOW_ORIGIN]);
+        assert_eq!("x-item-version, etag", headers[ACCESS_CONTROL_EXPOSE_HEADERS]);
+        assert!(!headers.contains_key(VARY));
+
+        let response = svc.clone().oneshot(request(Method::PUT, Some(ORIGIN_A))).await.unwrap();
+        assert_eq!("*", response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN]);
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_EXPOSE_HEADERS));
+
+        // Same-origin requests don't have an `Origin` header.
+        let response = svc.oneshot(request(Method::GET, None)).await.unwrap();
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+
+    #[tokio::test]
+    async fn credentials_require_the_request_origin() {
+        let svc = router(CorsConfig::new(AllowedOrigins::any()).allow_credentials(true));
+
+        let response = svc.clone().oneshot(request(Method::GET, Some(ORIGIN_A))).await.unwrap();
+        let headers = response.headers();
+        assert_eq!(ORIGIN_A, headers[ACCESS_CONTROL_ALLOW_ORIGIN]);
+        assert_eq!("true", headers[ACCESS_CONTROL_ALLOW_CREDENTIALS]);
+        assert_eq!("origin", headers[VARY]);
+
+        let svc = router(CorsConfig::new(AllowedOrigins::none()));
+        let response = svc.oneshot(request(Method::GET, Some(ORIGIN_A))).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+
+    #[tokio::test]
+    async fn plugins_registered_after_the_cors_plugin_dont_see_preflight_requests() {
+        let config = CorsConfig::new(AllowedOrigins::any());
+
+        let plugins = HttpPlugins::new()
+            .push(CorsPlugin::new(config.clone()))
+            .push(LayerPlugin(Unauthorized));
+        let get = Plugin::<(), GetItem, _>::apply(&plugins, service_fn(handler));
+        let put = Plugin::<(), PutItem, _>::apply(&plugins, service_fn(handler));
+        let svc = routing_service(Route::new(get), Route::new(put));
+        let response = svc.clone().oneshot(preflight("GET", ORIGIN_A)).await.unwrap();
+        assert_eq!(StatusCode::NO_CONTENT, response.status());
+        assert_eq!("GET, PUT", response.headers()[ACCESS_CONTROL_ALLOW_METHODS]);
+        // The actual request still goes through every plugin, and its response is decorated.
+        let response = svc.oneshot(request(Method::GET, Some(ORIGIN_A))).await.unwrap();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+        assert_eq!("*", response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN]);
+
+        // Plugins registered before the `CorsPlugin` reject preflight requests, which browsers send
+        // without credentials.
+        let plugins = HttpPlugins::new()
+            .push(LayerPlugin(Unauthorized))
+            .push(CorsPlugin::new(config));
+        let get = Plugin::<(), GetItem, _>::apply(&plugins, service_fn(handler));
+        let put = Plugin::<(), PutItem, _>::apply(&plugins, service_fn(handler));
+        let svc = routing_service(Route::new(get), Route::new(put));
+        let response = svc.oneshot(preflight("GET", ORIGIN_A)).await.unwrap();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+}
@@ -0,0 +1,664 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Middleware for request decompression and response compression.
+//!
+//! [`CompressionLayer`] decodes request bodies sent with a supported `Content-Encoding` before they
+//! reach the protocol deserializer, which is what operations annotated with
+//! [`@requestCompression`](https://smithy.io/2.0/spec/behavior-traits.html#requestcompression-trait)
+//! require. It also compresses response bodies with the most preferred algorithm the client lists
+//! in its `Accept-Encoding` header.
+//!
+//! The algorithms are provided by [`aws_smithy_compression`]. Decompressed request bodies are capped
+//! at a configurable size to guard against compression bombs: the protocol deserializer rejects
+//! requests that inflate past it with the protocol's `413 Payload Too Large` error. Requests
+//! compressed with an unsupported algorithm are rejected with the protocol's
+//! `415 Unsupported Media Type` error, which is why the layer is parameterized by the protocol.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::layer::compression::CompressionLayer;
+//! use aws_smithy_http_server::protocol::rest_json_1::RestJson1;
+//! use tower::Layer;
+//!
+//! // Reject requests that inflate to more than 1 MiB.
+//! let compression_layer = CompressionLayer::<RestJson1>::new().with_max_decompressed_size_bytes(1024 * 1024);
+//! # async fn handle() { }
+//! let app = tower::service_fn(handle);
+//! let app = compression_layer.layer(app);
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use aws_smithy_compression::{
+    CompressStream, CompressionAlgorithm, CompressionOptions, DecompressStream, DecompressedSizeLimitExceeded,
+};
+use bytes::Bytes;
+use futures_util::future::{Either, Ready};
+use futures_util::Future;
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, VARY};
+use http::{HeaderValue, Request, Response};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use tower::{Layer, Service};
+
+use crate::body::{BodyLimitExceeded, BoxBody};
+use crate::error::BoxError;
+use crate::response::IntoResponse;
+use crate::runtime_error::UnsupportedMediaTypeException;
+
+/// The default maximum size, in bytes, that a request body may decompress to.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+const EVENT_STREAM_CONTENT_TYPE: &str = "application/vnd.amazon.eventstream";
+
+/// A [`tower::Layer`] used to apply [`CompressionService`] to a service of protocol `P`.
+pub struct CompressionLayer<P> {
+    algorithms: Vec<CompressionAlgorithm>,
+    compression_options: CompressionOptions,
+    max_decompressed_size_bytes: usize,
+    decompress_requests: bool,
+    compress_responses: bool,
+    _protocol: PhantomData<fn(P)>,
+}
+
+impl<P> Default for CompressionLayer<P> {
+    fn default() -> Self {
+        Self {
+            algorithms: CompressionAlgorithm::supported().to_vec(),
+            compression_options: CompressionOptions::default(),
+            max_decompressed_size_bytes: DEFAULT_MAX_DECOMPRESSED_SIZE_BYTES,
+            decompress_requests: true,
+            compress_responses: true,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P> Clone for CompressionLayer<P> {
+    fn clone(&self) -> Self {
+        Self {
+            algorithms: self.algorithms.clone(),
+            compression_options: self.compression_options.clone(),
+            max_decompressed_size_bytes: self.max_decompressed_size_bytes,
+            decompress_requests: self.decompress_requests,
+            compress_responses: self.compress_responses,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for CompressionLayer<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressionLayer")
+            .field("algorithms", &self.algorithms)
+            .field("compression_options", &self.compression_options)
+            .field("max_decompressed_size_bytes", &self.max_decompressed_size_bytes)
+            .field("decompress_requests", &self.decompress_requests)
+            .field("compress_responses", &self.compress_responses)
+            .finish()
+    }
+}
+
+impl<P> CompressionLayer<P> {
+    /// Create a new `CompressionLayer` that decompresses requests and compresses responses with every
+    /// algorithm supported by the enabled `aws-smithy-compression` features.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only use the given algorithms, in order of preference when compressing responses.
+    pub fn with_algorithms(mut self, algorithms: impl IntoIterator<Item = CompressionAlgorithm>) -> Self {
+        self.algorithms = algorithms.into_iter().collect();
+        self
+    }
+
+    /// Set the compression level and minimum response size used when compressing responses.
+    ///
+    /// Responses whose size is known and smaller than
+    /// [`min_compression_size_bytes`](CompressionOptions::min_compression_size_bytes) aren't compressed.
+    pub fn with_compression_options(mut self, compression_options: CompressionOptions) -> Self {
+        self.compression_options = compression_options;
+        self
+    }
+
+    /// Set the maximum size, in bytes, that a request body may decompress to.
+    ///
+    /// Reading a request body past this size fails with a [`BodyLimitExceeded`] error, which the
+    /// protocol deserializer turns into a `413 Payload Too Large` response. The default is
+    /// [`DEFAULT_MAX_DECOMPRESSED_SIZE_BYTES`].
+    pub fn with_max_decompressed_size_bytes(mut self, max_decompressed_size_bytes: usize) -> Self {
+        self.max_decompressed_size_bytes = max_decompressed_size_bytes;
+        self
+    }
+
+    /// Set whether compressed request bodies are decompressed.
+    pub fn with_request_decompression(mut self, enabled: bool) -> Self {
+        self.decompress_requests = enabled;
+        self
+    }
+
+    /// Set whether response bodies are compressed.
+    pub fn with_response_compression(mut self, enabled: bool) -> Self {
+        self.compress_responses = enabled;
+        self
+    }
+
+    /// Returns the algorithm a request body was compressed with, `Ok(None)` if it wasn't compressed,
+    /// or `Err(())` if it was compressed with an unsupported algorithm.
+    fn request_algorithm(&self, content_encoding: Option<&HeaderValue>) -> Result<Option<CompressionAlgorithm>, ()> {
+        let Some(content_encoding) = content_encoding else {
+            return Ok(None);
+        };
+        let content_encoding = content_encoding.to_str().map_err(|_| ())?.trim();
+        if content_encoding.is_empty() || content_encoding.eq_ignore_ascii_case("identity") {
+            return Ok(None);
+        }
+        match content_encoding.parse::<CompressionAlgorithm>() {
+            Ok(algorithm) if self.algorithms.contains(&algorithm) => Ok(Some(algorithm)),
+            _ => Err(()),
+        }
+    }
+
+    /// Returns the most preferred algorithm that the client accepts, if any.
+    fn response_algorithm(&self, accept_encoding: Option<&HeaderValue>) -> Option<CompressionAlgorithm> {
+        let accepted: Vec<(&str, bool)> = accept_encoding?
+            .to_str()
+            .ok()?
+            .split(',')
+            .filter_map(|coding| {
+                let mut parts = coding.split(';');
+                let name = parts.next()?.trim();
+                // A quality value of `0` means "not acceptable".
+                let acceptable = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .all(|q| q.trim().parse::<f32>().map(|q| q > 0.0).unwrap_or(false));
+                Some((name, acceptable))
+            })
+            .collect();
+
+        self.algorithms.iter().copied().find(|algorithm| {
+            let explicit = accepted
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(algorithm.as_str()));
+            match explicit {
+                Some((_, acceptable)) => *acceptable,
+                None => accepted.iter().any(|(name, acceptable)| *name == "*" && *acceptable),
+            }
+        })
+    }
+}
+
+impl<S, P> Layer<S> for CompressionLayer<P> {
+    type Service = CompressionService<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CompressionService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// A middleware [`Service`] responsible for decompressing requests and compressing responses.
+pub struct CompressionService<S, P> {
+    inner: S,
+    layer: CompressionLayer<P>,
+}
+
+impl<S: Clone, P> Clone for CompressionService<S, P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            layer: self.layer.clone(),
+        }
+    }
+}
+
+impl<S: fmt::Debug, P> fmt::Debug for CompressionService<S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressionService")
+            .field("inner", &self.inner)
+            .field("layer", &self.layer)
+            .finish()
+    }
+}
+
+impl<B, S, P> Service<Request<B>> for CompressionService<S, P>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+    S: Service<Request<DecompressionBody<B>>, Response = Response<BoxBody>>,
+    UnsupportedMediaTypeException: IntoResponse<P>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response<BoxBody>, S::Error>>, CompressionFuture<S::Future>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let response_algorithm = if self.layer.compress_responses {
+            self.layer.response_algorithm(req.headers().get(ACCEPT_ENCODING))
+        } else {
+            None
+        };
+
+        let (mut parts, body) = req.into_parts();
+        let request_algorithm = if self.layer.decompress_requests {
+            match self.layer.request_algorithm(parts.headers.get(CONTENT_ENCODING)) {
+                Ok(algorithm) => algorithm,
+                Err(()) => {
+                    tracing::debug!(
+                        content_encoding = ?parts.headers.get(CONTENT_ENCODING),
+                        "request content-encoding is not supported"
+                    );
+                    return Either::Left(futures_util::future::ready(Ok(
+                        UnsupportedMediaTypeException.into_response()
+                    )));
+                }
+            }
+        } else {
+            None
+        };
+
+        let body = match request_algorithm {
+            Some(algorithm) => {
+                tracing::trace!(content_encoding = algorithm.as_str(), "decompressing request body");
+                // The deserializer sees the decompressed body, so these headers no longer describe it.
+                parts.headers.remove(CONTENT_ENCODING);
+                parts.headers.remove(CONTENT_LENGTH);
+                DecompressionBody::decompress(
+                    body,
+                    algorithm.stream_decompressor_with_limit(self.layer.max_decompressed_size_bytes),
+                )
+            }
+            None => DecompressionBody::passthrough(body),
+        };
+
+        Either::Right(CompressionFuture {
+            inner: self.inner.call(Request::from_parts(parts, body)),
+            response_algorithm,
+            compression_options: self.layer.compression_options.clone(),
+        })
+    }
+}
+
+pin_project! {
+    /// Future for [`CompressionService`].
+    pub struct CompressionFuture<F> {
+        #[pin]
+        inner: F,
+        response_algorithm: Option<CompressionAlgorithm>,
+        compression_options: CompressionOptions,
+    }
+}
+
+impl<F, E> Future for CompressionFuture<F>
+where
+    F: Future<Output = Result<Response<BoxBody>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let response = ready!(this.inner.poll(cx))?;
+        Poll::Ready(Ok(match this.response_algorithm.take() {
+            Some(algorithm) => compress_response(response, algorithm, this.compression_options),
+            None => response,
+        }))
+    }
+}
+
+fn compress_response(
+    response: Response<BoxBody>,
+    algorithm: CompressionAlgorithm,
+    options: &CompressionOptions,
+) -> Response<BoxBody> {
+    let headers = response.headers();
+    let is_event_stream = headers.get(CONTENT_TYPE).is_some_and(|content_type| {
+        content_type
+            .as_bytes()
+            .starts_with(EVENT_STREAM_CONTENT_TYPE.as_bytes())
+    });
+    let is_too_small = response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size < options.min_compression_size_bytes() as u64);
+    // Event streams are left alone since compressors buffer data, which would delay events.
+    if headers.contains_key(CONTENT_ENCODING) || is_event_stream || is_too_small || response.body().is_end_stream() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts
+        .headers
+        .insert(CONTENT_ENCODING, HeaderValue::from_static(algorithm.as_str()));
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.headers.append(VARY, HeaderValue::from_static("accept-encoding"));
+    let compress_stream = algorithm.into_impl_http_body_1_x(options).stream_compressor();
+    Response::from_parts(parts, crate::body::boxed(CompressionBody::new(body, compress_stream)))
+}
+
+/// Reports a decompressed body exceeding its maximum size as a [`BodyLimitExceeded`], which the
+/// protocol deserializers reject with a `413 Payload Too Large` error.
+fn decompress_error(err: BoxError) -> BoxError {
+    match err.downcast_ref::<DecompressedSizeLimitExceeded>() {
+        Some(exceeded) => Box::new(BodyLimitExceeded {
+            limit: exceeded.limit(),
+        }),
+        None => err,
+    }
+}
+
+pin_project! {
+    /// A request body that's decompressed as it's read, if it was sent compressed.
+    pub struct DecompressionBody<B> {
+        #[pin]
+        body: B,
+        decompress_stream: Option<Box<dyn DecompressStream>>,
+        trailers: Option<http::HeaderMap>,
+        is_end_stream: bool,
+    }
+}
+
+impl<B> DecompressionBody<B> {
+    fn decompress(body: B, decompress_stream: Box<dyn DecompressStream>) -> Self {
+        Self {
+            body,
+            decompress_stream: Some(decompress_stream),
+            trailers: None,
+            is_end_stream: false,
+        }
+    }
+
+    fn passthrough(body: B) -> Self {
+        Self {
+            body,
+            decompress_stream: None,
+            trailers: None,
+            is_end_stream: false,
+        }
+    }
+}
+
+impl<B> fmt::Debug for DecompressionBody<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecompressionBody")
+            .field("is_decompressing", &self.decompress_stream.is_some())
+            .finish()
+    }
+}
+
+impl<B> Body for DecompressionBody<B>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let Some(decompress_stream) = this.decompress_stream.as_mut() else {
+            return this.body.poll_frame(cx).map_err(Into::into);
+        };
+        loop {
+            // Trailers are held back until the decompressed stream has been finished.
+            if let Some(trailers) = this.trailers.take() {
+                return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+            }
+            if *this.is_end_stream {
+                return Poll::Ready(None);
+            }
+
+            let decompressed = match ready!(this.body.as_mut().poll_frame(cx)) {
+                // The decoder fails as soon as its output exceeds the maximum decompressed size.
+                Some(Ok(f)) => match f.into_data() {
+                    Ok(data) => decompress_stream.decompress_chunk(&data).map_err(decompress_error)?,
+                    Err(f) => {
+                        *this.trailers = f.into_trailers().ok();
+                        decompress_stream.finish().map_err(decompress_error)?
+                    }
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                None => {
+                    *this.is_end_stream = true;
+                    decompress_stream.finish().map_err(decompress_error)?
+                }
+            };
+            if !decompressed.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::data(decompressed))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match self.decompress_stream {
+            Some(_) => self.is_end_stream,
+            None => self.body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.decompress_stream {
+            // We can't know how large the decompressed data will be.
+            Some(_) => SizeHint::default(),
+            None => self.body.size_hint(),
+        }
+    }
+}
+
+pin_project! {
+    /// A response body that's compressed as it's written.
+    struct CompressionBody<B> {
+        #[pin]
+        body: B,
+        compress_stream: Box<dyn CompressStream>,
+        trailers: Option<http::HeaderMap>,
+        is_end_stream: bool,
+    }
+}
+
+impl<B> CompressionBody<B> {
+    fn new(body: B, compress_stream: Box<dyn CompressStream>) -> Self {
+        Self {
+            body,
+            compress_stream,
+            trailers: None,
+            is_end_stream: false,
+        }
+    }
+}
+
+impl<B> Body for CompressionBody<B>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            if let Some(trailers) = this.trailers.take() {
+                return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+            }
+            if *this.is_end_stream {
+                return Poll::Ready(None);
+            }
+
+            let compressed = match ready!(this.body.as_mut().poll_frame(cx)) {
+                Some(Ok(f)) => match f.into_data() {
+                    Ok(data) => this.compress_stream.compress_chunk(&data)?,
+                    Err(f) => {
+                        *this.trailers = f.into_trailers().ok();
+                        this.compress_stream.finish()?
+                    }
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                None => {
+                    *this.is_end_stream = true;
+                    this.compress_stream.finish()?
+                }
+            };
+            if !compressed.is_empty() {
+                return Poll::Ready(Some(Ok(Frame::data(compressed))));
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.is_end_stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::{collect_body_limited, CollectBodyError};
+    use crate::protocol::rest_json_1::rejection::RequestRejection;
+    use crate::protocol::rest_json_1::runtime_error::RuntimeError;
+    use crate::protocol::rest_json_1::RestJson1;
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use http::StatusCode;
+    use http_body_util::BodyExt;
+    use std::convert::Infallible;
+    use std::io::{Read, Write};
+    use tower::{service_fn, ServiceExt};
+
+    fn gzip(data: &[u8]) -> Bytes {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap().into()
+    }
+
+    fn echo_service(
+    ) -> impl Service<Request<DecompressionBody<BoxBody>>, Response = Response<BoxBody>, Error = Infallible> + Clone
+    {
+        service_fn(|req: Request<DecompressionBody<BoxBody>>| async move {
+            assert!(req.headers().get(CONTENT_ENCODING).is_none());
+            // Collect the body like the generated deserializers do.
+            let body = match collect_body_limited(req.into_body(), 0).await {
+                Ok(body) => body,
+                Err(CollectBodyError::Body(err)) => {
+                    let rejection = RequestRejection::from(err);
+                    return Ok(IntoResponse::<RestJson1>::into_response(RuntimeError::from(rejection)));
+                }
+                Err(CollectBodyError::TooLarge(_)) => unreachable!("the limit is disabled"),
+            };
+            Ok::<_, Infallible>(Response::new(crate::body::from_bytes(body)))
+        })
+    }
+
+    fn large_payload() -> Vec<u8> {
+        b"all work and no play makes jack a dull boy\n".repeat(1000)
+    }
+
+    #[tokio::test]
+    async fn compressed_request_is_decompressed() {
+        let service = CompressionLayer::<RestJson1>::new().layer(echo_service());
+        let request = Request::builder()
+            .header(CONTENT_ENCODING, "gzip")
+            .body(crate::body::from_bytes(gzip(b"hello world")))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, Bytes::from_static(b"hello world"));
+    }
+
+    #[tokio::test]
+    async fn unsupported_content_encoding_is_rejected() {
+        let service = CompressionLayer::<RestJson1>::new().layer(echo_service());
+        let request = Request::builder()
+            .header(CONTENT_ENCODING, "compress")
+            .body(crate::body::from_bytes(Bytes::from_static(b"???")))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.headers()["X-Amzn-Errortype"], "UnsupportedMediaTypeException");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+    }
+
+    #[tokio::test]
+    async fn compression_bomb_is_rejected() {
+        let service = CompressionLayer::<RestJson1>::new()
+            .with_max_decompressed_size_bytes(1024)
+            .layer(echo_service());
+        let bomb = gzip(&vec![0u8; 10 * 1024 * 1024]);
+        let request = Request::builder()
+            .header(CONTENT_ENCODING, "gzip")
+            .body(crate::body::from_bytes(bomb))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.headers()["X-Amzn-Errortype"], "PayloadTooLargeException");
+    }
+
+    #[tokio::test]
+    async fn response_is_compressed_when_accepted() {
+        let service = CompressionLayer::<RestJson1>::new().layer(echo_service());
+        let request = Request::builder()
+            .header(ACCEPT_ENCODING, "br;q=0, gzip;q=0.8, deflate")
+            .body(crate::body::from_bytes(large_payload().into()))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(response.headers().get(VARY).unwrap(), "accept-encoding");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let mut decompressed = Vec::new();
+        GzDecoder::new(&body[..]).read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, large_payload());
+    }
+
+    #[tokio::test]
+    async fn small_response_is_not_compressed() {
+        let service = CompressionLayer::<RestJson1>::new().layer(echo_service());
+        let request = Request::builder()
+            .header(ACCEPT_ENCODING, "gzip")
+            .body(crate::body::from_bytes(Bytes::from_static(b"tiny")))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+    }
+
+    #[tokio::test]
+    async fn response_is_not_compressed_when_not_accepted() {
+        let service = CompressionLayer::<RestJson1>::new().layer(echo_service());
+        let request = Request::builder()
+            .header(ACCEPT_ENCODING, "gzip;q=0, identity")
+            .body(crate::body::from_bytes(large_payload().into()))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+    }
+
+    #[test]
+    fn wildcard_accept_encoding_selects_preferred_algorithm() {
+        let layer = CompressionLayer::<RestJson1>::new();
+        assert_eq!(
+            Some(CompressionAlgorithm::Gzip),
+            layer.response_algorithm(Some(&HeaderValue::from_static("*")))
+        );
+        assert_eq!(None, layer.response_algorithm(Some(&HeaderValue::from_static("*;q=0"))));
+        assert_eq!(None, layer.response_algorithm(None));
+    }
+}
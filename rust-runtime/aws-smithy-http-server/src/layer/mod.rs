@@ -7,3 +7,6 @@
 //! [`Router`](crate::routing::Router), so they are enacted before a request is routed.
 
 pub mod alb_health_check;
+#[cfg(feature = "compression")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+pub mod compression;
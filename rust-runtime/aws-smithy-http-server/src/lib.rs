@@ -15,11 +15,13 @@
 pub(crate) mod macros;
 
 pub mod body;
+pub mod cors;
 #[doc(hidden)]
 pub mod error;
 pub mod extension;
 pub mod instrumentation;
 pub mod layer;
+pub mod limit;
 pub mod operation;
 pub mod plugin;
 #[doc(hidden)]
@@ -35,6 +37,7 @@ pub mod runtime_error;
 pub mod serve;
 pub mod service;
 pub mod shape_id;
+pub mod timeout;
 
 #[doc(inline)]
 pub(crate) use self::error::Error;
@@ -0,0 +1,262 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use tokio::time::Instant;
+
+use crate::shape_id::ShapeId;
+
+use super::limits::{CallerKeyFn, OperationLimit, RateLimit};
+
+/// The minimum number of callers tracked before idle callers are evicted.
+const MIN_SWEEP_THRESHOLD: usize = 1024;
+
+/// Tracks the requests admitted to an operation, for each caller.
+pub(crate) struct Limiter {
+    operation: ShapeId,
+    limit: OperationLimit,
+    caller_key: Option<CallerKeyFn>,
+    state: Mutex<State>,
+}
+
+struct State {
+    callers: HashMap<Option<String>, Caller>,
+    /// Idle callers are evicted once more than this many callers are tracked.
+    sweep_threshold: usize,
+}
+
+struct Caller {
+    tokens: f64,
+    refilled_at: Instant,
+    in_flight: usize,
+}
+
+impl Caller {
+    fn new(rate_limit: Option<RateLimit>, now: Instant) -> Self {
+        Self {
+            tokens: rate_limit.map(|rate_limit| rate_limit.capacity()).unwrap_or_default(),
+            refilled_at: now,
+            in_flight: 0,
+        }
+    }
+
+    fn refill(&mut self, rate_limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate_limit.tokens_per_second()).min(rate_limit.capacity());
+        self.refilled_at = now;
+    }
+
+    /// Admits a request if neither limit is reached, returning whether it was admitted.
+    fn try_admit(&mut self, limit: &OperationLimit, now: Instant) -> bool {
+        if let Some(max_in_flight) = limit.max_in_flight {
+            if self.in_flight >= max_in_flight {
+                return false;
+            }
+        }
+        if let Some(rate_limit) = &limit.rate_limit {
+            self.refill(rate_limit, now);
+            if self.tokens < 1.0 {
+                return false;
+            }
+            self.tokens -= 1.0;
+        }
+        self.in_flight += 1;
+        true
+    }
+
+    /// Returns true if forgetting the caller doesn't change the requests it's admitted.
+    fn is_idle(&mut self, rate_limit: Option<&RateLimit>, now: Instant) -> bool {
+        if self.in_flight > 0 {
+            return false;
+        }
+        match rate_limit {
+            Some(rate_limit) => {
+                self.refill(rate_limit, now);
+                self.tokens >= rate_limit.capacity()
+            }
+            None => true,
+        }
+    }
+}
+
+impl Limiter {
+    pub(crate) fn new(operation: ShapeId, limit: OperationLimit, caller_key: Option<CallerKeyFn>) -> Self {
+        Self {
+            operation,
+            limit,
+            caller_key,
+            state: Mutex::new(State {
+                callers: HashMap::new(),
+                sweep_threshold: MIN_SWEEP_THRESHOLD,
+            }),
+        }
+    }
+
+    pub(crate) fn operation(&self) -> &ShapeId {
+        &self.operation
+    }
+
+    /// Admits the request with `parts`, returning `None` if a limit is reached.
+    ///
+    /// The request is in flight until the returned [`Permit`] is dropped.
+    pub(crate) fn try_acquire(self: &Arc<Self>, parts: &http::request::Parts) -> Option<Permit> {
+        if self.limit.is_unlimited() {
+            return Some(Permit { release: None });
+        }
+
+        let key = self.caller_key.as_ref().and_then(|caller_key| caller_key(parts));
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let admitted = match state.callers.get_mut(&key) {
+            Some(caller) => caller.try_admit(&self.limit, now),
+            None => {
+                // New callers are only tracked once admitted, so rejected callers don't pile up.
+                let mut caller = Caller::new(self.limit.rate_limit, now);
+                let admitted = caller.try_admit(&self.limit, now);
+                if admitted {
+                    state.callers.insert(key.clone(), caller);
+                }
+                admitted
+            }
+        };
+        if !admitted {
+            return None;
+        }
+
+        if state.callers.len() > state.sweep_threshold {
+            let rate_limit = self.limit.rate_limit.as_ref();
+            state.callers.retain(|_, caller| !caller.is_idle(rate_limit, now));
+            state.sweep_threshold = MIN_SWEEP_THRESHOLD.max(2 * state.callers.len());
+        }
+
+        Some(Permit {
+            release: Some((self.clone(), key)),
+        })
+    }
+
+    fn release(&self, key: Option<String>) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(caller) = state.callers.get_mut(&key) {
+            caller.in_flight -= 1;
+            if caller.in_flight == 0 && self.limit.rate_limit.is_none() {
+                state.callers.remove(&key);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn tracked_callers(&self) -> usize {
+        self.state.lock().unwrap().callers.len()
+    }
+}
+
+impl fmt::Debug for Limiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Limiter")
+            .field("operation", &self.operation)
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request admitted by a [`Limiter`], which is in flight until dropped.
+#[derive(Debug)]
+pub(crate) struct Permit {
+    release: Option<(Arc<Limiter>, Option<String>)>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some((limiter, key)) = self.release.take() {
+            limiter.release(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const ID: ShapeId = ShapeId::new("namespace#Operation", "namespace", "Operation");
+
+    fn parts(caller: &str) -> http::request::Parts {
+        let (parts, _) = http::Request::get("/")
+            .header("caller", caller)
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts
+    }
+
+    fn limiter(limit: OperationLimit) -> Arc<Limiter> {
+        let caller_key: CallerKeyFn =
+            Arc::new(|parts: &http::request::Parts| Some(parts.headers.get("caller")?.to_str().ok()?.to_owned()));
+        Arc::new(Limiter::new(ID, limit, Some(caller_key)))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limit_refills_over_time() {
+        let limiter = limiter(OperationLimit::new().rate_limit(RateLimit::new(2, Duration::from_secs(1))));
+        let a = parts("a");
+
+        assert!(limiter.try_acquire(&a).is_some());
+        assert!(limiter.try_acquire(&a).is_some());
+        assert!(limiter.try_acquire(&a).is_none());
+        // Callers are limited separately.
+        assert!(limiter.try_acquire(&parts("b")).is_some());
+
+        tokio::time::advance(Duration::from_millis(500)).await;
+        assert!(limiter.try_acquire(&a).is_some());
+        assert!(limiter.try_acquire(&a).is_none());
+
+        // The bucket doesn't refill beyond its burst.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert!(limiter.try_acquire(&a).is_some());
+        assert!(limiter.try_acquire(&a).is_some());
+        assert!(limiter.try_acquire(&a).is_none());
+    }
+
+    #[tokio::test]
+    async fn max_in_flight_is_released_on_drop() {
+        let limiter = limiter(OperationLimit::new().max_in_flight(1));
+        let a = parts("a");
+
+        let permit = limiter.try_acquire(&a).unwrap();
+        assert!(limiter.try_acquire(&a).is_none());
+        assert!(limiter.try_acquire(&parts("b")).is_some());
+        drop(permit);
+        assert!(limiter.try_acquire(&a).is_some());
+        assert_eq!(0, limiter.tracked_callers());
+    }
+
+    #[tokio::test]
+    async fn rejected_callers_are_not_tracked() {
+        let limiter = limiter(OperationLimit::new().max_in_flight(0));
+        for caller in 0..10 {
+            assert!(limiter.try_acquire(&parts(&caller.to_string())).is_none());
+        }
+        assert_eq!(0, limiter.tracked_callers());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_callers_are_evicted() {
+        let limiter = limiter(OperationLimit::new().rate_limit(RateLimit::new(1, Duration::from_secs(1))));
+        for caller in 0..MIN_SWEEP_THRESHOLD {
+            assert!(limiter.try_acquire(&parts(&caller.to_string())).is_some());
+        }
+        assert_eq!(MIN_SWEEP_THRESHOLD, limiter.tracked_callers());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let a = parts("a");
+        assert!(limiter.try_acquire(&a).is_some());
+        assert_eq!(1, limiter.tracked_callers());
+        assert!(limiter.try_acquire(&a).is_none());
+    }
+}
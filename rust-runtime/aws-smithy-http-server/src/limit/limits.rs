@@ -0,0 +1,149 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::shape_id::ShapeId;
+
+/// A token-bucket rate limit.
+///
+/// The bucket holds up to [`burst`](RateLimit::burst) tokens, and is refilled at a rate of
+/// `requests` tokens per `period`. Each request takes a token, and is rejected if the bucket is empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    burst: u32,
+    tokens_per_second: f64,
+}
+
+impl RateLimit {
+    /// Allows `requests` requests per `period`, which are also allowed in a single burst.
+    ///
+    /// # Panics
+    /// This function panics if `requests` is zero or `period` is zero.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        assert!(requests > 0, "a rate limit must allow at least one request");
+        assert!(!period.is_zero(), "the period of a rate limit must not be zero");
+        Self {
+            burst: requests,
+            tokens_per_second: f64::from(requests) / period.as_secs_f64(),
+        }
+    }
+
+    /// Sets the maximum number of requests allowed in a single burst.
+    ///
+    /// # Panics
+    /// This function panics if `burst` is zero.
+    pub fn burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "a rate limit must allow bursts of at least one request");
+        self.burst = burst;
+        self
+    }
+
+    pub(crate) fn capacity(&self) -> f64 {
+        f64::from(self.burst)
+    }
+
+    pub(crate) fn tokens_per_second(&self) -> f64 {
+        self.tokens_per_second
+    }
+}
+
+/// The limits applied to the requests made to an operation.
+///
+/// An `OperationLimit` without any limit admits every request.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OperationLimit {
+    pub(crate) rate_limit: Option<RateLimit>,
+    pub(crate) max_in_flight: Option<usize>,
+}
+
+impl OperationLimit {
+    /// Creates an `OperationLimit` without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the rate of requests.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Limits the number of requests in flight, i.e. which have been admitted but whose response
+    /// hasn't been produced yet.
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.rate_limit.is_none() && self.max_in_flight.is_none()
+    }
+}
+
+pub(crate) type CallerKeyFn = Arc<dyn Fn(&http::request::Parts) -> Option<String> + Send + Sync>;
+
+/// The [`OperationLimit`]s applied by a [`LimitPlugin`](super::LimitPlugin), keyed by operation
+/// [`ShapeId`].
+#[derive(Clone, Default)]
+pub struct Limits {
+    operations: HashMap<ShapeId, OperationLimit>,
+    default: Option<OperationLimit>,
+    pub(crate) caller_key: Option<CallerKeyFn>,
+}
+
+impl Limits {
+    /// Creates a `Limits` admitting every request.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `limit` to the operation identified by `operation`.
+    pub fn operation(mut self, operation: ShapeId, limit: OperationLimit) -> Self {
+        self.operations.insert(operation, limit);
+        self
+    }
+
+    /// Applies `limit` to the operations without an [`operation`](Limits::operation) limit.
+    pub fn default_limit(mut self, limit: OperationLimit) -> Self {
+        self.default = Some(limit);
+        self
+    }
+
+    /// Applies the limits of each operation to each caller separately, rather than to all callers
+    /// together.
+    ///
+    /// `caller_key` extracts the key identifying the caller from the request, e.g. from a header
+    /// or from the [`ConnectInfo`](crate::request::connect_info::ConnectInfo) extension. Requests
+    /// from which no key is extracted share the same limits.
+    pub fn caller_key<F>(mut self, caller_key: F) -> Self
+    where
+        F: Fn(&http::request::Parts) -> Option<String> + Send + Sync + 'static,
+    {
+        self.caller_key = Some(Arc::new(caller_key));
+        self
+    }
+
+    pub(crate) fn get(&self, operation: &ShapeId) -> OperationLimit {
+        self.operations
+            .get(operation)
+            .or(self.default.as_ref())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for Limits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Limits")
+            .field("operations", &self.operations)
+            .field("default", &self.default)
+            .field("caller_key", &self.caller_key.as_ref().map(|_| "<function>"))
+            .finish()
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+//! Provides [`LimitPlugin`], an HTTP plugin for operation-level admission control.
+//!
+//! Where [`ListenerExt::limit_connections`](crate::serve::ListenerExt::limit_connections) caps the
+//! number of connections a server accepts, [`LimitPlugin`] caps the requests made to each
+//! operation, with:
+//!
+//! - a token-bucket [`RateLimit`], bounding the rate of requests; and
+//! - a maximum number of requests in flight, bounding the concurrency of requests.
+//!
+//! Limits are configured per operation [`ShapeId`](crate::shape_id::ShapeId). When a caller key
+//! is set with [`Limits::caller_key`], each caller of an operation has its own limits. Requests
+//! exceeding a limit are rejected, before being deserialized, with the throttling error of the
+//! service's protocol: a `ThrottlingException` with a `429 Too Many Requests` status code.
+//!
+//! # Example
+//!
+//! ```
+//! # use aws_smithy_http_server::shape_id::ShapeId;
+//! # struct GetPokemonSpecies;
+//! # impl GetPokemonSpecies { const ID: ShapeId = ShapeId::new("namespace#GetPokemonSpecies", "namespace", "GetPokemonSpecies"); }
+//! use aws_smithy_http_server::limit::{LimitExt, Limits, OperationLimit, RateLimit};
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! use std::time::Duration;
+//!
+//! let limits = Limits::new()
+//!     // At most 100 requests per second, and 10 requests in flight, to `GetPokemonSpecies`.
+//!     .operation(
+//!         GetPokemonSpecies::ID,
+//!         OperationLimit::new()
+//!             .rate_limit(RateLimit::new(100, Duration::from_secs(1)))
+//!             .max_in_flight(10),
+//!     )
+//!     // At most 50 requests in flight to every other operation.
+//!     .default_limit(OperationLimit::new().max_in_flight(50))
+//!     // Apply the limits to each API key separately.
+//!     .caller_key(|parts: &http::request::Parts| {
+//!         parts.headers.get("x-api-key")?.to_str().ok().map(str::to_owned)
+//!     });
+//!
+//! let http_plugins = HttpPlugins::new().limit(limits);
+//! ```
+
+mod limiter;
+mod limits;
+mod plugin;
+mod service;
+
+pub use limits::*;
+pub use plugin::*;
+pub use service::*;
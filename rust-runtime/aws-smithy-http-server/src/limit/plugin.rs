@@ -0,0 +1,56 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, HttpPlugins, Plugin, PluginStack};
+use crate::service::ServiceShape;
+
+use super::limiter::Limiter;
+use super::{LimitService, Limits};
+
+/// A [`Plugin`] which applies [`LimitService`] to every operation, with the [`Limits`] of the
+/// operation.
+///
+/// Each operation keeps track of its own limits, so `LimitPlugin` should only be applied once to
+/// a service.
+#[derive(Debug)]
+pub struct LimitPlugin {
+    limits: Limits,
+}
+
+impl LimitPlugin {
+    /// Creates a `LimitPlugin` applying `limits`.
+    pub fn new(limits: Limits) -> Self {
+        Self { limits }
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for LimitPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = LimitService<T, Ser::Protocol>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        let limiter = Limiter::new(Op::ID, self.limits.get(&Op::ID), self.limits.caller_key.clone());
+        LimitService::new(inner, limiter)
+    }
+}
+
+impl HttpMarker for LimitPlugin {}
+
+/// An extension trait for applying [`LimitPlugin`].
+pub trait LimitExt<CurrentPlugin> {
+    /// Applies a [`LimitService`] to every operation, rejecting the requests exceeding `limits`
+    /// with a throttling error. See the [module](crate::limit) documentation for more information.
+    fn limit(self, limits: Limits) -> HttpPlugins<PluginStack<LimitPlugin, CurrentPlugin>>;
+}
+
+impl<CurrentPlugin> LimitExt<CurrentPlugin> for HttpPlugins<CurrentPlugin> {
+    fn limit(self, limits: Limits) -> HttpPlugins<PluginStack<LimitPlugin, CurrentPlugin>> {
+        self.push(LimitPlugin::new(limits))
+    }
+}
@@ -0,0 +1,236 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [`Service`] and its associated [`Future`] applying the limits of an operation.
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use tower::Service;
+use tracing::debug;
+
+use crate::{body::BoxBody, response::IntoResponse, runtime_error::ThrottlingException};
+
+use super::limiter::{Limiter, Permit};
+
+pin_project_lite::pin_project! {
+    #[project = InnerProj]
+    enum Inner<Fut> {
+        // The request is in flight until `inner` completes.
+        Admitted {
+            #[pin]
+            inner: Fut,
+            permit: Option<Permit>,
+        },
+        Throttled {
+            response: Option<http::Response<BoxBody>>,
+        },
+    }
+}
+
+// This is to provide type erasure.
+pin_project_lite::pin_project! {
+    /// The [`Future`] of [`LimitService`].
+    pub struct LimitFuture<Fut> {
+        #[pin]
+        inner: Inner<Fut>,
+    }
+}
+
+impl<Fut, E> Future for LimitFuture<Fut>
+where
+    Fut: Future<Output = Result<http::Response<BoxBody>, E>>,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().inner.project() {
+            InnerProj::Admitted { inner, permit } => {
+                let output = futures_util::ready!(inner.poll(cx));
+                permit.take();
+                Poll::Ready(output)
+            }
+            InnerProj::Throttled { response } => Poll::Ready(Ok(response
+                .take()
+                .expect("futures must not be polled after completion"))),
+        }
+    }
+}
+
+/// A middleware [`Service`] rejecting the requests exceeding the limits of an operation with a
+/// protocol-specific throttling error.
+///
+/// See the [module](crate::limit) documentation for more information.
+pub struct LimitService<S, P> {
+    inner: S,
+    limiter: Arc<Limiter>,
+    _protocol: PhantomData<fn(P)>,
+}
+
+impl<S, P> LimitService<S, P> {
+    pub(crate) fn new(inner: S, limiter: Limiter) -> Self {
+        Self {
+            inner,
+            limiter: Arc::new(limiter),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S: Clone, P> Clone for LimitService<S, P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            limiter: self.limiter.clone(),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S: std::fmt::Debug, P> std::fmt::Debug for LimitService<S, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LimitService")
+            .field("inner", &self.inner)
+            .field("limiter", &self.limiter)
+            .finish()
+    }
+}
+
+impl<B, S, P> Service<http::Request<B>> for LimitService<S, P>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    ThrottlingException: IntoResponse<P>,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = LimitFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let (parts, body) = request.into_parts();
+        match self.limiter.try_acquire(&parts) {
+            Some(permit) => LimitFuture {
+                inner: Inner::Admitted {
+                    inner: self.inner.call(http::Request::from_parts(parts, body)),
+                    permit: Some(permit),
+                },
+            },
+            None => {
+                debug!(operation = %self.limiter.operation().absolute(), "request throttled");
+                LimitFuture {
+                    inner: Inner::Throttled {
+                        response: Some(ThrottlingException.into_response()),
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::Arc;
+
+    use tokio::sync::Notify;
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::limit::{LimitPlugin, Limits, OperationLimit};
+    use crate::operation::OperationShape;
+    use crate::plugin::Plugin;
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::rpc_v2_cbor::RpcV2Cbor;
+    use crate::service::ServiceShape;
+    use crate::shape_id::ShapeId;
+
+    struct Service<P>(PhantomData<P>);
+    impl<P> ServiceShape for Service<P> {
+        const ID: ShapeId = ShapeId::new("namespace#Service", "namespace", "Service");
+        const VERSION: Option<&'static str> = None;
+        type Protocol = P;
+        type Operations = ();
+    }
+
+    struct Limited;
+    impl OperationShape for Limited {
+        const ID: ShapeId = ShapeId::new("namespace#Limited", "namespace", "Limited");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+
+    struct Unlimited;
+    impl OperationShape for Unlimited {
+        const ID: ShapeId = ShapeId::new("namespace#Unlimited", "namespace", "Unlimited");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+
+    fn request() -> http::Request<()> {
+        http::Request::get("/").body(()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_exceeding_max_in_flight() {
+        let plugin = LimitPlugin::new(Limits::new().operation(Limited::ID, OperationLimit::new().max_in_flight(1)));
+        let notify = Arc::new(Notify::new());
+        let inner = {
+            let notify = notify.clone();
+            service_fn(move |_request: http::Request<()>| {
+                let notify = notify.clone();
+                async move {
+                    notify.notified().await;
+                    Ok::<_, Infallible>(http::Response::new(crate::body::empty()))
+                }
+            })
+        };
+        let svc = Plugin::<Service<RestJson1>, Limited, _>::apply(&plugin, inner.clone());
+
+        let in_flight = tokio::spawn(svc.clone().oneshot(request()));
+        tokio::task::yield_now().await;
+
+        let response = svc.clone().oneshot(request()).await.unwrap();
+        assert_eq!(http::StatusCode::TOO_MANY_REQUESTS, response.status());
+        assert_eq!("ThrottlingException", response.headers()["X-Amzn-Errortype"]);
+
+        // Other operations aren't limited.
+        let unlimited = Plugin::<Service<RestJson1>, Unlimited, _>::apply(&plugin, inner);
+        let unlimited = tokio::spawn(unlimited.oneshot(request()));
+        tokio::task::yield_now().await;
+        assert!(!unlimited.is_finished());
+
+        notify.notify_waiters();
+        assert_eq!(http::StatusCode::OK, in_flight.await.unwrap().unwrap().status());
+        unlimited.await.unwrap().unwrap();
+
+        let pending = tokio::spawn(svc.oneshot(request()));
+        tokio::task::yield_now().await;
+        notify.notify_waiters();
+        assert_eq!(http::StatusCode::OK, pending.await.unwrap().unwrap().status());
+    }
+
+    #[tokio::test]
+    async fn throttling_error_is_protocol_specific() {
+        let plugin = LimitPlugin::new(Limits::new().default_limit(OperationLimit::new().max_in_flight(0)));
+        let inner = service_fn(|_request: http::Request<()>| async {
+            Ok::<_, Infallible>(http::Response::new(crate::body::empty()))
+        });
+        let svc = Plugin::<Service<RpcV2Cbor>, Limited, _>::apply(&plugin, inner);
+
+        let response = svc.oneshot(request()).await.unwrap();
+        assert_eq!(http::StatusCode::TOO_MANY_REQUESTS, response.status());
+        assert_eq!("application/cbor", response.headers()[http::header::CONTENT_TYPE]);
+    }
+}
@@ -17,8 +17,8 @@ use tower::{util::Oneshot, Service, ServiceExt};
 use tracing::error;
 
 use crate::{
-    body::BoxBody, plugin::Plugin, request::FromRequest, response::IntoResponse,
-    runtime_error::InternalFailureException, service::ServiceShape,
+    body::BoxBody, cors::CorsPreflight, plugin::Plugin, request::FromRequest, response::IntoResponse,
+    routing::method_disallowed, runtime_error::InternalFailureException, service::ServiceShape,
 };
 
 use super::OperationShape;
@@ -87,6 +87,7 @@ where
 pin_project! {
     #[project = InnerProj]
     #[project_replace = InnerProjReplace]
+    #[allow(clippy::enum_variant_names)]
     enum Inner<FromFut, HandlerFut> {
         FromRequest {
             #[pin]
@@ -95,7 +96,10 @@ pin_project! {
         Inner {
             #[pin]
             call: HandlerFut
-        }
+        },
+        // A CORS preflight request, which isn't handled by a `CorsService`, never reaches the
+        // handler.
+        Preflight
     }
 }
 
@@ -155,6 +159,7 @@ where
                     };
                     return Poll::Ready(Ok(output));
                 }
+                InnerProj::Preflight => return Poll::Ready(Ok(method_disallowed())),
             };
 
             this.inner.as_mut().project_replace(Inner::Inner { call });
@@ -181,6 +186,13 @@ where
     }
 
     fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if req.extensions().get::<CorsPreflight>().is_some() {
+            return UpgradeFuture {
+                service: None,
+                inner: Inner::Preflight,
+            };
+        }
+
         let clone = self.inner.clone();
         let service = std::mem::replace(&mut self.inner, clone);
         UpgradeFuture {
@@ -227,3 +239,51 @@ where
         std::future::ready(Ok(InternalFailureException.into_response()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::future::ready;
+
+    use tower::service_fn;
+
+    use super::*;
+    use crate::protocol::rest_json_1::RestJson1;
+
+    struct Input;
+
+    impl<P, B> FromRequest<P, B> for Input {
+        type Rejection = Infallible;
+        type Future = Ready<Result<Self, Infallible>>;
+
+        fn from_request(_request: http::Request<B>) -> Self::Future {
+            ready(Ok(Input))
+        }
+    }
+
+    struct Output;
+
+    impl<P> IntoResponse<P> for Output {
+        fn into_response(self) -> http::Response<BoxBody> {
+            http::Response::new(crate::body::empty())
+        }
+    }
+
+    #[tokio::test]
+    async fn preflight_requests_never_reach_the_handler() {
+        let svc = Upgrade::<RestJson1, Input, _> {
+            _protocol: PhantomData,
+            _input: PhantomData,
+            inner: service_fn(|_input: Input| async { Ok::<_, Infallible>(Output) }),
+        };
+
+        let response = svc.clone().oneshot(http::Request::new(())).await.unwrap();
+        assert_eq!(http::StatusCode::OK, response.status());
+
+        let mut request = http::Request::new(());
+        request
+            .extensions_mut()
+            .insert(CorsPreflight::new(vec![(http::Method::GET, None)]));
+        let response = svc.oneshot(request).await.unwrap();
+        assert_eq!(http::StatusCode::METHOD_NOT_ALLOWED, response.status());
+    }
+}
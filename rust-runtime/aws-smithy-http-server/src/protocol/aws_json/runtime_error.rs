@@ -5,12 +5,16 @@
 
 use crate::protocol::aws_json_11::AwsJson1_1;
 use crate::response::IntoResponse;
-use crate::runtime_error::{InternalFailureException, INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE};
+use crate::runtime_error::{
+    InternalFailureException, ThrottlingException, TimeoutException, UnsupportedMediaTypeException,
+    INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE,
+};
 use crate::{extension::RuntimeErrorExtension, protocol::aws_json_10::AwsJson1_0};
 use http::StatusCode;
 
 use super::rejection::{RequestRejection, ResponseRejection};
 
+#[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
 pub enum RuntimeError {
     /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Serialization`]
@@ -28,6 +32,15 @@ pub enum RuntimeError {
     /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Validation`]
     #[error("validation failure: operation input contains data that does not adhere to the modeled constraints: {0}")]
     Validation(String),
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::PayloadTooLarge`]
+    #[error("payload too large: request body exceeds the configured maximum size")]
+    PayloadTooLarge,
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Throttling`]
+    #[error("throttling: a rate or concurrency limit was reached")]
+    Throttling,
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Timeout`]
+    #[error("timeout: the operation did not complete before its timeout elapsed")]
+    Timeout,
 }
 
 impl RuntimeError {
@@ -38,6 +51,9 @@ impl RuntimeError {
             Self::NotAcceptable => "NotAcceptableException",
             Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
             Self::Validation(_) => "ValidationException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
+            Self::Throttling => "ThrottlingException",
+            Self::Timeout => "TimeoutException",
         }
     }
 
@@ -48,6 +64,9 @@ impl RuntimeError {
             Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Throttling => StatusCode::TOO_MANY_REQUESTS,
+            Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
         }
     }
 }
@@ -58,12 +77,48 @@ impl IntoResponse<AwsJson1_0> for InternalFailureException {
     }
 }
 
+impl IntoResponse<AwsJson1_0> for ThrottlingException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<AwsJson1_0>::into_response(RuntimeError::Throttling)
+    }
+}
+
+impl IntoResponse<AwsJson1_0> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<AwsJson1_0>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<AwsJson1_0> for UnsupportedMediaTypeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<AwsJson1_0>::into_response(RuntimeError::UnsupportedMediaType)
+    }
+}
+
 impl IntoResponse<AwsJson1_1> for InternalFailureException {
     fn into_response(self) -> http::Response<crate::body::BoxBody> {
         IntoResponse::<AwsJson1_1>::into_response(RuntimeError::InternalFailure(crate::Error::new(String::new())))
     }
 }
 
+impl IntoResponse<AwsJson1_1> for ThrottlingException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<AwsJson1_1>::into_response(RuntimeError::Throttling)
+    }
+}
+
+impl IntoResponse<AwsJson1_1> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<AwsJson1_1>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<AwsJson1_1> for UnsupportedMediaTypeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<AwsJson1_1>::into_response(RuntimeError::UnsupportedMediaType)
+    }
+}
+
 impl IntoResponse<AwsJson1_0> for RuntimeError {
     fn into_response(self) -> http::Response<crate::body::BoxBody> {
         let res = http::Response::builder()
@@ -108,6 +163,7 @@ impl From<ResponseRejection> for RuntimeError {
 impl From<RequestRejection> for RuntimeError {
     fn from(err: RequestRejection) -> Self {
         match err {
+            RequestRejection::RequestBodyTooLarge(_) => Self::PayloadTooLarge,
             RequestRejection::ConstraintViolation(reason) => Self::Validation(reason),
             _ => Self::Serialization(crate::Error::new(err)),
         }
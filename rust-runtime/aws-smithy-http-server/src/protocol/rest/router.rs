@@ -6,6 +6,8 @@
 use std::convert::Infallible;
 
 use crate::body::BoxBody;
+use crate::cors::CorsPreflight;
+use crate::routing::path_tree::PathTree;
 use crate::routing::request_spec::Match;
 use crate::routing::request_spec::RequestSpec;
 use crate::routing::Route;
@@ -28,11 +30,16 @@ pub enum Error {
 
 /// A [`Router`] supporting [AWS restJson1] and [AWS restXml] protocols.
 ///
+/// Routes are looked up by path in a prefix tree compiled from their URI patterns, and only the
+/// routes whose path matches have their query string and method checked.
+///
 /// [AWS restJson1]: https://awslabs.github.io/smithy/2.0/aws/protocols/aws-restjson1-protocol.html
 /// [AWS restXml]: https://awslabs.github.io/smithy/2.0/aws/protocols/aws-restxml-protocol.html
 #[derive(Debug, Clone)]
 pub struct RestRouter<S> {
     routes: Vec<(RequestSpec, S)>,
+    // Indexes into `routes`
+    tree: PathTree,
 }
 
 impl<S> RestRouter<S> {
@@ -47,6 +54,7 @@ impl<S> RestRouter<S> {
                 .into_iter()
                 .map(|(request_spec, route)| (request_spec, layer.layer(route)))
                 .collect(),
+            tree: self.tree,
         }
     }
 
@@ -59,6 +67,7 @@ impl<S> RestRouter<S> {
     {
         RestRouter {
             routes: self.routes.into_iter().map(|(spec, s)| (spec, Route::new(s))).collect(),
+            tree: self.tree,
         }
     }
 }
@@ -73,8 +82,10 @@ where
     fn match_route(&self, request: &http::Request<B>) -> Result<S, Self::Error> {
         let mut method_allowed = true;
 
-        for (request_spec, route) in &self.routes {
-            match request_spec.matches(request) {
+        // The matching routes are in the same order as `routes`, i.e. sorted by specificity.
+        for index in self.tree.matches(request.uri().path()) {
+            let (request_spec, route) = &self.routes[index];
+            match request_spec.matches_except_path(request) {
                 // Match found.
                 Match::Yes => return Ok(route.clone()),
                 // Match found, but method disallowed.
@@ -90,6 +101,37 @@ where
             Err(Error::MethodNotAllowed)
         }
     }
+
+    fn match_preflight(&self, request: &http::Request<B>) -> Option<(S, CorsPreflight)> {
+        let requested_method: http::Method = request
+            .headers()
+            .get(http::header::ACCESS_CONTROL_REQUEST_METHOD)?
+            .as_bytes()
+            .try_into()
+            .ok()?;
+
+        let mut routes: Vec<(http::Method, _)> = Vec::new();
+        let mut matched_route = None;
+        for index in self.tree.matches(request.uri().path()) {
+            let (request_spec, route) = &self.routes[index];
+            // The method of a preflight request is `OPTIONS`, so routes matching its path and query
+            // string disallow its method.
+            if request_spec.matches_except_path(request) == Match::No {
+                continue;
+            }
+            // Routes are sorted by specificity, so the first route matching a method is the one the
+            // actual request would be routed to.
+            let method = request_spec.method();
+            if !routes.iter().any(|(allowed, _)| allowed == method) {
+                routes.push((method.clone(), request_spec.operation().cloned()));
+            }
+            if matched_route.is_none() && *method == requested_method {
+                matched_route = Some(route.clone());
+            }
+        }
+
+        matched_route.map(|route| (route, CorsPreflight::new(routes)))
+    }
 }
 
 impl<S> FromIterator<(RequestSpec, S)> for RestRouter<S> {
@@ -102,16 +144,21 @@ impl<S> FromIterator<(RequestSpec, S)> for RestRouter<S> {
         // and pick the first one that matches.
         routes.sort_by_key(|(request_spec, _route)| std::cmp::Reverse(request_spec.rank()));
 
-        Self { routes }
+        let mut tree = PathTree::default();
+        for (index, (request_spec, _route)) in routes.iter().enumerate() {
+            tree.insert(request_spec.path_segments(), index);
+        }
+
+        Self { routes, tree }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{protocol::test_helpers::req, routing::request_spec::*};
+    use crate::{protocol::test_helpers::req, routing::request_spec::*, shape_id::ShapeId};
 
-    use http::Method;
+    use http::{HeaderMap, HeaderValue, Method};
 
     // This test is a rewrite of `mux.spec.ts`.
     // https://github.com/awslabs/smithy-typescript/blob/fbf97a9bf4c1d8cf7f285ea7c24e1f0ef280142a/smithy-typescript-ssdk-libs/server-common/src/httpbinding/mux.spec.ts
@@ -261,4 +308,187 @@ mod tests {
             assert_eq!(router.match_route(&req(&method, uri, None)).unwrap(), svc_name);
         }
     }
+
+    /// The routing algorithm `RestRouter` used before routing with a prefix tree: evaluates the path
+    /// regular expression of every route, in order.
+    struct LinearRouter<'a, S> {
+        router: &'a RestRouter<S>,
+        regexes: Vec<regex::Regex>,
+    }
+
+    impl<'a, S: Clone> LinearRouter<'a, S> {
+        fn new(router: &'a RestRouter<S>) -> Self {
+            let regexes = router
+                .routes
+                .iter()
+                .map(|(request_spec, _)| {
+                    (&PathSpec::from_vector_unchecked(request_spec.path_segments().to_vec())).into()
+                })
+                .collect();
+            Self { router, regexes }
+        }
+
+        fn match_route<B>(&self, request: &http::Request<B>) -> Result<S, Error> {
+            let mut method_allowed = true;
+            for ((request_spec, route), regex) in self.router.routes.iter().zip(&self.regexes) {
+                if !regex.is_match(request.uri().path()) {
+                    continue;
+                }
+                match request_spec.matches_except_path(request) {
+                    Match::Yes => return Ok(route.clone()),
+                    Match::MethodNotAllowed => method_allowed = false,
+                    Match::No => continue,
+                }
+            }
+            if method_allowed {
+                Err(Error::NotFound)
+            } else {
+                Err(Error::MethodNotAllowed)
+            }
+        }
+    }
+
+    /// Deterministic pseudo-random generator, so that failures are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, bound: usize) -> usize {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) as usize) % bound
+        }
+
+        fn pick<'a>(&mut self, choices: &[&'a str]) -> &'a str {
+            choices[self.below(choices.len())]
+        }
+    }
+
+    const LITERALS: &[&str] = &["a", "b", "z", "a/b", "(a+)"];
+    const SEGMENTS: &[&str] = &["a", "b", "z", "", "x", "(a+)", "a%2Fb"];
+    const QUERY_KEYS: &[&str] = &["foo", "bar"];
+    const QUERY_VALUES: &[&str] = &["1", "2", ""];
+    const METHODS: &[Method] = &[Method::GET, Method::POST, Method::DELETE];
+
+    fn random_request_spec(rng: &mut Lcg) -> RequestSpec {
+        let mut greedy = false;
+        let path_segments = (0..rng.below(5))
+            .map(|_| match rng.below(6) {
+                0 | 1 => PathSegment::Label,
+                // Smithy allows at most one greedy label per pattern
+                2 if !greedy => {
+                    greedy = true;
+                    PathSegment::Greedy
+                }
+                _ => PathSegment::Literal(rng.pick(LITERALS).to_owned()),
+            })
+            .collect();
+        let query_segments = (0..rng.below(3))
+            .map(|_| match rng.below(2) {
+                0 => QuerySegment::Key(rng.pick(QUERY_KEYS).to_owned()),
+                _ => QuerySegment::KeyValue(rng.pick(QUERY_KEYS).to_owned(), rng.pick(QUERY_VALUES).to_owned()),
+            })
+            .collect();
+        RequestSpec::from_parts(METHODS[rng.below(METHODS.len())].clone(), path_segments, query_segments)
+    }
+
+    fn random_uri(rng: &mut Lcg) -> String {
+        let mut uri = String::new();
+        for _ in 0..rng.below(6) {
+            uri.push('/');
+            uri.push_str(rng.pick(SEGMENTS));
+        }
+        if uri.is_empty() || rng.below(4) == 0 {
+            uri.push('/');
+        }
+        let query: Vec<String> = (0..rng.below(4))
+            .map(|_| match rng.below(3) {
+                0 => rng.pick(QUERY_KEYS).to_owned(),
+                _ => format!("{}={}", rng.pick(QUERY_KEYS), rng.pick(QUERY_VALUES)),
+            })
+            .collect();
+        if !query.is_empty() {
+            uri.push('?');
+            uri.push_str(&query.join("&"));
+        }
+        uri
+    }
+
+    #[test]
+    fn prefix_tree_routes_like_linear_routing() {
+        let mut rng = Lcg(0x5eed);
+        for _ in 0..200 {
+            let request_specs: Vec<(RequestSpec, usize)> = (0..1 + rng.below(30))
+                .map(|i| (random_request_spec(&mut rng), i))
+                .collect();
+            let router: RestRouter<_> = request_specs.into_iter().collect();
+            let linear_router = LinearRouter::new(&router);
+
+            for _ in 0..100 {
+                let uri = random_uri(&mut rng);
+                let method = &METHODS[rng.below(METHODS.len())];
+                let request = req(method, &uri, None);
+                assert_eq!(
+                    linear_router.match_route(&request),
+                    router.match_route(&request),
+                    "{method} {uri} with {router:#?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn preflight_requests_are_routed_to_the_requested_method() {
+        const GET: ShapeId = ShapeId::new("namespace#Get", "namespace", "Get");
+        const GET_X: ShapeId = ShapeId::new("namespace#GetX", "namespace", "GetX");
+        let spec = |method: Method, query: Vec<QuerySegment>| {
+            RequestSpec::from_parts(
+                method,
+                vec![PathSegment::Literal(String::from("a")), PathSegment::Label],
+                query,
+            )
+        };
+        let router: RestRouter<_> = [
+            (spec(Method::GET, vec![]).with_operation(GET), "get"),
+            (spec(Method::PUT, vec![]), "put"),
+            (
+                spec(Method::DELETE, vec![QuerySegment::Key(String::from("x"))]),
+                "delete",
+            ),
+            (
+                spec(Method::GET, vec![QuerySegment::Key(String::from("x"))]).with_operation(GET_X),
+                "get-x",
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let preflight = |uri: &str, method: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(http::header::ORIGIN, HeaderValue::from_static("https://example.com"));
+            headers.insert(
+                http::header::ACCESS_CONTROL_REQUEST_METHOD,
+                HeaderValue::from_static(method),
+            );
+            req(&Method::OPTIONS, uri, Some(headers))
+        };
+
+        let (route, preflight_ext) = router.match_preflight(&preflight("/a/b", "PUT")).unwrap();
+        assert_eq!("put", route);
+        assert_eq!(&[(Method::GET, Some(GET)), (Method::PUT, None)], preflight_ext.routes());
+
+        // Routes matching the query string are preferred, as they are for the actual request.
+        let (route, preflight_ext) = router.match_preflight(&preflight("/a/b?x", "GET")).unwrap();
+        assert_eq!("get-x", route);
+        assert_eq!(
+            &[(Method::DELETE, None), (Method::GET, Some(GET_X)), (Method::PUT, None)],
+            preflight_ext.routes()
+        );
+
+        assert!(router.match_preflight(&preflight("/a/b", "DELETE")).is_none());
+        assert!(router.match_preflight(&preflight("/a/b", "POST")).is_none());
+        assert!(router.match_preflight(&preflight("/b", "GET")).is_none());
+        assert!(router.match_preflight(&req(&Method::OPTIONS, "/a/b", None)).is_none());
+    }
 }
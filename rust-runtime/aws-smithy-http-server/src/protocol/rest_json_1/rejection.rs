@@ -110,6 +110,12 @@ pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
 
+    /// Used when a non-streaming request body is larger than the maximum size configured with
+    /// the `requestBodyMaxBytes` or `requestBodyMaxBytesPerOperation` codegen settings. The body is
+    /// rejected as soon as it exceeds the maximum, without buffering the rest of it.
+    #[error("request body is too large: {0}")]
+    RequestBodyTooLarge(#[from] crate::body::BodyLimitExceeded),
+
     /// Used when the request contained an `Accept` header with a MIME type, and the server cannot
     /// return a response body adhering to that MIME type.
     #[error("request contains invalid value for `Accept` header")]
@@ -193,7 +199,10 @@ impl From<std::convert::Infallible> for RequestRejection {
 // When using BoxBody or custom body implementations, errors are crate::Error, not hyper::Error.
 impl From<crate::Error> for RequestRejection {
     fn from(err: crate::Error) -> Self {
-        Self::BufferHttpBodyBytes(err)
+        match crate::body::BodyLimitExceeded::find(&err) {
+            Some(exceeded) => Self::RequestBodyTooLarge(exceeded),
+            None => Self::BufferHttpBodyBytes(err),
+        }
     }
 }
 
@@ -216,4 +225,8 @@ convert_to_request_rejection!(hyper::Error, BufferHttpBodyBytes);
 
 // Useful in general, but it also required in order to accept Lambda HTTP requests using
 // `Router<lambda_http::Body>` since `lambda_http::Error` is a type alias for `Box<dyn Error + ..>`.
-convert_to_request_rejection!(Box<dyn std::error::Error + Send + Sync + 'static>, BufferHttpBodyBytes);
+impl From<Box<dyn std::error::Error + Send + Sync + 'static>> for RequestRejection {
+    fn from(err: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
+        Self::from(crate::Error::new(err))
+    }
+}
@@ -35,9 +35,13 @@ use super::RestJson1;
 use crate::extension::RuntimeErrorExtension;
 use crate::response::IntoResponse;
 use crate::runtime_error::InternalFailureException;
+use crate::runtime_error::ThrottlingException;
+use crate::runtime_error::TimeoutException;
+use crate::runtime_error::UnsupportedMediaTypeException;
 use crate::runtime_error::INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE;
 use http::StatusCode;
 
+#[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
 pub enum RuntimeError {
     /// Request failed to deserialize or response failed to serialize.
@@ -61,6 +65,16 @@ pub enum RuntimeError {
     /// [constraint traits]: <https://awslabs.github.io/smithy/2.0/spec/constraint-traits.html>
     #[error("validation failure: operation input contains data that does not adhere to the modeled constraints: {0}")]
     Validation(String),
+    /// The request body is larger than the maximum size the server buffers for the operation.
+    #[error("payload too large: request body exceeds the configured maximum size")]
+    PayloadTooLarge,
+    /// The request was rejected because a rate or concurrency limit was reached, see
+    /// [`crate::limit`].
+    #[error("throttling: a rate or concurrency limit was reached")]
+    Throttling,
+    /// The operation didn't produce a response before its timeout elapsed, see [`crate::timeout`].
+    #[error("timeout: the operation did not complete before its timeout elapsed")]
+    Timeout,
 }
 
 impl RuntimeError {
@@ -74,6 +88,9 @@ impl RuntimeError {
             Self::NotAcceptable => "NotAcceptableException",
             Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
             Self::Validation(_) => "ValidationException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
+            Self::Throttling => "ThrottlingException",
+            Self::Timeout => "TimeoutException",
         }
     }
 
@@ -84,6 +101,9 @@ impl RuntimeError {
             Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Throttling => StatusCode::TOO_MANY_REQUESTS,
+            Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
         }
     }
 }
@@ -94,6 +114,24 @@ impl IntoResponse<RestJson1> for InternalFailureException {
     }
 }
 
+impl IntoResponse<RestJson1> for ThrottlingException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RestJson1>::into_response(RuntimeError::Throttling)
+    }
+}
+
+impl IntoResponse<RestJson1> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RestJson1>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<RestJson1> for UnsupportedMediaTypeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RestJson1>::into_response(RuntimeError::UnsupportedMediaType)
+    }
+}
+
 impl IntoResponse<RestJson1> for RuntimeError {
     fn into_response(self) -> http::Response<crate::body::BoxBody> {
         let res = http::Response::builder()
@@ -122,6 +160,7 @@ impl From<RequestRejection> for RuntimeError {
     fn from(err: RequestRejection) -> Self {
         match err {
             RequestRejection::MissingContentType(_reason) => Self::UnsupportedMediaType,
+            RequestRejection::RequestBodyTooLarge(_) => Self::PayloadTooLarge,
             RequestRejection::ConstraintViolation(reason) => Self::Validation(reason),
             RequestRejection::NotAcceptable => Self::NotAcceptable,
             _ => Self::Serialization(crate::Error::new(err)),
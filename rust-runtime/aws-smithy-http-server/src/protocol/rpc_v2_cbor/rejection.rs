@@ -23,6 +23,8 @@ pub enum ResponseRejection {
 pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
+    #[error("request body is too large: {0}")]
+    RequestBodyTooLarge(#[from] crate::body::BodyLimitExceeded),
     #[error("request contains invalid value for `Accept` header")]
     NotAcceptable,
     #[error("expected `Content-Type` header not found: {0}")]
@@ -49,7 +51,10 @@ impl From<std::convert::Infallible> for RequestRejection {
 // When using BoxBody or custom body implementations, errors are crate::Error, not hyper::Error.
 impl From<crate::Error> for RequestRejection {
     fn from(err: crate::Error) -> Self {
-        Self::BufferHttpBodyBytes(err)
+        match crate::body::BodyLimitExceeded::find(&err) {
+            Some(exceeded) => Self::RequestBodyTooLarge(exceeded),
+            None => Self::BufferHttpBodyBytes(err),
+        }
     }
 }
 
@@ -58,4 +63,8 @@ impl From<crate::Error> for RequestRejection {
 // this error, so we need this conversion to handle it within the framework.
 convert_to_request_rejection!(hyper::Error, BufferHttpBodyBytes);
 
-convert_to_request_rejection!(Box<dyn std::error::Error + Send + Sync + 'static>, BufferHttpBodyBytes);
+impl From<Box<dyn std::error::Error + Send + Sync + 'static>> for RequestRejection {
+    fn from(err: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
+        Self::from(crate::Error::new(err))
+    }
+}
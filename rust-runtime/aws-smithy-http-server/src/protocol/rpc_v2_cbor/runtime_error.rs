@@ -4,13 +4,17 @@
  */
 
 use crate::response::IntoResponse;
-use crate::runtime_error::{InternalFailureException, INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE};
+use crate::runtime_error::{
+    InternalFailureException, ThrottlingException, TimeoutException, UnsupportedMediaTypeException,
+    INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE,
+};
 use crate::{extension::RuntimeErrorExtension, protocol::rpc_v2_cbor::RpcV2Cbor};
 use bytes::Bytes;
 use http::StatusCode;
 
 use super::rejection::{RequestRejection, ResponseRejection};
 
+#[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
 pub enum RuntimeError {
     /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Serialization`]
@@ -30,6 +34,15 @@ pub enum RuntimeError {
         "validation failure: operation input contains data that does not adhere to the modeled constraints: {0:?}"
     )]
     Validation(Vec<u8>),
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::PayloadTooLarge`]
+    #[error("payload too large: request body exceeds the configured maximum size")]
+    PayloadTooLarge,
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Throttling`]
+    #[error("throttling: a rate or concurrency limit was reached")]
+    Throttling,
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Timeout`]
+    #[error("timeout: the operation did not complete before its timeout elapsed")]
+    Timeout,
 }
 
 impl RuntimeError {
@@ -40,6 +53,9 @@ impl RuntimeError {
             Self::NotAcceptable => "NotAcceptableException",
             Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
             Self::Validation(_) => "ValidationException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
+            Self::Throttling => "ThrottlingException",
+            Self::Timeout => "TimeoutException",
         }
     }
 
@@ -50,6 +66,9 @@ impl RuntimeError {
             Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Throttling => StatusCode::TOO_MANY_REQUESTS,
+            Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
         }
     }
 }
@@ -60,6 +79,24 @@ impl IntoResponse<RpcV2Cbor> for InternalFailureException {
     }
 }
 
+impl IntoResponse<RpcV2Cbor> for ThrottlingException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RpcV2Cbor>::into_response(RuntimeError::Throttling)
+    }
+}
+
+impl IntoResponse<RpcV2Cbor> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RpcV2Cbor>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<RpcV2Cbor> for UnsupportedMediaTypeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RpcV2Cbor>::into_response(RuntimeError::UnsupportedMediaType)
+    }
+}
+
 impl IntoResponse<RpcV2Cbor> for RuntimeError {
     fn into_response(self) -> http::Response<crate::body::BoxBody> {
         let res = http::Response::builder()
@@ -91,6 +128,7 @@ impl From<ResponseRejection> for RuntimeError {
 impl From<RequestRejection> for RuntimeError {
     fn from(err: RequestRejection) -> Self {
         match err {
+            RequestRejection::RequestBodyTooLarge(_) => Self::PayloadTooLarge,
             RequestRejection::ConstraintViolation(reason) => Self::Validation(reason),
             _ => Self::Serialization(crate::Error::new(err)),
         }
@@ -18,6 +18,7 @@ pub mod request_spec;
 
 mod route;
 
+pub(crate) mod path_tree;
 pub(crate) mod tiny_map;
 
 use std::{
@@ -40,6 +41,7 @@ use tower::{util::Oneshot, Service, ServiceExt};
 
 use crate::{
     body::{boxed, BoxBody},
+    cors::{is_preflight, CorsPreflight},
     error::BoxError,
     response::IntoResponse,
 };
@@ -71,6 +73,14 @@ pub trait Router<B> {
 
     /// Matches a [`http::Request`] to a target [`Service`].
     fn match_route(&self, request: &http::Request<B>) -> Result<Self::Service, Self::Error>;
+
+    /// Matches a CORS preflight [`http::Request`] to the target [`Service`] of the request it's
+    /// the preflight of, see [`crate::cors`].
+    ///
+    /// Returns `None` if the router can't match preflight requests, which is the default.
+    fn match_preflight(&self, _request: &http::Request<B>) -> Option<(Self::Service, CorsPreflight)> {
+        None
+    }
 }
 
 /// A [`Service`] using the [`Router`] `R` to redirect messages to specific routes.
@@ -189,13 +199,21 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
         tracing::debug!("inside routing service call");
         match self.router.match_route(&req) {
             // Successfully routed, use the routes `Service::call`.
             Ok(ok) => RoutingFuture::from_oneshot(ok.oneshot(req)),
-            // Failed to route, use the `R::Error`s `IntoResponse<P>`.
             Err(error) => {
+                // A CORS preflight request is routed to the route of the request it's the
+                // preflight of, if no route matches it as is.
+                if is_preflight(&req) {
+                    if let Some((route, preflight)) = self.router.match_preflight(&req) {
+                        req.extensions_mut().insert(preflight);
+                        return RoutingFuture::from_oneshot(route.oneshot(req));
+                    }
+                }
+                // Failed to route, use the `R::Error`s `IntoResponse<P>`.
                 tracing::debug!(%error, "failed to route");
                 RoutingFuture::from_response(error.into_response())
             }
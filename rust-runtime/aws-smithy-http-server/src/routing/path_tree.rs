@@ -0,0 +1,152 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::collections::HashMap;
+
+use super::request_spec::PathSegment;
+
+/// A prefix tree of URI path patterns, keyed by path segment.
+///
+/// It finds all the patterns matching a path in a single traversal of the path, instead of
+/// evaluating the regular expression of every pattern. Matching is the same as the regular
+/// expressions built from [`PathSpec`](super::request_spec::PathSpec)s:
+///
+/// - a literal matches a segment equal to it;
+/// - a label matches any single segment, including the empty segment;
+/// - a greedy label matches one or more segments, including empty segments.
+///
+/// Each pattern is identified by the index it was inserted with.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PathTree {
+    root: Node,
+    /// Patterns without segments, which only match `/`.
+    root_patterns: Vec<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    literals: HashMap<String, Node>,
+    label: Option<Box<Node>>,
+    greedy: Option<Box<Node>>,
+    /// Patterns ending at this node.
+    patterns: Vec<usize>,
+}
+
+impl PathTree {
+    /// Inserts the pattern made of `segments`, identified by `index`.
+    pub(crate) fn insert(&mut self, segments: &[PathSegment], index: usize) {
+        if segments.is_empty() {
+            self.root_patterns.push(index);
+            return;
+        }
+
+        let mut node = &mut self.root;
+        for segment in segments {
+            match segment {
+                // Literals are matched against the path as is, so a literal spanning several
+                // segments is equivalent to one literal per segment.
+                PathSegment::Literal(literal) => {
+                    for part in literal.split('/') {
+                        node = node.literals.entry(part.to_owned()).or_default();
+                    }
+                }
+                PathSegment::Label => node = node.label.get_or_insert_with(Default::default),
+                PathSegment::Greedy => node = node.greedy.get_or_insert_with(Default::default),
+            }
+        }
+        node.patterns.push(index);
+    }
+
+    /// Returns the indices of the patterns matching `path`, in ascending order.
+    pub(crate) fn matches(&self, path: &str) -> Vec<usize> {
+        let mut matches = Vec::new();
+        if path == "/" {
+            matches.extend(&self.root_patterns);
+        }
+        if let Some(path) = path.strip_prefix('/') {
+            let segments: Vec<&str> = path.split('/').collect();
+            self.root.collect(&segments, &mut matches);
+        }
+        matches.sort_unstable();
+        // A pattern with a greedy label can match a path in more than one way.
+        matches.dedup();
+        matches
+    }
+}
+
+impl Node {
+    fn collect(&self, segments: &[&str], matches: &mut Vec<usize>) {
+        let Some((first, rest)) = segments.split_first() else {
+            matches.extend(&self.patterns);
+            return;
+        };
+        if let Some(literal) = self.literals.get(*first) {
+            literal.collect(rest, matches);
+        }
+        if let Some(label) = &self.label {
+            label.collect(rest, matches);
+        }
+        if let Some(greedy) = &self.greedy {
+            for consumed in 1..=segments.len() {
+                greedy.collect(&segments[consumed..], matches);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(patterns: &[Vec<PathSegment>]) -> PathTree {
+        let mut tree = PathTree::default();
+        for (index, segments) in patterns.iter().enumerate() {
+            tree.insert(segments, index);
+        }
+        tree
+    }
+
+    fn literal(literal: &str) -> PathSegment {
+        PathSegment::Literal(literal.to_owned())
+    }
+
+    #[test]
+    fn matches_all_patterns() {
+        let tree = tree(&[
+            vec![],
+            vec![literal("a")],
+            vec![PathSegment::Label],
+            vec![literal("a"), PathSegment::Label],
+            vec![literal("a"), PathSegment::Greedy],
+            vec![literal("a"), PathSegment::Greedy, literal("z")],
+        ]);
+
+        let cases: Vec<(&str, Vec<usize>)> = vec![
+            ("/", vec![0, 2]),
+            ("/a", vec![1, 2]),
+            ("/b", vec![2]),
+            ("/a/", vec![3, 4]),
+            ("/a/b", vec![3, 4]),
+            ("/a/b/c", vec![4]),
+            ("/a/z", vec![3, 4]),
+            ("/a//z", vec![4, 5]),
+            ("/a/b/z/c/z", vec![4, 5]),
+            ("//a", vec![]),
+            ("", vec![]),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(expected, tree.matches(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn literals_spanning_segments() {
+        let tree = tree(&[vec![literal("a/b"), PathSegment::Label], vec![literal("")]]);
+
+        assert_eq!(vec![0], tree.matches("/a/b/c"));
+        assert!(tree.matches("/a/bc").is_empty());
+        assert_eq!(vec![1], tree.matches("/"));
+    }
+}
@@ -8,6 +8,8 @@ use std::borrow::Cow;
 use http::Request;
 use regex::Regex;
 
+use crate::shape_id::ShapeId;
+
 #[derive(Debug, Clone)]
 pub enum PathSegment {
     Literal(String),
@@ -81,7 +83,7 @@ impl UriSpec {
 pub struct RequestSpec {
     method: http::Method,
     uri_spec: UriSpec,
-    uri_path_regex: Regex,
+    operation: Option<ShapeId>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -121,14 +123,20 @@ impl From<&PathSpec> for Regex {
 
 impl RequestSpec {
     pub fn new(method: http::Method, uri_spec: UriSpec) -> Self {
-        let uri_path_regex = (&uri_spec.path_and_query.path_segments).into();
         RequestSpec {
             method,
             uri_spec,
-            uri_path_regex,
+            operation: None,
         }
     }
 
+    /// Sets the ID of the operation this spec routes to, which is used to answer CORS preflight
+    /// requests, see [`crate::cors`].
+    pub fn with_operation(mut self, operation: ShapeId) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
     /// A measure of how "important" a `RequestSpec` is. The more specific a `RequestSpec` is, the
     /// higher it ranks in importance. Specificity is measured by the number of segments plus the
     /// number of query string literals in its URI pattern, so `/{Bucket}/{Key}?query` is more
@@ -161,13 +169,41 @@ impl RequestSpec {
         self.uri_spec.path_and_query.path_segments.0.len() + self.uri_spec.path_and_query.query_segments.0.len()
     }
 
+    /// The HTTP method.
+    pub(crate) fn method(&self) -> &http::Method {
+        &self.method
+    }
+
+    /// The ID of the operation this spec routes to, if known.
+    pub(crate) fn operation(&self) -> Option<&ShapeId> {
+        self.operation.as_ref()
+    }
+
+    /// The segments of the URI path pattern.
+    pub(crate) fn path_segments(&self) -> &[PathSegment] {
+        &self.uri_spec.path_and_query.path_segments.0
+    }
+
+    /// Matches the request against this spec, evaluating the regular expression of its path.
+    ///
+    /// Routers find the specs matching the path of a request with a
+    /// [`PathTree`](crate::routing::path_tree::PathTree) instead, and then call
+    /// [`RequestSpec::matches_except_path`]; this is the reference they're tested against.
+    #[cfg(test)]
     pub(crate) fn matches<B>(&self, req: &Request<B>) -> Match {
-        if let Some(_host_prefix) = &self.uri_spec.host_prefix {
-            todo!("Look at host prefix");
+        let uri_path_regex: Regex = (&self.uri_spec.path_and_query.path_segments).into();
+        if !uri_path_regex.is_match(req.uri().path()) {
+            return Match::No;
         }
 
-        if !self.uri_path_regex.is_match(req.uri().path()) {
-            return Match::No;
+        self.matches_except_path(req)
+    }
+
+    /// Matches the request against this spec, for a request whose path is already known to match
+    /// the path pattern of the spec.
+    pub(crate) fn matches_except_path<B>(&self, req: &Request<B>) -> Match {
+        if let Some(_host_prefix) = &self.uri_spec.host_prefix {
+            todo!("Look at host prefix");
         }
 
         if self.uri_spec.path_and_query.query_segments.0.is_empty() {
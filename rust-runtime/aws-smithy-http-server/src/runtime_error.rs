@@ -10,4 +10,22 @@
 /// [`crate::protocol::rest_json_1::runtime_error::RuntimeError::InternalFailure`] variant.
 pub struct InternalFailureException;
 
+/// A _protocol-agnostic_ type representing a request rejected because a rate or concurrency limit
+/// was reached, see [`crate::limit`]. Like [`InternalFailureException`], it is converted into
+/// protocol-specific error variants. For example, in the [`crate::protocol::rest_json_1`] protocol,
+/// it is converted to the [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Throttling`]
+/// variant.
+pub struct ThrottlingException;
+
+/// A _protocol-agnostic_ type representing an operation whose response wasn't produced in time,
+/// see [`crate::timeout`]. For example, in the [`crate::protocol::rest_json_1`] protocol, it is
+/// converted to the [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Timeout`] variant.
+pub struct TimeoutException;
+
+/// A _protocol-agnostic_ type representing a request whose body is encoded in a way the server
+/// doesn't support, see [`crate::layer::compression`]. For example, in the
+/// [`crate::protocol::rest_json_1`] protocol, it is converted to the
+/// [`crate::protocol::rest_json_1::runtime_error::RuntimeError::UnsupportedMediaType`] variant.
+pub struct UnsupportedMediaTypeException;
+
 pub const INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE: &str = "invalid HTTP response for `RuntimeError`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues";
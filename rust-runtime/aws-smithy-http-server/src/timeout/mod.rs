@@ -0,0 +1,48 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+//! Provides [`TimeoutPlugin`], an HTTP plugin bounding the time operations take to respond.
+//!
+//! When an operation doesn't produce a response before its timeout elapses, the request is
+//! abandoned and the timeout error of the service's protocol is returned instead: a
+//! `TimeoutException` with a `504 Gateway Timeout` status code. The response carries a
+//! [`RuntimeErrorExtension`](crate::extension::RuntimeErrorExtension) naming the error, which is
+//! how metrics middleware, such as the `DefaultMetricsPlugin` of
+//! `aws-smithy-http-server-metrics` registered before the [`TimeoutPlugin`], record timeouts.
+//!
+//! A [`TimeoutPlugin`] applies the same timeout to every operation. Use [`Scoped`](crate::plugin::Scoped)
+//! or [`filter_by_operation`](crate::plugin::filter_by_operation) to apply different timeouts to
+//! different operations.
+//!
+//! # Example
+//!
+//! ```
+//! # use aws_smithy_http_server::shape_id::ShapeId;
+//! # #[derive(PartialEq)]
+//! # enum Operation { GetPokemonSpecies, GetStorage }
+//! use aws_smithy_http_server::plugin::{filter_by_operation, HttpPlugins};
+//! use aws_smithy_http_server::timeout::TimeoutPlugin;
+//! use std::time::Duration;
+//!
+//! let http_plugins = HttpPlugins::new()
+//!     // Operations must respond within 30 seconds, except `GetStorage`...
+//!     .push(filter_by_operation(
+//!         TimeoutPlugin::new(Duration::from_secs(30)),
+//!         |operation: Operation| operation != Operation::GetStorage,
+//!     ))
+//!     // ...which must respond within 5 seconds.
+//!     .push(filter_by_operation(
+//!         TimeoutPlugin::new(Duration::from_secs(5)),
+//!         |operation: Operation| operation == Operation::GetStorage,
+//!     ));
+//! ```
+
+mod plugin;
+mod service;
+
+pub use plugin::*;
+pub use service::*;
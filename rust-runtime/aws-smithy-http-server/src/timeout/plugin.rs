@@ -0,0 +1,41 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::time::Duration;
+
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, Plugin};
+use crate::service::ServiceShape;
+
+use super::TimeoutService;
+
+/// A [`Plugin`] which applies [`TimeoutService`], with the same timeout, to every operation.
+///
+/// See the [module](crate::timeout) documentation for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeoutPlugin {
+    timeout: Duration,
+}
+
+impl TimeoutPlugin {
+    /// Creates a `TimeoutPlugin` requiring operations to respond within `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for TimeoutPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = TimeoutService<T, Ser::Protocol>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        TimeoutService::new(inner, Op::ID, self.timeout)
+    }
+}
+
+impl HttpMarker for TimeoutPlugin {}
@@ -0,0 +1,199 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [`Service`] and its associated [`Future`] bounding the time an operation takes to respond.
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use tokio::time::Sleep;
+use tower::Service;
+use tracing::warn;
+
+use crate::{body::BoxBody, response::IntoResponse, runtime_error::TimeoutException, shape_id::ShapeId};
+
+pin_project_lite::pin_project! {
+    /// The [`Future`] of [`TimeoutService`].
+    pub struct TimeoutFuture<Fut, P> {
+        #[pin]
+        inner: Fut,
+        #[pin]
+        sleep: Sleep,
+        operation: ShapeId,
+        _protocol: PhantomData<fn(P)>,
+    }
+}
+
+impl<Fut, P, E> Future for TimeoutFuture<Fut, P>
+where
+    Fut: Future<Output = Result<http::Response<BoxBody>, E>>,
+    TimeoutException: IntoResponse<P>,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Poll::Ready(output) = this.inner.poll(cx) {
+            return Poll::Ready(output);
+        }
+        futures_util::ready!(this.sleep.poll(cx));
+        warn!(operation = %this.operation.absolute(), "operation timed out");
+        Poll::Ready(Ok(TimeoutException.into_response()))
+    }
+}
+
+impl<Fut: std::fmt::Debug, P> std::fmt::Debug for TimeoutFuture<Fut, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeoutFuture")
+            .field("inner", &self.inner)
+            .field("sleep", &self.sleep)
+            .field("operation", &self.operation)
+            .finish()
+    }
+}
+
+/// A middleware [`Service`] responding with a protocol-specific timeout error when the inner
+/// service doesn't respond before a timeout elapses.
+///
+/// See the [module](crate::timeout) documentation for more information.
+pub struct TimeoutService<S, P> {
+    inner: S,
+    operation: ShapeId,
+    timeout: Duration,
+    _protocol: PhantomData<fn(P)>,
+}
+
+impl<S, P> TimeoutService<S, P> {
+    /// Creates a `TimeoutService` requiring `inner`, the service of `operation`, to respond within `timeout`.
+    pub fn new(inner: S, operation: ShapeId, timeout: Duration) -> Self {
+        Self {
+            inner,
+            operation,
+            timeout,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S: Clone, P> Clone for TimeoutService<S, P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            operation: self.operation.clone(),
+            timeout: self.timeout,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S: std::fmt::Debug, P> std::fmt::Debug for TimeoutService<S, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimeoutService")
+            .field("inner", &self.inner)
+            .field("operation", &self.operation)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl<B, S, P> Service<http::Request<B>> for TimeoutService<S, P>
+where
+    S: Service<http::Request<B>, Response = http::Response<BoxBody>>,
+    TimeoutException: IntoResponse<P>,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = TimeoutFuture<S::Future, P>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        TimeoutFuture {
+            inner: self.inner.call(request),
+            sleep: tokio::time::sleep(self.timeout),
+            operation: self.operation.clone(),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::extension::RuntimeErrorExtension;
+    use crate::protocol::aws_json_10::AwsJson1_0;
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::rest_xml::RestXml;
+    use crate::protocol::rpc_v2_cbor::RpcV2Cbor;
+
+    const ID: ShapeId = ShapeId::new("namespace#Operation", "namespace", "Operation");
+
+    fn sleeping(
+        duration: Duration,
+    ) -> impl Service<
+        http::Request<()>,
+        Response = http::Response<BoxBody>,
+        Error = Infallible,
+        Future = impl Future<Output = Result<http::Response<BoxBody>, Infallible>>,
+    > + Clone {
+        service_fn(move |_request: http::Request<()>| async move {
+            tokio::time::sleep(duration).await;
+            Ok(http::Response::new(crate::body::empty()))
+        })
+    }
+
+    fn request() -> http::Request<()> {
+        http::Request::get("/").body(()).unwrap()
+    }
+
+    async fn timed_out<P>() -> http::Response<BoxBody>
+    where
+        TimeoutException: IntoResponse<P>,
+    {
+        let svc = TimeoutService::<_, P>::new(sleeping(Duration::from_secs(10)), ID, Duration::from_secs(1));
+        svc.oneshot(request()).await.unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn responds_before_timeout() {
+        let svc = TimeoutService::<_, RestJson1>::new(sleeping(Duration::from_secs(1)), ID, Duration::from_secs(2));
+        let response = svc.oneshot(request()).await.unwrap();
+        assert_eq!(http::StatusCode::OK, response.status());
+        assert!(response.extensions().get::<RuntimeErrorExtension>().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timeout_error_is_protocol_specific() {
+        let start = tokio::time::Instant::now();
+        let response = timed_out::<RestJson1>().await;
+        assert_eq!(Duration::from_secs(1), start.elapsed());
+        assert_eq!(http::StatusCode::GATEWAY_TIMEOUT, response.status());
+        assert_eq!("TimeoutException", response.headers()["X-Amzn-Errortype"]);
+        assert_eq!(
+            "TimeoutException",
+            response.extensions().get::<RuntimeErrorExtension>().unwrap().as_str()
+        );
+
+        for (response, content_type) in [
+            (timed_out::<RestXml>().await, "application/xml"),
+            (timed_out::<AwsJson1_0>().await, "application/x-amz-json-1.0"),
+            (timed_out::<RpcV2Cbor>().await, "application/cbor"),
+        ] {
+            assert_eq!(http::StatusCode::GATEWAY_TIMEOUT, response.status());
+            assert_eq!(content_type, response.headers()[http::header::CONTENT_TYPE]);
+        }
+    }
+}
@@ -21,6 +21,8 @@ pub enum ResponseRejection {
 pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
+    #[error("request body is too large: {0}")]
+    RequestBodyTooLarge(#[from] crate::body::BodyLimitExceeded),
     #[error("request contains invalid value for `Accept` header")]
     NotAcceptable,
     #[error("expected `Content-Type` header not found: {0}")]
@@ -11,6 +11,7 @@ use http::StatusCode;
 
 use super::rejection::{RequestRejection, ResponseRejection};
 
+#[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
 pub enum RuntimeError {
     /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Serialization`]
@@ -28,6 +29,9 @@ pub enum RuntimeError {
     /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Validation`]
     #[error("validation failure: operation input contains data that does not adhere to the modeled constraints: {0}")]
     Validation(String),
+    /// See: [`crate::protocol::rest_json_1::runtime_error::RuntimeError::PayloadTooLarge`]
+    #[error("payload too large: request body exceeds the configured maximum size")]
+    PayloadTooLarge,
 }
 
 impl RuntimeError {
@@ -38,6 +42,7 @@ impl RuntimeError {
             Self::NotAcceptable => "NotAcceptableException",
             Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
             Self::Validation(_) => "ValidationException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
         }
     }
 
@@ -48,6 +53,7 @@ impl RuntimeError {
             Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
         }
     }
 }
@@ -108,6 +114,7 @@ impl From<ResponseRejection> for RuntimeError {
 impl From<RequestRejection> for RuntimeError {
     fn from(err: RequestRejection) -> Self {
         match err {
+            RequestRejection::RequestBodyTooLarge(_) => Self::PayloadTooLarge,
             RequestRejection::ConstraintViolation(reason) => Self::Validation(reason),
             _ => Self::Serialization(crate::Error::new(err)),
         }
@@ -110,6 +110,12 @@ pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
 
+    /// Used when a non-streaming request body is larger than the maximum size configured with
+    /// the `requestBodyMaxBytes` or `requestBodyMaxBytesPerOperation` codegen settings. The body is
+    /// rejected as soon as it exceeds the maximum, without buffering the rest of it.
+    #[error("request body is too large: {0}")]
+    RequestBodyTooLarge(#[from] crate::body::BodyLimitExceeded),
+
     /// Used when the request contained an `Accept` header with a MIME type, and the server cannot
     /// return a response body adhering to that MIME type.
     #[error("request contains invalid value for `Accept` header")]
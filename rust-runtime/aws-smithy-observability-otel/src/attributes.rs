@@ -46,22 +46,21 @@ impl From<AttributesWrap> for Vec<KeyValue> {
         value
             .0
             .into_attributes()
-            .map(|(k, v)| {
-                KeyValue::new(
-                    k,
-                    match v {
-                        AttributeValue::I64(val) => Value::I64(val),
-                        AttributeValue::F64(val) => Value::F64(val),
-                        AttributeValue::String(val) => Value::String(val.into()),
-                        AttributeValue::Bool(val) => Value::Bool(val),
-                        _ => Value::String("UNSUPPORTED ATTRIBUTE VALUE TYPE".into()),
-                    },
-                )
-            })
+            .map(|(k, v)| KeyValue::new(k, value_from_attr_value(v)))
             .collect::<Vec<KeyValue>>()
     }
 }
 
+pub(crate) fn value_from_attr_value(input: AttributeValue) -> Value {
+    match input {
+        AttributeValue::I64(val) => Value::I64(val),
+        AttributeValue::F64(val) => Value::F64(val),
+        AttributeValue::String(val) => Value::String(val.into()),
+        AttributeValue::Bool(val) => Value::Bool(val),
+        _ => Value::String("UNSUPPORTED ATTRIBUTE VALUE TYPE".into()),
+    }
+}
+
 impl From<&[KeyValue]> for AttributesWrap {
     fn from(value: &[KeyValue]) -> Self {
         let mut attrs = Attributes::new();
@@ -21,6 +21,7 @@
 
 pub mod attributes;
 pub mod meter;
+pub mod tracer;
 
 #[cfg(test)]
 mod tests {
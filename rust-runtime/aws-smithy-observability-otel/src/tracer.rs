@@ -0,0 +1,206 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! OpenTelemetry based implementations of the Smithy Observability Tracer traits.
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+
+use crate::attributes::{kv_from_option_attr, value_from_attr_value};
+use aws_smithy_observability::tracer::{ProvideSpan, Span, SpanBuilder, SpanKind, SpanStatus};
+pub use aws_smithy_observability::tracer::{ProvideTracer, Tracer};
+use aws_smithy_observability::{AttributeValue, Attributes, ErrorKind, ObservabilityError, Scope};
+use opentelemetry::trace::{
+    Span as OtelSpanTrait, SpanContext as OtelSpanContext, SpanKind as OtelSpanKind,
+    Status as OtelStatus, TraceContextExt, Tracer as OtelTracerTrait,
+    TracerProvider as OtelTracerProviderTrait,
+};
+use opentelemetry::{Context as OtelContext, KeyValue};
+use opentelemetry_sdk::trace::{
+    Span as OtelSdkSpan, Tracer as OtelSdkTracer, TracerProvider as OtelSdkTracerProvider,
+};
+
+#[derive(Debug)]
+struct SpanWrap {
+    span: Mutex<OtelSdkSpan>,
+    // Cached so that child spans can be parented without locking the span
+    span_context: OtelSpanContext,
+}
+
+impl Scope for SpanWrap {
+    fn end(&self) {
+        self.span.lock().unwrap().end();
+    }
+}
+
+impl Span for SpanWrap {
+    fn set_attribute(&self, key: Cow<'static, str>, value: AttributeValue) {
+        self.span
+            .lock()
+            .unwrap()
+            .set_attribute(KeyValue::new(key, value_from_attr_value(value)));
+    }
+
+    fn add_event(&self, name: Cow<'static, str>, attributes: Option<&Attributes>) {
+        self.span
+            .lock()
+            .unwrap()
+            .add_event(name, kv_from_option_attr(attributes));
+    }
+
+    fn set_status(&self, status: SpanStatus) {
+        let status = match status {
+            SpanStatus::Ok => OtelStatus::Ok,
+            SpanStatus::Error(description) => OtelStatus::Error { description },
+            _ => OtelStatus::Unset,
+        };
+        self.span.lock().unwrap().set_status(status);
+    }
+}
+
+#[derive(Debug)]
+struct TracerWrap(OtelSdkTracer);
+
+impl ProvideSpan for TracerWrap {
+    fn create_span(&self, builder: SpanBuilder<'_>) -> Arc<dyn Span> {
+        // Spans from other providers can't be parents of OTel spans, so they fall back to the
+        // current OTel context like spans without a parent
+        let parent_cx = builder
+            .get_parent()
+            .as_ref()
+            .and_then(|parent| (parent.as_ref() as &dyn Any).downcast_ref::<SpanWrap>())
+            .map(|parent| OtelContext::new().with_remote_span_context(parent.span_context.clone()))
+            .unwrap_or_else(OtelContext::current);
+
+        let kind = match builder.get_kind() {
+            SpanKind::Client => OtelSpanKind::Client,
+            SpanKind::Server => OtelSpanKind::Server,
+            SpanKind::Producer => OtelSpanKind::Producer,
+            SpanKind::Consumer => OtelSpanKind::Consumer,
+            _ => OtelSpanKind::Internal,
+        };
+
+        let span = self
+            .0
+            .span_builder(builder.get_name().clone())
+            .with_kind(kind)
+            .with_attributes(kv_from_option_attr(builder.get_attributes().as_ref()))
+            .start_with_context(&self.0, &parent_cx);
+
+        Arc::new(SpanWrap {
+            span_context: span.span_context().clone(),
+            span: Mutex::new(span),
+        })
+    }
+}
+
+/// An OpenTelemetry based implementation of the AWS SDK's [ProvideTracer] trait
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct OtelTracerProvider {
+    tracer_provider: OtelSdkTracerProvider,
+}
+
+impl OtelTracerProvider {
+    /// Create a new [OtelTracerProvider] from an [OtelSdkTracerProvider].
+    pub fn new(otel_tracer_provider: OtelSdkTracerProvider) -> Self {
+        Self {
+            tracer_provider: otel_tracer_provider,
+        }
+    }
+
+    /// Flush the trace pipeline.
+    pub fn flush(&self) -> Result<(), ObservabilityError> {
+        match self
+            .tracer_provider
+            .force_flush()
+            .into_iter()
+            .find_map(Result::err)
+        {
+            None => Ok(()),
+            Some(err) => Err(ObservabilityError::new(ErrorKind::Other, err)),
+        }
+    }
+}
+
+impl ProvideTracer for OtelTracerProvider {
+    fn get_tracer(&self, scope: &'static str, _attributes: Option<&Attributes>) -> Tracer {
+        Tracer::new(Arc::new(TracerWrap(self.tracer_provider.tracer(scope))))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "AwsSmithyObservabilityOtelProvider"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::OtelTracerProvider;
+    use aws_smithy_observability::tracer::{SpanKind, SpanStatus};
+    use aws_smithy_observability::{AttributeValue, Attributes, TelemetryProvider};
+    use opentelemetry::trace::{SpanKind as OtelSpanKind, Status as OtelStatus};
+    use opentelemetry::{KeyValue, Value};
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use opentelemetry_sdk::trace::TracerProvider;
+
+    #[test]
+    fn spans_are_exported_with_their_parent() {
+        let exporter = InMemorySpanExporter::default();
+        let otel_tp = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let sdk_tp = TelemetryProvider::builder()
+            .tracer_provider(Arc::new(OtelTracerProvider::new(otel_tp)))
+            .build();
+        let tracer = sdk_tp.tracer_provider().get_tracer("TestTracer", None);
+
+        let mut attrs = Attributes::new();
+        attrs.set("rpc.service", AttributeValue::String("TestService".into()));
+        let parent = tracer
+            .create_span("TestService.TestOperation")
+            .set_kind(SpanKind::Client)
+            .set_attributes(attrs)
+            .build();
+        let child = tracer
+            .create_span("attempt")
+            .set_parent(parent.clone())
+            .build();
+        child.set_attribute("attempt".into(), AttributeValue::I64(1));
+        child.add_event("retry".into(), None);
+        child.set_status(SpanStatus::Error("timeout".into()));
+        child.end();
+        parent.set_status(SpanStatus::Ok);
+        parent.end();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        let (child, parent) = (&spans[0], &spans[1]);
+
+        assert_eq!(parent.name, "TestService.TestOperation");
+        assert_eq!(parent.span_kind, OtelSpanKind::Client);
+        assert_eq!(parent.status, OtelStatus::Ok);
+        assert!(parent.attributes.contains(&KeyValue::new(
+            "rpc.service",
+            Value::String("TestService".into())
+        )));
+
+        assert_eq!(child.name, "attempt");
+        assert_eq!(child.span_kind, OtelSpanKind::Internal);
+        assert_eq!(child.parent_span_id, parent.span_context.span_id());
+        assert_eq!(
+            child.span_context.trace_id(),
+            parent.span_context.trace_id()
+        );
+        assert_eq!(child.status, OtelStatus::error("timeout"));
+        assert!(child
+            .attributes
+            .contains(&KeyValue::new("attempt", Value::I64(1))));
+        assert_eq!(child.events.len(), 1);
+        assert_eq!(child.events[0].name, "retry");
+    }
+}
@@ -0,0 +1,46 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+/* Automatically managed default lints */
+#![cfg_attr(docsrs, feature(doc_cfg))]
+/* End of automatically managed default lints */
+#![warn(
+    missing_docs,
+    rustdoc::missing_crate_level_docs,
+    unreachable_pub,
+    rust_2018_idioms
+)]
+
+//! Smithy Observability Prometheus
+//!
+//! A [`ProvideMeter`](aws_smithy_observability::meter::ProvideMeter) that aggregates metrics
+//! in-process and renders them in the Prometheus text exposition format. The payload returned by
+//! [`PrometheusMeterProvider::render`](meter::PrometheusMeterProvider::render) can be served from
+//! any HTTP handler for Prometheus to scrape.
+//!
+//! ```
+//! use aws_smithy_observability::global::set_telemetry_provider;
+//! use aws_smithy_observability::TelemetryProvider;
+//! use aws_smithy_observability_prometheus::meter::{PrometheusMeterProvider, CONTENT_TYPE};
+//! use std::sync::Arc;
+//!
+//! let prometheus = PrometheusMeterProvider::builder()
+//!     .with_buckets("smithy.client.call.duration", vec![0.01, 0.1, 1.0, 10.0])
+//!     .build();
+//! set_telemetry_provider(
+//!     TelemetryProvider::builder()
+//!         .meter_provider(Arc::new(prometheus.clone()))
+//!         .build(),
+//! )
+//! .unwrap();
+//!
+//! // In the handler of the scrape endpoint
+//! let body = prometheus.render();
+//! let content_type = CONTENT_TYPE;
+//! # let _ = (body, content_type);
+//! ```
+
+pub mod meter;
+mod registry;
@@ -0,0 +1,650 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Prometheus based implementations of the Smithy Observability Meter traits.
+
+use crate::registry::{sanitize_name, Aggregation, FamilyDefinition, MetricType, Registry};
+use aws_smithy_observability::instruments::{
+    AsyncInstrumentBuilder, AsyncMeasure, Histogram, InstrumentBuilder, MonotonicCounter,
+    ProvideInstrument, UpDownCounter,
+};
+pub use aws_smithy_observability::meter::{Meter, ProvideMeter};
+use aws_smithy_observability::{Attributes, Context};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+
+/// The content type of the payload returned by [`PrometheusMeterProvider::render`].
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The histogram bucket upper bounds used when none are configured for an instrument.
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// A [ProvideMeter] that aggregates measurements in-process and renders them in the
+/// [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/).
+///
+/// Clones share the same aggregated state, so a clone can be kept to [`render`](Self::render) the
+/// scrape payload after the provider has been handed to a
+/// [`TelemetryProvider`](aws_smithy_observability::TelemetryProvider).
+#[derive(Clone, Debug)]
+pub struct PrometheusMeterProvider {
+    registry: Arc<Registry>,
+    default_buckets: Arc<[f64]>,
+    buckets: Arc<HashMap<String, Arc<[f64]>>>,
+}
+
+impl Default for PrometheusMeterProvider {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl PrometheusMeterProvider {
+    /// Create a new [PrometheusMeterProvider] using the [DEFAULT_BUCKETS] for every histogram.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [PrometheusMeterProviderBuilder].
+    pub fn builder() -> PrometheusMeterProviderBuilder {
+        PrometheusMeterProviderBuilder::default()
+    }
+
+    /// Render the current value of every instrument in the Prometheus text exposition format.
+    ///
+    /// The callbacks of async instruments are invoked before rendering. The result is the body of a
+    /// response to a Prometheus scrape and should be served with the [CONTENT_TYPE] content type.
+    pub fn render(&self) -> String {
+        self.registry.render()
+    }
+}
+
+impl ProvideMeter for PrometheusMeterProvider {
+    // Prometheus has no notion of instrumentation scope, so meters of every scope share the
+    // same metric families
+    fn get_meter(&self, _scope: &'static str, _attributes: Option<&Attributes>) -> Meter {
+        Meter::new(Arc::new(PrometheusMeter {
+            provider: self.clone(),
+        }))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "AwsSmithyObservabilityPrometheusProvider"
+    }
+}
+
+/// Builder for [PrometheusMeterProvider].
+#[derive(Debug)]
+pub struct PrometheusMeterProviderBuilder {
+    default_buckets: Vec<f64>,
+    buckets: HashMap<String, Vec<f64>>,
+}
+
+impl Default for PrometheusMeterProviderBuilder {
+    fn default() -> Self {
+        Self {
+            default_buckets: DEFAULT_BUCKETS.to_vec(),
+            buckets: HashMap::new(),
+        }
+    }
+}
+
+impl PrometheusMeterProviderBuilder {
+    /// Set the histogram bucket upper bounds used by histograms without buckets of their own.
+    ///
+    /// The `+Inf` bucket is always present and doesn't need to be included.
+    pub fn with_default_buckets(mut self, buckets: impl Into<Vec<f64>>) -> Self {
+        self.default_buckets = buckets.into();
+        self
+    }
+
+    /// Set the histogram bucket upper bounds of the histogram named `instrument_name`.
+    ///
+    /// The name is the one the instrument is created with, e.g. `smithy.client.call.duration`.
+    /// The `+Inf` bucket is always present and doesn't need to be included.
+    pub fn with_buckets(
+        mut self,
+        instrument_name: impl Into<String>,
+        buckets: impl Into<Vec<f64>>,
+    ) -> Self {
+        self.buckets.insert(instrument_name.into(), buckets.into());
+        self
+    }
+
+    /// Build the [PrometheusMeterProvider].
+    pub fn build(self) -> PrometheusMeterProvider {
+        PrometheusMeterProvider {
+            registry: Arc::new(Registry::default()),
+            default_buckets: normalize_buckets(self.default_buckets),
+            buckets: Arc::new(
+                self.buckets
+                    .into_iter()
+                    .map(|(name, buckets)| (name, normalize_buckets(buckets)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn normalize_buckets(mut buckets: Vec<f64>) -> Arc<[f64]> {
+    buckets.retain(|bound| bound.is_finite());
+    buckets.sort_by(f64::total_cmp);
+    buckets.dedup();
+    buckets.into()
+}
+
+/// Returns the name of the metric family of an instrument, following the Prometheus naming
+/// conventions for units and counters.
+fn metric_name(name: &str, units: Option<&str>, metric_type: MetricType) -> String {
+    let mut metric_name = sanitize_name(name);
+    let unit_suffix = match units {
+        Some("s") => Some(Cow::Borrowed("seconds")),
+        Some("ms") => Some(Cow::Borrowed("milliseconds")),
+        Some("By") => Some(Cow::Borrowed("bytes")),
+        // Dimensionless and annotation-only units (e.g. `{request}`) don't have a suffix
+        Some("1") | None => None,
+        Some(units) if units.starts_with('{') => None,
+        Some(units) => Some(Cow::Owned(sanitize_name(units))),
+    };
+    if let Some(unit_suffix) = unit_suffix {
+        if !metric_name.ends_with(&format!("_{unit_suffix}")) {
+            metric_name.push('_');
+            metric_name.push_str(&unit_suffix);
+        }
+    }
+    if metric_type == MetricType::Counter && !metric_name.ends_with("_total") {
+        metric_name.push_str("_total");
+    }
+    metric_name
+}
+
+#[derive(Debug)]
+struct PrometheusMeter {
+    provider: PrometheusMeterProvider,
+}
+
+impl PrometheusMeter {
+    fn register(
+        &self,
+        name: &str,
+        description: &Option<Cow<'static, str>>,
+        units: &Option<Cow<'static, str>>,
+        metric_type: MetricType,
+        asynchronous: bool,
+    ) -> Option<String> {
+        let buckets = self
+            .provider
+            .buckets
+            .get(name)
+            .unwrap_or(&self.provider.default_buckets)
+            .clone();
+        self.provider.registry.register(FamilyDefinition {
+            name: metric_name(name, units.as_deref(), metric_type),
+            help: description.clone(),
+            metric_type,
+            asynchronous,
+            buckets,
+        })
+    }
+
+    fn create_async<T: IntoF64 + 'static>(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = T>>, T>,
+        metric_type: MetricType,
+    ) -> Arc<dyn AsyncMeasure<Value = T>> {
+        let name = self.register(
+            builder.get_name(),
+            builder.get_description(),
+            builder.get_units(),
+            metric_type,
+            true,
+        );
+        // The registry owns the callback, so the instrument only holds a weak reference to it
+        let rejected = name.is_none();
+        let measure = Arc::new(AsyncInstrument {
+            name,
+            registry: Arc::downgrade(&self.provider.registry),
+            stopped: Arc::new(AtomicBool::new(false)),
+            _value: PhantomData,
+        });
+        // The measurements of a rejected instrument would be dropped, so its callback is never run
+        if !rejected {
+            let callback = builder.get_callback();
+            let callback_measure = measure.clone();
+            self.provider.registry.register_callback(
+                measure.stopped.clone(),
+                Arc::new(move || callback(callback_measure.as_ref())),
+            );
+        }
+        measure
+    }
+}
+
+impl ProvideInstrument for PrometheusMeter {
+    fn create_gauge(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = f64>>, f64>,
+    ) -> Arc<dyn AsyncMeasure<Value = f64>> {
+        self.create_async(builder, MetricType::Gauge)
+    }
+
+    fn create_up_down_counter(
+        &self,
+        builder: InstrumentBuilder<'_, Arc<dyn UpDownCounter>>,
+    ) -> Arc<dyn UpDownCounter> {
+        let name = self.register(
+            builder.get_name(),
+            builder.get_description(),
+            builder.get_units(),
+            MetricType::Gauge,
+            false,
+        );
+        Arc::new(SyncInstrument {
+            name,
+            registry: self.provider.registry.clone(),
+            aggregation: Aggregation::Sum,
+        })
+    }
+
+    fn create_async_up_down_counter(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = i64>>, i64>,
+    ) -> Arc<dyn AsyncMeasure<Value = i64>> {
+        self.create_async(builder, MetricType::Gauge)
+    }
+
+    fn create_monotonic_counter(
+        &self,
+        builder: InstrumentBuilder<'_, Arc<dyn MonotonicCounter>>,
+    ) -> Arc<dyn MonotonicCounter> {
+        let name = self.register(
+            builder.get_name(),
+            builder.get_description(),
+            builder.get_units(),
+            MetricType::Counter,
+            false,
+        );
+        Arc::new(SyncInstrument {
+            name,
+            registry: self.provider.registry.clone(),
+            aggregation: Aggregation::Sum,
+        })
+    }
+
+    fn create_async_monotonic_counter(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = u64>>, u64>,
+    ) -> Arc<dyn AsyncMeasure<Value = u64>> {
+        self.create_async(builder, MetricType::Counter)
+    }
+
+    fn create_histogram(
+        &self,
+        builder: InstrumentBuilder<'_, Arc<dyn Histogram>>,
+    ) -> Arc<dyn Histogram> {
+        let name = self.register(
+            builder.get_name(),
+            builder.get_description(),
+            builder.get_units(),
+            MetricType::Histogram,
+            false,
+        );
+        Arc::new(SyncInstrument {
+            name,
+            registry: self.provider.registry.clone(),
+            aggregation: Aggregation::Histogram,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SyncInstrument {
+    /// `None` if the instrument was rejected by the registry
+    name: Option<String>,
+    registry: Arc<Registry>,
+    aggregation: Aggregation,
+}
+
+impl SyncInstrument {
+    fn record(&self, value: f64, attributes: Option<&Attributes>) {
+        if let Some(name) = &self.name {
+            self.registry
+                .record(name, self.aggregation, value, attributes);
+        }
+    }
+}
+
+impl MonotonicCounter for SyncInstrument {
+    fn add(&self, value: u64, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        self.record(value as f64, attributes);
+    }
+}
+
+impl UpDownCounter for SyncInstrument {
+    fn add(&self, value: i64, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        self.record(value as f64, attributes);
+    }
+}
+
+impl Histogram for SyncInstrument {
+    fn record(&self, value: f64, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        SyncInstrument::record(self, value, attributes);
+    }
+}
+
+struct AsyncInstrument<T> {
+    /// `None` if the instrument was rejected by the registry
+    name: Option<String>,
+    registry: Weak<Registry>,
+    stopped: Arc<AtomicBool>,
+    _value: PhantomData<fn(T)>,
+}
+
+impl<T> Debug for AsyncInstrument<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncInstrument")
+            .field("name", &self.name)
+            .field("stopped", &self.stopped)
+            .finish()
+    }
+}
+
+impl<T: IntoF64> AsyncMeasure for AsyncInstrument<T> {
+    type Value = T;
+
+    fn record(
+        &self,
+        value: Self::Value,
+        attributes: Option<&Attributes>,
+        _context: Option<&dyn Context>,
+    ) {
+        if let (Some(name), Some(registry)) = (&self.name, self.registry.upgrade()) {
+            registry.record(name, Aggregation::LastValue, value.into_f64(), attributes);
+        }
+    }
+
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+trait IntoF64: Send + Sync {
+    fn into_f64(self) -> f64;
+}
+
+impl IntoF64 for f64 {
+    fn into_f64(self) -> f64 {
+        self
+    }
+}
+
+impl IntoF64 for i64 {
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl IntoF64 for u64 {
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{metric_name, PrometheusMeterProvider};
+    use crate::registry::MetricType;
+    use aws_smithy_observability::instruments::AsyncMeasure;
+    use aws_smithy_observability::meter::ProvideMeter;
+    use aws_smithy_observability::{AttributeValue, Attributes};
+
+    fn attributes(key: &'static str, value: &str) -> Attributes {
+        let mut attributes = Attributes::new();
+        attributes.set(key, AttributeValue::String(value.to_owned()));
+        attributes
+    }
+
+    #[test]
+    fn metric_names_follow_prometheus_conventions() {
+        assert_eq!(
+            "smithy_client_call_duration_seconds",
+            metric_name(
+                "smithy.client.call.duration",
+                Some("s"),
+                MetricType::Histogram
+            )
+        );
+        assert_eq!(
+            "smithy_client_call_attempts_total",
+            metric_name(
+                "smithy.client.call.attempts",
+                Some("{attempt}"),
+                MetricType::Counter
+            )
+        );
+        assert_eq!(
+            "payload_size_bytes",
+            metric_name("payload.size", Some("By"), MetricType::Gauge)
+        );
+        assert_eq!(
+            "requests_total",
+            metric_name("requests_total", Some("1"), MetricType::Counter)
+        );
+    }
+
+    #[test]
+    fn renders_counters_and_up_down_counters() {
+        let provider = PrometheusMeterProvider::new();
+        let meter = provider.get_meter("test", None);
+
+        let counter = meter
+            .create_monotonic_counter("test.requests")
+            .set_description("The number of requests")
+            .build();
+        counter.add(1, Some(&attributes("rpc.method", "GetItem")), None);
+        counter.add(2, Some(&attributes("rpc.method", "GetItem")), None);
+        counter.add(5, Some(&attributes("rpc.method", "PutItem")), None);
+
+        let in_flight = meter.create_up_down_counter("test.in_flight").build();
+        in_flight.add(3, None, None);
+        in_flight.add(-1, None, None);
+
+        assert_eq!(
+            "# TYPE test_in_flight gauge\n\
+             test_in_flight 2\n\
+             # HELP test_requests_total The number of requests\n\
+             # TYPE test_requests_total counter\n\
+             test_requests_total{rpc_method=\"GetItem\"} 3\n\
+             test_requests_total{rpc_method=\"PutItem\"} 5\n",
+            provider.render()
+        );
+    }
+
+    #[test]
+    fn renders_histograms_with_configured_buckets() {
+        let provider = PrometheusMeterProvider::builder()
+            .with_default_buckets(vec![1.0])
+            .with_buckets("test.duration", vec![0.5, 0.1, 0.5])
+            .build();
+        let meter = provider.get_meter("test", None);
+
+        let duration = meter
+            .create_histogram("test.duration")
+            .set_units("s")
+            .build();
+        for value in [0.0625, 0.25, 0.75] {
+            duration.record(value, None, None);
+        }
+        let size = meter.create_histogram("test.size").build();
+        size.record(2.0, Some(&attributes("path", "a\"b")), None);
+
+        assert_eq!(
+            "# TYPE test_duration_seconds histogram\n\
+             test_duration_seconds_bucket{le=\"0.1\"} 1\n\
+             test_duration_seconds_bucket{le=\"0.5\"} 2\n\
+             test_duration_seconds_bucket{le=\"+Inf\"} 3\n\
+             test_duration_seconds_sum 1.0625\n\
+             test_duration_seconds_count 3\n\
+             # TYPE test_size histogram\n\
+             test_size_bucket{path=\"a\\\"b\",le=\"1\"} 0\n\
+             test_size_bucket{path=\"a\\\"b\",le=\"+Inf\"} 1\n\
+             test_size_sum{path=\"a\\\"b\"} 2\n\
+             test_size_count{path=\"a\\\"b\"} 1\n",
+            provider.render()
+        );
+    }
+
+    #[test]
+    fn renders_async_instruments_until_stopped() {
+        let provider = PrometheusMeterProvider::new();
+        let meter = provider.get_meter("test", None);
+
+        let gauge = meter
+            .create_gauge("test.gauge", |measure: &dyn AsyncMeasure<Value = f64>| {
+                measure.record(1.5, None, None);
+            })
+            .build();
+        let _counter = meter
+            .create_async_monotonic_counter(
+                "test.async_counter",
+                |measure: &dyn AsyncMeasure<Value = u64>| {
+                    measure.record(7, None, None);
+                },
+            )
+            .build();
+
+        let expected = "# TYPE test_async_counter_total counter\n\
+                        test_async_counter_total 7\n\
+                        # TYPE test_gauge gauge\n\
+                        test_gauge 1.5\n";
+        assert_eq!(expected, provider.render());
+        // Rendering again reports the current values rather than accumulating them
+        assert_eq!(expected, provider.render());
+
+        gauge.stop();
+        assert_eq!(
+            "# TYPE test_async_counter_total counter\n\
+             test_async_counter_total 7\n",
+            provider.render()
+        );
+    }
+
+    #[test]
+    fn instruments_with_the_same_name_share_a_family() {
+        let provider = PrometheusMeterProvider::new();
+        for _ in 0..2 {
+            let meter = provider.get_meter("test", None);
+            meter
+                .create_monotonic_counter("test.calls")
+                .build()
+                .add(1, None, None);
+        }
+
+        assert_eq!(
+            "# TYPE test_calls_total counter\n\
+             test_calls_total 2\n",
+            provider.render()
+        );
+    }
+
+    #[test]
+    fn instruments_with_the_same_name_and_a_different_type_are_rejected() {
+        let provider = PrometheusMeterProvider::builder()
+            .with_default_buckets(vec![1.0])
+            .build();
+        let meter = provider.get_meter("test", None);
+
+        meter
+            .create_histogram("test.value")
+            .build()
+            .record(0.5, None, None);
+        // Neither a sync nor an async gauge can join the histogram family
+        meter
+            .create_up_down_counter("test.value")
+            .build()
+            .add(5, None, None);
+        let _gauge = meter
+            .create_gauge("test.value", |measure: &dyn AsyncMeasure<Value = f64>| {
+                measure.record(2.0, None, None);
+            })
+            .build();
+        // A sync up-down counter and an async gauge can't share a family either
+        meter
+            .create_up_down_counter("test.in_flight")
+            .build()
+            .add(1, None, None);
+        let _in_flight = meter
+            .create_async_up_down_counter(
+                "test.in_flight",
+                |measure: &dyn AsyncMeasure<Value = i64>| {
+                    measure.record(9, None, None);
+                },
+            )
+            .build();
+
+        assert_eq!(
+            "# TYPE test_in_flight gauge\n\
+             test_in_flight 1\n\
+             # TYPE test_value histogram\n\
+             test_value_bucket{le=\"1\"} 1\n\
+             test_value_bucket{le=\"+Inf\"} 1\n\
+             test_value_sum 0.5\n\
+             test_value_count 1\n",
+            provider.render()
+        );
+    }
+
+    #[test]
+    fn callbacks_can_create_instruments() {
+        let provider = PrometheusMeterProvider::new();
+        let meter = provider.get_meter("test", None);
+
+        let callback_provider = provider.clone();
+        let _outer = meter
+            .create_gauge(
+                "test.outer",
+                move |measure: &dyn AsyncMeasure<Value = f64>| {
+                    measure.record(1.0, None, None);
+                    let _inner = callback_provider
+                        .get_meter("test", None)
+                        .create_gauge("test.inner", |measure: &dyn AsyncMeasure<Value = f64>| {
+                            measure.record(2.0, None, None);
+                        })
+                        .build();
+                },
+            )
+            .build();
+
+        assert_eq!("# TYPE test_outer gauge\ntest_outer 1\n", provider.render());
+    }
+
+    #[test]
+    fn concurrent_renders_include_async_instruments() {
+        let provider = PrometheusMeterProvider::new();
+        let meter = provider.get_meter("test", None);
+        let _gauge = meter
+            .create_gauge("test.gauge", |measure: &dyn AsyncMeasure<Value = f64>| {
+                measure.record(1.0, None, None);
+            })
+            .build();
+
+        let renders: Vec<_> = (0..4)
+            .map(|_| {
+                let provider = provider.clone();
+                std::thread::spawn(move || (0..100).map(|_| provider.render()).collect::<Vec<_>>())
+            })
+            .collect();
+        for render in renders {
+            for rendered in render.join().unwrap() {
+                assert_eq!("# TYPE test_gauge gauge\ntest_gauge 1\n", rendered);
+            }
+        }
+    }
+}
@@ -0,0 +1,393 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! In-process aggregation of measurements and rendering of the Prometheus text exposition format.
+
+use aws_smithy_observability::{AttributeValue, Attributes};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The label names and values identifying a series, sorted by label name.
+type Labels = Vec<(String, String)>;
+
+/// How measurements recorded by an instrument are aggregated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Aggregation {
+    /// Measurements are added to the current value of the series
+    Sum,
+    /// Measurements replace the current value of the series
+    LastValue,
+    /// Measurements are counted in buckets
+    Histogram,
+}
+
+/// The Prometheus metric type of a family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+            MetricType::Histogram => "histogram",
+        }
+    }
+}
+
+/// The definition of a metric family, derived from the instrument that first registered it.
+#[derive(Debug)]
+pub(crate) struct FamilyDefinition {
+    pub(crate) name: String,
+    pub(crate) help: Option<Cow<'static, str>>,
+    pub(crate) metric_type: MetricType,
+    /// True if the family is populated by the callbacks of async instruments
+    pub(crate) asynchronous: bool,
+    /// The upper bounds of the histogram buckets, excluding `+Inf`
+    pub(crate) buckets: Arc<[f64]>,
+}
+
+impl FamilyDefinition {
+    /// Returns true if the instruments of both definitions can share a family.
+    fn is_compatible(&self, other: &FamilyDefinition) -> bool {
+        self.metric_type == other.metric_type && self.asynchronous == other.asynchronous
+    }
+}
+
+#[derive(Debug)]
+enum Series {
+    Value(f64),
+    Histogram {
+        bucket_counts: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+#[derive(Debug)]
+struct Family {
+    definition: FamilyDefinition,
+    series: BTreeMap<Labels, Series>,
+}
+
+type CallbackFn = Arc<dyn Fn() + Send + Sync>;
+
+struct Callback {
+    stopped: Arc<AtomicBool>,
+    callback: CallbackFn,
+}
+
+/// The aggregated state of every instrument created by a meter provider.
+#[derive(Default)]
+pub(crate) struct Registry {
+    families: Mutex<BTreeMap<String, Family>>,
+    callbacks: Mutex<Vec<Callback>>,
+    /// Held for the whole of a [`render`](Registry::render)
+    rendering: Mutex<()>,
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field(
+                "families",
+                &self.families.lock().unwrap().keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Registry {
+    /// Register a metric family, returning its name.
+    ///
+    /// Instruments with the same name share a family. If the family already exists, its
+    /// definition is kept as is. Returns `None` if the existing family has a different metric type
+    /// or is populated by a different kind of instrument, since the instrument can't share it.
+    pub(crate) fn register(&self, definition: FamilyDefinition) -> Option<String> {
+        let name = definition.name.clone();
+        let mut families = self.families.lock().unwrap();
+        match families.get(&name) {
+            Some(family) if !family.definition.is_compatible(&definition) => {
+                tracing::warn!(
+                    metric = %name,
+                    existing_type = family.definition.metric_type.as_str(),
+                    metric_type = definition.metric_type.as_str(),
+                    "a metric family with this name but a different type was already registered; the measurements of this instrument will be dropped"
+                );
+                None
+            }
+            Some(_) => Some(name),
+            None => {
+                families.insert(
+                    name.clone(),
+                    Family {
+                        definition,
+                        series: BTreeMap::new(),
+                    },
+                );
+                Some(name)
+            }
+        }
+    }
+
+    /// Register the callback of an async instrument. It is invoked on every [`render`](Self::render)
+    /// until `stopped` is set.
+    pub(crate) fn register_callback(&self, stopped: Arc<AtomicBool>, callback: CallbackFn) {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .push(Callback { stopped, callback });
+    }
+
+    /// Record a measurement for the family called `name`.
+    pub(crate) fn record(
+        &self,
+        name: &str,
+        aggregation: Aggregation,
+        value: f64,
+        attributes: Option<&Attributes>,
+    ) {
+        let mut families = self.families.lock().unwrap();
+        let Some(family) = families.get_mut(name) else {
+            return;
+        };
+        let labels = labels(attributes);
+        match aggregation {
+            Aggregation::Sum => {
+                let series = family.series.entry(labels).or_insert(Series::Value(0.0));
+                if let Series::Value(current) = series {
+                    *current += value;
+                }
+            }
+            Aggregation::LastValue => {
+                family.series.insert(labels, Series::Value(value));
+            }
+            Aggregation::Histogram => {
+                let buckets = &family.definition.buckets;
+                let series = family
+                    .series
+                    .entry(labels)
+                    .or_insert_with(|| Series::Histogram {
+                        bucket_counts: vec![0; buckets.len()],
+                        sum: 0.0,
+                        count: 0,
+                    });
+                if let Series::Histogram {
+                    bucket_counts,
+                    sum,
+                    count,
+                } = series
+                {
+                    // Buckets are stored non-cumulatively and accumulated when rendered
+                    if let Some(index) = buckets.iter().position(|bound| value <= *bound) {
+                        bucket_counts[index] += 1;
+                    }
+                    *sum += value;
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    /// Collect the values of async instruments and render every metric family in the Prometheus
+    /// text exposition format.
+    pub(crate) fn render(&self) -> String {
+        // Concurrent scrapes are serialized, so that one doesn't render after another discarded the
+        // values of async instruments but before their callbacks observed new ones
+        let _rendering = self.rendering.lock().unwrap();
+
+        // Async instruments report their current values on every collection, so values observed by
+        // earlier collections (e.g. for series that no longer exist) are discarded
+        for family in self.families.lock().unwrap().values_mut() {
+            if family.definition.asynchronous {
+                family.series.clear();
+            }
+        }
+        // Callbacks may create instruments, which registers new callbacks, so they're invoked
+        // without holding the lock
+        let callbacks: Vec<CallbackFn> = {
+            let mut callbacks = self.callbacks.lock().unwrap();
+            callbacks.retain(|callback| !callback.stopped.load(Ordering::SeqCst));
+            callbacks
+                .iter()
+                .map(|callback| callback.callback.clone())
+                .collect()
+        };
+        for callback in callbacks {
+            callback();
+        }
+
+        let mut output = String::new();
+        for family in self.families.lock().unwrap().values() {
+            if family.series.is_empty() {
+                continue;
+            }
+            render_family(&mut output, family).expect("writing to a String can't fail");
+        }
+        output
+    }
+}
+
+fn render_family(output: &mut String, family: &Family) -> fmt::Result {
+    let definition = &family.definition;
+    let name = &definition.name;
+    if let Some(help) = &definition.help {
+        writeln!(output, "# HELP {name} {}", escape_help(help))?;
+    }
+    writeln!(output, "# TYPE {name} {}", definition.metric_type.as_str())?;
+
+    for (labels, series) in &family.series {
+        match series {
+            Series::Value(value) => {
+                writeln!(
+                    output,
+                    "{name}{} {}",
+                    format_labels(labels, None),
+                    format_value(*value)
+                )?;
+            }
+            Series::Histogram {
+                bucket_counts,
+                sum,
+                count,
+            } => {
+                let mut cumulative = 0;
+                for (bound, bucket_count) in definition.buckets.iter().zip(bucket_counts) {
+                    cumulative += bucket_count;
+                    let le = format_value(*bound);
+                    writeln!(
+                        output,
+                        "{name}_bucket{} {cumulative}",
+                        format_labels(labels, Some(&le))
+                    )?;
+                }
+                writeln!(
+                    output,
+                    "{name}_bucket{} {count}",
+                    format_labels(labels, Some("+Inf"))
+                )?;
+                writeln!(
+                    output,
+                    "{name}_sum{} {}",
+                    format_labels(labels, None),
+                    format_value(*sum)
+                )?;
+                writeln!(
+                    output,
+                    "{name}_count{} {count}",
+                    format_labels(labels, None)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn labels(attributes: Option<&Attributes>) -> Labels {
+    let mut labels: Labels = attributes
+        .map(|attributes| {
+            attributes
+                .attributes()
+                .iter()
+                .map(|(key, value)| (sanitize_name(key), label_value(value)))
+                .collect()
+        })
+        .unwrap_or_default();
+    labels.sort();
+    labels
+}
+
+fn label_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::I64(value) => value.to_string(),
+        AttributeValue::F64(value) => format_value(*value),
+        AttributeValue::String(value) => value.clone(),
+        AttributeValue::Bool(value) => value.to_string(),
+        _ => "UNSUPPORTED ATTRIBUTE VALUE TYPE".to_string(),
+    }
+}
+
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    if labels.is_empty() && le.is_none() {
+        return String::new();
+    }
+    let mut formatted = labels
+        .iter()
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label_value(value)))
+        .collect::<Vec<_>>();
+    if let Some(le) = le {
+        formatted.push(format!("le=\"{le}\""));
+    }
+    format!("{{{}}}", formatted.join(","))
+}
+
+/// Formats a sample value the way Prometheus parses it.
+pub(crate) fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Replaces the characters that aren't valid in Prometheus metric and label names with `_`.
+pub(crate) fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_value, sanitize_name};
+
+    #[test]
+    fn sanitizes_names() {
+        assert_eq!(
+            "smithy_client_call_duration",
+            sanitize_name("smithy.client.call.duration")
+        );
+        assert_eq!("_1st_metric", sanitize_name("1st-metric"));
+    }
+
+    #[test]
+    fn formats_values() {
+        assert_eq!("0.005", format_value(0.005));
+        assert_eq!("10", format_value(10.0));
+        assert_eq!("+Inf", format_value(f64::INFINITY));
+        assert_eq!("-Inf", format_value(f64::NEG_INFINITY));
+        assert_eq!("NaN", format_value(f64::NAN));
+    }
+}
@@ -82,4 +82,24 @@ mod tests {
             .build();
         instrument.add(4, None, None);
     }
+
+    #[test]
+    #[serial]
+    fn can_create_spans_with_global_telemetry_provider() {
+        let curr_provider = get_telemetry_provider().unwrap();
+
+        // Use the global provider to create a span with a parent
+        let curr_tracer = curr_provider
+            .tracer_provider()
+            .get_tracer("TestTracer", None);
+        let parent = curr_tracer.create_span("TestParentSpan").build();
+        let span = curr_tracer
+            .create_span("TestSpan")
+            .set_kind(crate::tracer::SpanKind::Client)
+            .set_parent(parent.clone())
+            .build();
+        span.set_status(crate::tracer::SpanStatus::Ok);
+        span.end();
+        parent.end();
+    }
 }
@@ -29,3 +29,6 @@ mod noop;
 mod provider;
 pub use provider::{TelemetryProvider, TelemetryProviderBuilder};
 pub mod instruments;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod tracer;
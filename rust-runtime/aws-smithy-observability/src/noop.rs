@@ -3,8 +3,9 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-//! An noop implementation of the Meter traits
+//! An noop implementation of the Meter and Tracer traits
 
+use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::{fmt::Debug, sync::Arc};
 
@@ -12,9 +13,10 @@ use crate::instruments::{
     AsyncInstrumentBuilder, AsyncMeasure, Histogram, InstrumentBuilder, MonotonicCounter,
     ProvideInstrument, UpDownCounter,
 };
+use crate::tracer::{ProvideSpan, ProvideTracer, Span, SpanBuilder, SpanStatus, Tracer};
 use crate::{
-    attributes::Attributes,
-    context::Context,
+    attributes::{AttributeValue, Attributes},
+    context::{Context, Scope},
     meter::{Meter, ProvideMeter},
 };
 
@@ -109,3 +111,36 @@ impl Histogram for NoopHistogram {
     ) {
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct NoopTracerProvider;
+impl ProvideTracer for NoopTracerProvider {
+    fn get_tracer(&self, _scope: &'static str, _attributes: Option<&Attributes>) -> Tracer {
+        Tracer::new(Arc::new(NoopTracer))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "noop"
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct NoopTracer;
+impl ProvideSpan for NoopTracer {
+    fn create_span(&self, _builder: SpanBuilder<'_>) -> Arc<dyn Span> {
+        Arc::new(NoopSpan)
+    }
+}
+
+#[derive(Debug)]
+struct NoopSpan;
+impl Scope for NoopSpan {
+    fn end(&self) {}
+}
+impl Span for NoopSpan {
+    fn set_attribute(&self, _key: Cow<'static, str>, _value: AttributeValue) {}
+
+    fn add_event(&self, _name: Cow<'static, str>, _attributes: Option<&Attributes>) {}
+
+    fn set_status(&self, _status: SpanStatus) {}
+}
@@ -7,12 +7,17 @@
 
 use std::sync::Arc;
 
-use crate::{meter::ProvideMeter, noop::NoopMeterProvider};
+use crate::{
+    meter::ProvideMeter,
+    noop::{NoopMeterProvider, NoopTracerProvider},
+    tracer::ProvideTracer,
+};
 
 /// A struct to hold the various types of telemetry providers.
 #[non_exhaustive]
 pub struct TelemetryProvider {
     meter_provider: Arc<dyn ProvideMeter + Send + Sync>,
+    tracer_provider: Arc<dyn ProvideTracer + Send + Sync>,
 }
 
 impl TelemetryProvider {
@@ -20,6 +25,7 @@ impl TelemetryProvider {
     pub fn builder() -> TelemetryProviderBuilder {
         TelemetryProviderBuilder {
             meter_provider: Arc::new(NoopMeterProvider),
+            tracer_provider: Arc::new(NoopTracerProvider),
         }
     }
 
@@ -27,6 +33,7 @@ impl TelemetryProvider {
     pub fn noop() -> TelemetryProvider {
         Self {
             meter_provider: Arc::new(NoopMeterProvider),
+            tracer_provider: Arc::new(NoopTracerProvider),
         }
     }
 
@@ -34,16 +41,22 @@ impl TelemetryProvider {
     pub fn meter_provider(&self) -> &(dyn ProvideMeter + Send + Sync) {
         self.meter_provider.as_ref()
     }
+
+    /// Get the set [ProvideTracer]
+    pub fn tracer_provider(&self) -> &(dyn ProvideTracer + Send + Sync) {
+        self.tracer_provider.as_ref()
+    }
 }
 
-// If we choose to expand our Telemetry provider and make Logging and Tracing
-// configurable at some point in the future we can do that by adding default
-// logger_provider and tracer_providers based on `tracing` to maintain backwards
-// compatibilty with what we have today.
+// Spans are always emitted through `tracing` as well, so the default tracer_provider is a noop.
+// If we choose to make Logging configurable at some point in the future we can do that by adding
+// a default logger_provider based on `tracing` to maintain backwards compatibilty with what we
+// have today.
 impl Default for TelemetryProvider {
     fn default() -> Self {
         Self {
             meter_provider: Arc::new(NoopMeterProvider),
+            tracer_provider: Arc::new(NoopTracerProvider),
         }
     }
 }
@@ -52,6 +65,7 @@ impl Default for TelemetryProvider {
 #[non_exhaustive]
 pub struct TelemetryProviderBuilder {
     meter_provider: Arc<dyn ProvideMeter + Send + Sync>,
+    tracer_provider: Arc<dyn ProvideTracer + Send + Sync>,
 }
 
 impl TelemetryProviderBuilder {
@@ -61,10 +75,17 @@ impl TelemetryProviderBuilder {
         self
     }
 
+    /// Set the [ProvideTracer].
+    pub fn tracer_provider(mut self, tracer_provider: Arc<impl ProvideTracer + 'static>) -> Self {
+        self.tracer_provider = tracer_provider;
+        self
+    }
+
     /// Build the [TelemetryProvider].
     pub fn build(self) -> TelemetryProvider {
         TelemetryProvider {
             meter_provider: self.meter_provider,
+            tracer_provider: self.tracer_provider,
         }
     }
 }
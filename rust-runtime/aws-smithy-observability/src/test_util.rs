@@ -0,0 +1,538 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An in-memory [ProvideMeter] implementation for asserting on emitted metrics in tests.
+//!
+//! ```
+//! use aws_smithy_observability::test_util::InMemoryMeterProvider;
+//! use aws_smithy_observability::meter::ProvideMeter;
+//! use aws_smithy_observability::{AttributeValue, Attributes};
+//!
+//! let provider = InMemoryMeterProvider::new();
+//! let histogram = provider
+//!     .get_meter("test", None)
+//!     .create_histogram("smithy.client.call.duration")
+//!     .build();
+//!
+//! let mut attributes = Attributes::new();
+//! attributes.set("rpc.method", AttributeValue::String("GetObject".into()));
+//! histogram.record(0.5, Some(&attributes), None);
+//! histogram.record(1.5, Some(&attributes), None);
+//! histogram.record(4.0, None, None);
+//!
+//! let query = provider
+//!     .query("smithy.client.call.duration")
+//!     .for_operation("GetObject");
+//! assert_eq!(2, query.count());
+//! assert_eq!(2.0, query.sum());
+//! ```
+
+use crate::instruments::{
+    AsyncInstrumentBuilder, AsyncMeasure, Histogram, InstrumentBuilder, MonotonicCounter,
+    ProvideInstrument, UpDownCounter,
+};
+use crate::meter::{Meter, ProvideMeter};
+use crate::{AttributeValue, Attributes, Context};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The kind of instrument that recorded a [Measurement].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstrumentKind {
+    /// A gauge
+    Gauge,
+    /// An [UpDownCounter]
+    UpDownCounter,
+    /// An async up-down counter
+    AsyncUpDownCounter,
+    /// A [MonotonicCounter]
+    MonotonicCounter,
+    /// An async monotonic counter
+    AsyncMonotonicCounter,
+    /// A [Histogram]
+    Histogram,
+}
+
+/// A single value recorded by an instrument.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    scope: &'static str,
+    name: Cow<'static, str>,
+    kind: InstrumentKind,
+    value: f64,
+    attributes: Attributes,
+}
+
+impl Measurement {
+    /// The scope of the meter that created the instrument.
+    pub fn scope(&self) -> &'static str {
+        self.scope
+    }
+
+    /// The name of the instrument.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The kind of the instrument.
+    pub fn kind(&self) -> InstrumentKind {
+        self.kind
+    }
+
+    /// The recorded value. Integer values are converted to [f64].
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The attributes the value was recorded with.
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    fn is_same_series(&self, other: &Measurement) -> bool {
+        self.scope == other.scope
+            && self.name == other.name
+            && self.attributes.attributes() == other.attributes.attributes()
+    }
+}
+
+type Measurements = Arc<Mutex<Vec<Measurement>>>;
+type Callbacks = Arc<Mutex<Vec<Arc<dyn Fn() + Send + Sync>>>>;
+
+/// A [ProvideMeter] that records every measurement in memory.
+///
+/// Clones share the same recorded measurements, so a clone can be set as the meter provider of a
+/// [TelemetryProvider](crate::TelemetryProvider) while the original is used for assertions.
+///
+/// The callbacks of async instruments (e.g. gauges) are only invoked by [`collect`](Self::collect).
+#[derive(Clone, Default)]
+pub struct InMemoryMeterProvider {
+    measurements: Measurements,
+    callbacks: Callbacks,
+}
+
+impl fmt::Debug for InMemoryMeterProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryMeterProvider")
+            .field("measurements", &self.measurements)
+            .field("callbacks", &self.callbacks.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl InMemoryMeterProvider {
+    /// Create a new, empty `InMemoryMeterProvider`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Invoke the callbacks of all async instruments that haven't been stopped, recording the
+    /// values they observe.
+    pub fn collect(&self) {
+        // Callbacks may create instruments, which registers new callbacks, so don't hold the lock
+        // while invoking them.
+        let callbacks = self.callbacks.lock().unwrap().clone();
+        for callback in callbacks {
+            callback();
+        }
+    }
+
+    /// All measurements recorded so far, in the order they were recorded.
+    pub fn measurements(&self) -> Vec<Measurement> {
+        self.measurements.lock().unwrap().clone()
+    }
+
+    /// Query the measurements recorded by instruments called `name`.
+    pub fn query(&self, name: &str) -> MeasurementQuery {
+        MeasurementQuery {
+            measurements: self
+                .measurements()
+                .into_iter()
+                .filter(|m| m.name == name)
+                .collect(),
+        }
+    }
+
+    /// Discard all measurements recorded so far.
+    pub fn clear(&self) {
+        self.measurements.lock().unwrap().clear();
+    }
+}
+
+impl ProvideMeter for InMemoryMeterProvider {
+    fn get_meter(&self, scope: &'static str, _attributes: Option<&Attributes>) -> Meter {
+        Meter::new(Arc::new(InMemoryMeter {
+            scope,
+            measurements: self.measurements.clone(),
+            callbacks: self.callbacks.clone(),
+        }))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "in-memory"
+    }
+}
+
+/// A filtered set of [Measurement]s, created by [InMemoryMeterProvider::query].
+#[derive(Clone, Debug)]
+pub struct MeasurementQuery {
+    measurements: Vec<Measurement>,
+}
+
+impl MeasurementQuery {
+    /// Only keep the measurements that were recorded with the attribute `key` set to `value`.
+    pub fn with_attribute(mut self, key: &str, value: impl Into<AttributeValue>) -> Self {
+        let value = value.into();
+        self.measurements
+            .retain(|m| m.attributes.get(key) == Some(&value));
+        self
+    }
+
+    /// Only keep the measurements that were recorded for the operation called `operation`, as
+    /// identified by the `rpc.method` attribute.
+    pub fn for_operation(self, operation: &str) -> Self {
+        self.with_attribute("rpc.method", AttributeValue::String(operation.into()))
+    }
+
+    /// The matching measurements, in the order they were recorded.
+    pub fn measurements(&self) -> &[Measurement] {
+        &self.measurements
+    }
+
+    /// The values of the matching measurements, in the order they were recorded.
+    pub fn values(&self) -> Vec<f64> {
+        self.measurements.iter().map(Measurement::value).collect()
+    }
+
+    /// The number of matching measurements.
+    pub fn count(&self) -> usize {
+        self.measurements.len()
+    }
+
+    /// The sum of the values of the matching measurements.
+    ///
+    /// Async counters observe cumulative totals, so only the most recent observation of each
+    /// attribute set is counted for them.
+    pub fn sum(&self) -> f64 {
+        let mut latest: Vec<&Measurement> = Vec::new();
+        let mut sum = 0.0;
+        for measurement in &self.measurements {
+            match measurement.kind {
+                InstrumentKind::AsyncMonotonicCounter | InstrumentKind::AsyncUpDownCounter => {
+                    match latest.iter_mut().find(|m| m.is_same_series(measurement)) {
+                        Some(previous) => *previous = measurement,
+                        None => latest.push(measurement),
+                    }
+                }
+                _ => sum += measurement.value,
+            }
+        }
+        sum + latest.iter().map(|m| m.value).sum::<f64>()
+    }
+
+    /// The value of the most recently recorded matching measurement.
+    pub fn last(&self) -> Option<f64> {
+        self.measurements.last().map(Measurement::value)
+    }
+}
+
+struct InMemoryMeter {
+    scope: &'static str,
+    measurements: Measurements,
+    callbacks: Callbacks,
+}
+
+impl fmt::Debug for InMemoryMeter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryMeter")
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+struct Recorder {
+    scope: &'static str,
+    name: Cow<'static, str>,
+    kind: InstrumentKind,
+    measurements: Measurements,
+    stopped: AtomicBool,
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("scope", &self.scope)
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl Recorder {
+    fn record(&self, value: f64, attributes: Option<&Attributes>) {
+        if self.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        self.measurements.lock().unwrap().push(Measurement {
+            scope: self.scope,
+            name: self.name.clone(),
+            kind: self.kind,
+            value,
+            attributes: attributes.cloned().unwrap_or_default(),
+        });
+    }
+}
+
+impl InMemoryMeter {
+    fn recorder(&self, name: Cow<'static, str>, kind: InstrumentKind) -> Arc<Recorder> {
+        Arc::new(Recorder {
+            scope: self.scope,
+            name,
+            kind,
+            measurements: self.measurements.clone(),
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn register<T>(
+        &self,
+        recorder: Arc<Recorder>,
+        callback: Arc<dyn Fn(&dyn AsyncMeasure<Value = T>) + Send + Sync>,
+    ) -> Arc<dyn AsyncMeasure<Value = T>>
+    where
+        T: IntoF64 + 'static,
+    {
+        let measure = Arc::new(AsyncRecorder::<T>(recorder, std::marker::PhantomData));
+        let registered = measure.clone();
+        self.callbacks
+            .lock()
+            .unwrap()
+            .push(Arc::new(move || callback(registered.as_ref())));
+        measure
+    }
+}
+
+impl ProvideInstrument for InMemoryMeter {
+    fn create_gauge(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = f64>>, f64>,
+    ) -> Arc<dyn AsyncMeasure<Value = f64>> {
+        let recorder = self.recorder(builder.get_name().clone(), InstrumentKind::Gauge);
+        self.register(recorder, builder.get_callback())
+    }
+
+    fn create_up_down_counter(
+        &self,
+        builder: InstrumentBuilder<'_, Arc<dyn UpDownCounter>>,
+    ) -> Arc<dyn UpDownCounter> {
+        self.recorder(builder.get_name().clone(), InstrumentKind::UpDownCounter)
+    }
+
+    fn create_async_up_down_counter(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = i64>>, i64>,
+    ) -> Arc<dyn AsyncMeasure<Value = i64>> {
+        let recorder = self.recorder(
+            builder.get_name().clone(),
+            InstrumentKind::AsyncUpDownCounter,
+        );
+        self.register(recorder, builder.get_callback())
+    }
+
+    fn create_monotonic_counter(
+        &self,
+        builder: InstrumentBuilder<'_, Arc<dyn MonotonicCounter>>,
+    ) -> Arc<dyn MonotonicCounter> {
+        self.recorder(builder.get_name().clone(), InstrumentKind::MonotonicCounter)
+    }
+
+    fn create_async_monotonic_counter(
+        &self,
+        builder: AsyncInstrumentBuilder<'_, Arc<dyn AsyncMeasure<Value = u64>>, u64>,
+    ) -> Arc<dyn AsyncMeasure<Value = u64>> {
+        let recorder = self.recorder(
+            builder.get_name().clone(),
+            InstrumentKind::AsyncMonotonicCounter,
+        );
+        self.register(recorder, builder.get_callback())
+    }
+
+    fn create_histogram(
+        &self,
+        builder: InstrumentBuilder<'_, Arc<dyn Histogram>>,
+    ) -> Arc<dyn Histogram> {
+        self.recorder(builder.get_name().clone(), InstrumentKind::Histogram)
+    }
+}
+
+impl Histogram for Recorder {
+    fn record(&self, value: f64, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        Recorder::record(self, value, attributes);
+    }
+}
+
+impl MonotonicCounter for Recorder {
+    fn add(&self, value: u64, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        self.record(value.into_f64(), attributes);
+    }
+}
+
+impl UpDownCounter for Recorder {
+    fn add(&self, value: i64, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        self.record(value.into_f64(), attributes);
+    }
+}
+
+trait IntoF64: Send + Sync + fmt::Debug {
+    fn into_f64(self) -> f64;
+}
+
+impl IntoF64 for f64 {
+    fn into_f64(self) -> f64 {
+        self
+    }
+}
+
+impl IntoF64 for i64 {
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl IntoF64 for u64 {
+    fn into_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+#[derive(Debug)]
+struct AsyncRecorder<T>(Arc<Recorder>, std::marker::PhantomData<fn(T)>);
+
+impl<T: IntoF64> AsyncMeasure for AsyncRecorder<T> {
+    type Value = T;
+
+    fn record(&self, value: T, attributes: Option<&Attributes>, _context: Option<&dyn Context>) {
+        self.0.record(value.into_f64(), attributes);
+    }
+
+    fn stop(&self) {
+        self.0.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InMemoryMeterProvider, InstrumentKind};
+    use crate::meter::ProvideMeter;
+    use crate::{AttributeValue, Attributes};
+
+    fn attributes(method: &str) -> Attributes {
+        let mut attributes = Attributes::new();
+        attributes.set("rpc.method", AttributeValue::String(method.into()));
+        attributes
+    }
+
+    #[test]
+    fn records_sync_instruments() {
+        let provider = InMemoryMeterProvider::new();
+        let meter = provider.get_meter("test-scope", None);
+        let counter = meter.create_monotonic_counter("counter").build();
+        let up_down = meter.create_up_down_counter("up_down").build();
+        let histogram = meter.create_histogram("histogram").build();
+
+        counter.add(2, Some(&attributes("GetObject")), None);
+        counter.add(3, Some(&attributes("PutObject")), None);
+        up_down.add(-4, None, None);
+        histogram.record(0.25, Some(&attributes("GetObject")), None);
+
+        assert_eq!(4, provider.measurements().len());
+        let counters = provider.query("counter");
+        assert_eq!(5.0, counters.sum());
+        assert_eq!(2.0, counters.clone().for_operation("GetObject").sum());
+        assert_eq!(
+            InstrumentKind::MonotonicCounter,
+            counters.measurements()[0].kind()
+        );
+        assert_eq!("test-scope", counters.measurements()[0].scope());
+        assert_eq!(Some(-4.0), provider.query("up_down").last());
+        assert_eq!(vec![0.25], provider.query("histogram").values());
+        assert_eq!(0, provider.query("missing").count());
+
+        provider.clear();
+        assert!(provider.measurements().is_empty());
+    }
+
+    #[test]
+    fn records_async_instruments_on_collect() {
+        let provider = InMemoryMeterProvider::new();
+        let meter = provider.get_meter("test-scope", None);
+        let gauge = meter
+            .create_gauge("gauge", |measure| {
+                measure.record(1.5, Some(&attributes("GetObject")), None)
+            })
+            .build();
+        let _counter = meter
+            .create_async_monotonic_counter("async_counter", |measure| {
+                measure.record(7, None, None)
+            })
+            .build();
+
+        assert!(provider.measurements().is_empty());
+        provider.collect();
+        provider.collect();
+        assert_eq!(vec![1.5, 1.5], provider.query("gauge").values());
+        assert_eq!(7.0, provider.query("async_counter").sum());
+
+        gauge.stop();
+        provider.collect();
+        assert_eq!(2, provider.query("gauge").count());
+        assert_eq!(3, provider.query("async_counter").count());
+    }
+
+    #[test]
+    fn sums_latest_observation_of_each_async_counter_series() {
+        let provider = InMemoryMeterProvider::new();
+        let meter = provider.get_meter("test-scope", None);
+        let total = std::sync::atomic::AtomicU64::new(0);
+        let _counter = meter
+            .create_async_monotonic_counter("async_counter", move |measure| {
+                let total = total.fetch_add(5, std::sync::atomic::Ordering::SeqCst) + 5;
+                measure.record(total, Some(&attributes("GetObject")), None);
+                measure.record(1, Some(&attributes("PutObject")), None);
+            })
+            .build();
+
+        provider.collect();
+        provider.collect();
+        let query = provider.query("async_counter");
+        assert_eq!(4, query.count());
+        assert_eq!(11.0, query.sum());
+        assert_eq!(10.0, query.for_operation("GetObject").sum());
+    }
+
+    #[test]
+    fn callbacks_can_create_instruments() {
+        let provider = InMemoryMeterProvider::new();
+        let meter = provider.get_meter("test-scope", None);
+        let inner_provider = provider.clone();
+        let _gauge = meter
+            .create_gauge("gauge", move |measure| {
+                let _ = inner_provider
+                    .get_meter("test-scope", None)
+                    .create_gauge("inner_gauge", |measure| measure.record(2.0, None, None))
+                    .build();
+                measure.record(1.0, None, None)
+            })
+            .build();
+
+        provider.collect();
+        assert_eq!(vec![1.0], provider.query("gauge").values());
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Traces record the path of a request through a system as a tree of timed [Span]s.
+
+use crate::attributes::{AttributeValue, Attributes};
+use crate::context::Scope;
+use std::any::Any;
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
+
+/// Provides named instances of [Tracer].
+pub trait ProvideTracer: Send + Sync + Debug {
+    /// Get or create a named [Tracer].
+    fn get_tracer(&self, scope: &'static str, attributes: Option<&Attributes>) -> Tracer;
+
+    /// Returns the name of this provider implementation.
+    /// This is used for feature tracking without requiring type imports.
+    fn provider_name(&self) -> &'static str {
+        "unknown"
+    }
+}
+
+/// The entry point to creating spans.
+#[derive(Clone)]
+pub struct Tracer {
+    pub(crate) span_provider: Arc<dyn ProvideSpan + Send + Sync>,
+}
+
+impl Tracer {
+    /// Create a new [Tracer] from a [ProvideSpan]
+    pub fn new(span_provider: Arc<dyn ProvideSpan + Send + Sync>) -> Self {
+        Tracer { span_provider }
+    }
+
+    /// Create a new [Span].
+    pub fn create_span(&self, name: impl Into<Cow<'static, str>>) -> SpanBuilder<'_> {
+        SpanBuilder::new(self, name.into())
+    }
+}
+
+impl Debug for Tracer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tracer")
+            .field("span_provider", &self.span_provider)
+            .finish()
+    }
+}
+
+/// The trait that telemetry providers implement to create [Span]s.
+pub trait ProvideSpan: Send + Sync + Debug {
+    /// Start a new [Span] from the given builder.
+    fn create_span(&self, builder: SpanBuilder<'_>) -> Arc<dyn Span>;
+}
+
+/// Configuration for starting a [Span].
+#[non_exhaustive]
+pub struct SpanBuilder<'a> {
+    span_provider: &'a dyn ProvideSpan,
+    name: Cow<'static, str>,
+    kind: SpanKind,
+    attributes: Option<Attributes>,
+    parent: Option<Arc<dyn Span>>,
+}
+
+impl<'a> SpanBuilder<'a> {
+    /// Create a new span builder
+    pub(crate) fn new(tracer: &'a Tracer, name: Cow<'static, str>) -> Self {
+        SpanBuilder {
+            span_provider: tracer.span_provider.as_ref(),
+            name,
+            kind: SpanKind::default(),
+            attributes: None,
+            parent: None,
+        }
+    }
+
+    /// Get the name.
+    pub fn get_name(&self) -> &Cow<'static, str> {
+        &self.name
+    }
+
+    /// Set the [SpanKind].
+    pub fn set_kind(mut self, kind: SpanKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Get the [SpanKind].
+    pub fn get_kind(&self) -> SpanKind {
+        self.kind
+    }
+
+    /// Set the attributes the span starts with.
+    pub fn set_attributes(mut self, attributes: Attributes) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+
+    /// Get the attributes the span starts with.
+    pub fn get_attributes(&self) -> &Option<Attributes> {
+        &self.attributes
+    }
+
+    /// Set the parent of the span. Without a parent, the provider decides which span (if any)
+    /// the new span belongs to.
+    pub fn set_parent(mut self, parent: Arc<dyn Span>) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Get the parent of the span.
+    pub fn get_parent(&self) -> &Option<Arc<dyn Span>> {
+        &self.parent
+    }
+
+    /// Start the [Span].
+    pub fn build(self) -> Arc<dyn Span> {
+        self.span_provider.create_span(self)
+    }
+}
+
+/// The relationship of a [Span] to the other spans of a trace.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpanKind {
+    /// An operation internal to an application
+    #[default]
+    Internal,
+    /// A request to a remote service
+    Client,
+    /// The handling of a request from a remote client
+    Server,
+    /// The sending of a message to a broker
+    Producer,
+    /// The processing of a message from a broker
+    Consumer,
+}
+
+/// The outcome of the work represented by a [Span].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SpanStatus {
+    /// The outcome wasn't set
+    #[default]
+    Unset,
+    /// The work completed successfully
+    Ok,
+    /// The work failed, with a description of the error
+    Error(Cow<'static, str>),
+}
+
+/// A timed unit of work. The span ends when [Scope::end] is called.
+///
+/// Spans are `Any` so that telemetry providers can downcast the parent of a [SpanBuilder] back to
+/// their own span type.
+pub trait Span: Scope + Any + Send + Sync + Debug {
+    /// Set an attribute.
+    fn set_attribute(&self, key: Cow<'static, str>, value: AttributeValue);
+
+    /// Record an event that happened during the span.
+    fn add_event(&self, name: Cow<'static, str>, attributes: Option<&Attributes>);
+
+    /// Set the [SpanStatus].
+    fn set_status(&self, status: SpanStatus);
+}
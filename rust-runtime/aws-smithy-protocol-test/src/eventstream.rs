@@ -0,0 +1,364 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::{pretty_comparison, validate_body, MediaType, ProtocolTestFailure};
+use aws_smithy_eventstream::frame::read_message_from;
+use aws_smithy_types::event_stream::{HeaderValue, Message};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Header carrying the signature of a message that wraps a signed message in its payload
+const CHUNK_SIGNATURE: &str = ":chunk-signature";
+const CONTENT_TYPE: &str = ":content-type";
+
+fn decode_messages(mut body: &[u8]) -> Result<Vec<Message>, String> {
+    let mut messages = Vec::new();
+    while !body.is_empty() {
+        messages.push(read_message_from(&mut body).map_err(|err| err.to_string())?);
+    }
+    Ok(messages)
+}
+
+fn is_signed(message: &Message) -> bool {
+    message
+        .headers()
+        .iter()
+        .any(|header| header.name().as_str() == CHUNK_SIGNATURE)
+}
+
+fn header_map(message: &Message) -> BTreeMap<&str, &HeaderValue> {
+    message
+        .headers()
+        .iter()
+        .map(|header| (header.name().as_str(), header.value()))
+        .collect()
+}
+
+/// Renders the headers and payload of a message, one header per line, for diagnostics
+fn render(message: &Message) -> String {
+    let mut out = String::new();
+    for (name, value) in header_map(message) {
+        writeln!(out, "{name}: {value:?}").unwrap();
+    }
+    match std::str::from_utf8(message.payload()) {
+        Ok(payload) => writeln!(out, "payload: {payload}").unwrap(),
+        Err(_) => writeln!(
+            out,
+            "payload (base64): {}",
+            base64_simd::STANDARD.encode_to_string(message.payload())
+        )
+        .unwrap(),
+    }
+    out
+}
+
+fn message_mismatch(
+    index: usize,
+    expected: &Message,
+    actual: &Message,
+    reason: &str,
+) -> ProtocolTestFailure {
+    ProtocolTestFailure::BodyDidNotMatch {
+        comparison: pretty_comparison(&render(expected), &render(actual)),
+        hint: format!("event stream message {index}: {reason}"),
+    }
+}
+
+fn compare_payloads(
+    index: usize,
+    expected: &Message,
+    actual: &Message,
+) -> Result<(), ProtocolTestFailure> {
+    let content_type = header_map(expected)
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.as_string().ok())
+        .map(|value| value.as_str().to_string());
+    let media_type = content_type
+        .as_deref()
+        .map(MediaType::from)
+        .unwrap_or_else(|| MediaType::Other("application/octet-stream".to_string()));
+    let expected_payload = expected.payload().as_ref();
+    let actual_payload = actual.payload().as_ref();
+    let result = match (media_type.clone(), std::str::from_utf8(expected_payload)) {
+        // binary payloads are expected base64 encoded, like binary bodies
+        (MediaType::Cbor | MediaType::EventStream, _) => validate_body(
+            actual_payload,
+            &base64_simd::STANDARD.encode_to_string(expected_payload),
+            media_type,
+        ),
+        (_, Ok(expected_payload)) if std::str::from_utf8(actual_payload).is_ok() => {
+            validate_body(actual_payload, expected_payload, media_type)
+        }
+        _ if expected_payload == actual_payload => Ok(()),
+        _ => Err(ProtocolTestFailure::BodyDidNotMatch {
+            comparison: pretty_comparison(&render(expected), &render(actual)),
+            hint: "binary payloads differ".to_string(),
+        }),
+    };
+    result.map_err(|failure| match failure {
+        ProtocolTestFailure::BodyDidNotMatch { comparison, hint } => {
+            ProtocolTestFailure::BodyDidNotMatch {
+                comparison,
+                hint: format!("event stream message {index}: payloads differ\n{hint}"),
+            }
+        }
+        other => other,
+    })
+}
+
+fn compare_messages(
+    index: usize,
+    expected: &Message,
+    actual: &Message,
+) -> Result<(), ProtocolTestFailure> {
+    if is_signed(expected) && is_signed(actual) {
+        // Signatures and dates of signed messages aren't deterministic: only compare the header
+        // names, and then the messages they wrap.
+        let expected_names: Vec<_> = header_map(expected).into_keys().collect();
+        let actual_names: Vec<_> = header_map(actual).into_keys().collect();
+        if expected_names != actual_names {
+            return Err(message_mismatch(
+                index,
+                expected,
+                actual,
+                "headers of the signed messages differ",
+            ));
+        }
+        return match (expected.payload().is_empty(), actual.payload().is_empty()) {
+            (true, true) => Ok(()),
+            (false, false) => {
+                let decode = |message: &Message| {
+                    read_message_from(message.payload().as_ref()).map_err(|err| {
+                        ProtocolTestFailure::InvalidBodyFormat {
+                            expected: "a signed event stream message".to_string(),
+                            found: err.to_string(),
+                        }
+                    })
+                };
+                compare_messages(index, &decode(expected)?, &decode(actual)?)
+            }
+            _ => Err(message_mismatch(
+                index,
+                expected,
+                actual,
+                "only one of the signed messages wraps a message",
+            )),
+        };
+    }
+    if header_map(expected) != header_map(actual) {
+        return Err(message_mismatch(index, expected, actual, "headers differ"));
+    }
+    compare_payloads(index, expected, actual)
+}
+
+/// Compares two event stream bodies, message by message.
+///
+/// Headers are compared regardless of their order, and payloads are compared according to the
+/// media type of their `:content-type` header. For signed messages, only the names of the headers
+/// of the signature are compared, since signatures aren't deterministic.
+pub(crate) fn try_event_stream_eq(
+    actual_body: &[u8],
+    expected_body: &str,
+) -> Result<(), ProtocolTestFailure> {
+    let expected_body = base64_simd::STANDARD
+        .decode_to_vec(expected_body)
+        .expect("smithy protocol test `body` property is not properly base64 encoded");
+    let expected = decode_messages(&expected_body)
+        .expect("smithy protocol test `body` property is not a valid event stream");
+    let actual =
+        decode_messages(actual_body).map_err(|err| ProtocolTestFailure::InvalidBodyFormat {
+            expected: "event stream".to_owned(),
+            found: err,
+        })?;
+    if expected.len() != actual.len() {
+        let render_all = |messages: &[Message]| {
+            messages
+                .iter()
+                .map(render)
+                .collect::<Vec<_>>()
+                .join("---\n")
+        };
+        return Err(ProtocolTestFailure::BodyDidNotMatch {
+            comparison: pretty_comparison(&render_all(&expected), &render_all(&actual)),
+            hint: format!(
+                "expected {} event stream messages, found {}",
+                expected.len(),
+                actual.len()
+            ),
+        });
+    }
+    for (index, (expected, actual)) in expected.iter().zip(&actual).enumerate() {
+        compare_messages(index, expected, actual)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{validate_body, MediaType, ProtocolTestFailure};
+    use aws_smithy_eventstream::frame::write_message_to;
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+
+    fn event(event_type: &'static str, content_type: &'static str, payload: &[u8]) -> Message {
+        Message::new(payload.to_vec())
+            .add_header(Header::new(
+                ":message-type",
+                HeaderValue::String("event".into()),
+            ))
+            .add_header(Header::new(
+                ":event-type",
+                HeaderValue::String(event_type.into()),
+            ))
+            .add_header(Header::new(
+                ":content-type",
+                HeaderValue::String(content_type.into()),
+            ))
+    }
+
+    fn signed(message: Option<&Message>, signature: &[u8]) -> Message {
+        Message::new(message.map(encode).unwrap_or_default())
+            .add_header(Header::new(
+                ":chunk-signature",
+                HeaderValue::ByteArray(signature.to_vec().into()),
+            ))
+            .add_header(Header::new(
+                ":date",
+                HeaderValue::Timestamp(aws_smithy_types::DateTime::from_secs(
+                    signature.len() as i64
+                )),
+            ))
+    }
+
+    fn encode(message: &Message) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_message_to(message, &mut out).unwrap();
+        out
+    }
+
+    fn stream(messages: &[Message]) -> Vec<u8> {
+        messages.iter().flat_map(encode).collect()
+    }
+
+    fn base64(body: &[u8]) -> String {
+        base64_simd::STANDARD.encode_to_string(body)
+    }
+
+    #[test]
+    fn matching_streams() {
+        let expected = stream(&[
+            event("Greeting", "application/json", br#"{"a": 1, "b": 2}"#),
+            event("Farewell", "text/plain", b"bye"),
+        ]);
+        let actual = stream(&[
+            event("Greeting", "application/json", br#"{"b":2,"a":1}"#),
+            event("Farewell", "text/plain", b"bye"),
+        ]);
+        validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect("payloads are compared as JSON");
+    }
+
+    #[test]
+    fn header_order_is_ignored() {
+        let message = event("Greeting", "text/plain", b"hi");
+        let reordered = Message::new_from_parts(
+            message.headers().iter().rev().cloned().collect(),
+            message.payload().clone(),
+        );
+        validate_body(
+            stream(&[reordered]),
+            &base64(&stream(&[message])),
+            MediaType::EventStream,
+        )
+        .expect("header order doesn't matter");
+    }
+
+    #[test]
+    fn mismatched_headers() {
+        let expected = stream(&[event("Greeting", "text/plain", b"hi")]);
+        let actual = stream(&[event("Farewell", "text/plain", b"hi")]);
+        let err = validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect_err("event types differ");
+        match err {
+            ProtocolTestFailure::BodyDidNotMatch { hint, .. } => {
+                assert_eq!("event stream message 0: headers differ", hint)
+            }
+            other => panic!("unexpected failure: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mismatched_payloads() {
+        let expected = stream(&[event("Greeting", "application/json", br#"{"a": 1}"#)]);
+        let actual = stream(&[event("Greeting", "application/json", br#"{"a": 2}"#)]);
+        let err = validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect_err("payloads differ");
+        match err {
+            ProtocolTestFailure::BodyDidNotMatch { hint, .. } => {
+                assert!(
+                    hint.starts_with("event stream message 0: payloads differ"),
+                    "{hint}"
+                )
+            }
+            other => panic!("unexpected failure: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cbor_payloads_are_compared_semantically() {
+        // {"a": 1, "b": 2} and {"b": 2, "a": 1}
+        let expected = stream(&[event(
+            "Greeting",
+            "application/cbor",
+            &[0xa2, 0x61, 0x61, 0x01, 0x61, 0x62, 0x02],
+        )]);
+        let actual = stream(&[event(
+            "Greeting",
+            "application/cbor",
+            &[0xa2, 0x61, 0x62, 0x02, 0x61, 0x61, 0x01],
+        )]);
+        validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect("map order doesn't matter");
+    }
+
+    #[test]
+    fn mismatched_message_count() {
+        let expected = stream(&[
+            event("Greeting", "text/plain", b"hi"),
+            event("Greeting", "text/plain", b"hi"),
+        ]);
+        let actual = stream(&[event("Greeting", "text/plain", b"hi")]);
+        validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect_err("a message is missing");
+    }
+
+    #[test]
+    fn signatures_are_ignored() {
+        let message = event("Greeting", "text/plain", b"hi");
+        let expected = stream(&[signed(Some(&message), b"signature"), signed(None, b"end")]);
+        let actual = stream(&[
+            signed(Some(&message), b"another signature"),
+            signed(None, b"another end"),
+        ]);
+        validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect("signatures aren't compared");
+
+        let other = event("Greeting", "text/plain", b"bye");
+        let actual = stream(&[signed(Some(&other), b"signature"), signed(None, b"end")]);
+        validate_body(actual, &base64(&expected), MediaType::EventStream)
+            .expect_err("signed messages differ");
+    }
+
+    #[test]
+    fn invalid_actual_body() {
+        let expected = stream(&[event("Greeting", "text/plain", b"hi")]);
+        let err = validate_body(
+            b"not an event stream",
+            &base64(&expected),
+            MediaType::EventStream,
+        )
+        .expect_err("body isn't an event stream");
+        assert!(matches!(err, ProtocolTestFailure::InvalidBodyFormat { .. }));
+    }
+}
@@ -13,9 +13,11 @@
     rust_2018_idioms
 )]
 
+mod eventstream;
 mod urlencoded;
 mod xml;
 
+use crate::eventstream::try_event_stream_eq;
 use crate::sealed::GetNormalizedHeader;
 use crate::xml::try_xml_equivalent;
 use assert_json_diff::assert_json_matches_no_panic;
@@ -343,6 +345,8 @@ pub enum MediaType {
     Xml,
     /// CBOR media types are decoded from base64 to binary and compared
     Cbor,
+    /// Event streams are decoded from base64 to binary, and compared message by message
+    EventStream,
     /// For x-www-form-urlencoded, do some map order comparison shenanigans
     UrlEncodedForm,
     /// Other media types are compared literally
@@ -356,6 +360,7 @@ impl<T: AsRef<str>> From<T> for MediaType {
             "application/x-amz-json-1.1" => MediaType::Json,
             "application/xml" => MediaType::Xml,
             "application/cbor" => MediaType::Cbor,
+            "application/vnd.amazon.eventstream" => MediaType::EventStream,
             "application/x-www-form-urlencoded" => MediaType::UrlEncodedForm,
             other => MediaType::Other(other.to_string()),
         }
@@ -387,6 +392,7 @@ pub fn validate_body<T: AsRef<[u8]> + Debug>(
             found: "input was not valid UTF-8".to_owned(),
         }),
         (MediaType::Cbor, _) => try_cbor_eq(actual_body, expected_body),
+        (MediaType::EventStream, _) => try_event_stream_eq(actual_body.as_ref(), expected_body),
         (MediaType::Other(media_type), Ok(actual_body)) => {
             if actual_body != expected_body {
                 Err(ProtocolTestFailure::BodyDidNotMatch {
@@ -497,6 +503,12 @@ fn cbor_values_equal(
             Ok(a_float == b_float || (a_float.is_nan() && b_float.is_nan()))
         }
 
+        // Tagged values, e.g. timestamps, may wrap floats or aggregates.
+        (
+            ciborium::value::Value::Tag(a_tag, a_value),
+            ciborium::value::Value::Tag(b_tag, b_value),
+        ) => Ok(a_tag == b_tag && cbor_values_equal(a_value, b_value)?),
+
         _ => Ok(a == b),
     }
 }
@@ -585,7 +597,7 @@ actual body in base64 (useful to update the protocol test):
 
 pub fn decode_body_data(body: &[u8], media_type: MediaType) -> Cow<'_, [u8]> {
     match media_type {
-        MediaType::Cbor => Cow::Owned(
+        MediaType::Cbor | MediaType::EventStream => Cow::Owned(
             base64_simd::STANDARD
                 .decode_to_vec(body)
                 .expect("smithy protocol test `body` property is not properly base64 encoded"),
@@ -745,6 +757,21 @@ mod tests {
         let expected_base64 = base64_encode(&[0xBF, 0x61, 0x61, 0x9F, 0x02, 0x01, 0xFF, 0xFF]);
         validate_body(actual, expected_base64.as_str(), MediaType::Cbor)
             .expect_err("arrays in CBOR should follow strict ordering");
+
+        // The following is the CBOR representation of `1(NaN)`, a timestamp, as a single precision float.
+        let actual = [0xC1, 0xFA, 0x7F, 0xC0, 0x00, 0x00];
+        // The following is the CBOR representation of `1(NaN)` as a double precision float.
+        let expected_base64 =
+            base64_encode(&[0xC1, 0xFB, 0x7F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        validate_body(actual, expected_base64.as_str(), MediaType::Cbor)
+            .expect("NaN values in tagged CBOR values should match");
+
+        // The following is the CBOR representation of `1(1.5)`.
+        let actual = [0xC1, 0xF9, 0x3E, 0x00];
+        // The following is the CBOR representation of `1(2.5)`.
+        let expected_base64 = base64_encode(&[0xC1, 0xF9, 0x41, 0x00]);
+        validate_body(actual, expected_base64.as_str(), MediaType::Cbor)
+            .expect_err("tagged CBOR values should be compared");
     }
 
     #[test]
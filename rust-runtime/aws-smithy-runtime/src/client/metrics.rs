@@ -10,7 +10,7 @@ use aws_smithy_observability::{
 };
 use aws_smithy_runtime_api::client::{
     interceptors::{dyn_dispatch_hint, Intercept, SharedInterceptor},
-    orchestrator::Metadata,
+    orchestrator::{Metadata, OrchestratorError},
     runtime_components::RuntimeComponentsBuilder,
     runtime_plugin::RuntimePlugin,
 };
@@ -26,21 +26,9 @@ fn add_outcome_attrs(
         '_,
     >,
 ) {
-    // Coarse category only; the error is type-erased here, so the modeled name isn't reachable.
     // Absent on success, per OTel convention.
     if let Some(Err(err)) = context.output_or_error() {
-        let category = if err.is_timeout_error() {
-            "timeout"
-        } else if err.is_connector_error() {
-            "connector"
-        } else if err.is_response_error() {
-            "response"
-        } else if err.is_operation_error() {
-            "operation"
-        } else {
-            "other"
-        };
-        attrs.set("error.type", AttributeValue::String(category.into()));
+        attrs.set("error.type", AttributeValue::String(error_type(err).into()));
     }
 
     // Raw HTTP status code, whenever a response reached us.
@@ -52,6 +40,23 @@ fn add_outcome_attrs(
     }
 }
 
+/// Returns the coarse category of `err` used for the `error.type` attribute.
+///
+/// The error is type-erased here, so the modeled name isn't reachable.
+pub(crate) fn error_type<E>(err: &OrchestratorError<E>) -> &'static str {
+    if err.is_timeout_error() {
+        "timeout"
+    } else if err.is_connector_error() {
+        "connector"
+    } else if err.is_response_error() {
+        "response"
+    } else if err.is_operation_error() {
+        "operation"
+    } else {
+        "other"
+    }
+}
+
 /// Struct to hold metric data in the ConfigBag
 #[derive(Debug, Clone)]
 pub(crate) struct MeasurementsContainer {
@@ -49,6 +49,9 @@ mod http;
 /// Utility for making one-off unmodeled requests with the orchestrator.
 pub mod operation;
 
+/// Operation and attempt spans emitted through the global telemetry provider
+mod spans;
+
 macro_rules! halt {
     ([$ctx:ident] => $err:expr) => {{
         debug!("encountered orchestrator error; halting");
@@ -137,18 +140,22 @@ pub enum StopPoint {
 ///
 /// See the docs on [`invoke`] for more details.
 pub async fn invoke_with_stop_point(
-    // NOTE: service_name and operation_name were at one point used for instrumentation that is now
-    // handled as part of codegen. Manually constructed operations (e.g. via Operation::builder())
-    // are handled as part of Operation::invoke
-    _service_name: &str,
-    _operation_name: &str,
+    // NOTE: `tracing` instrumentation for the service and operation names is handled as part of
+    // codegen. Manually constructed operations (e.g. via Operation::builder()) are handled as part
+    // of Operation::invoke. The names are only used here for the spans emitted through the
+    // telemetry provider.
+    service_name: &str,
+    operation_name: &str,
     input: Input,
     runtime_plugins: &RuntimePlugins,
     stop_point: StopPoint,
 ) -> Result<InterceptorContext, SdkError<Error, HttpResponse>> {
-    async move {
+    let operation_span = spans::start_operation_span(service_name, operation_name);
+    let result = async {
         let mut cfg = ConfigBag::base();
         let cfg = &mut cfg;
+        cfg.interceptor_state()
+            .store_put(operation_span.operation_span());
 
         let mut ctx = InterceptorContext::new(input);
 
@@ -175,7 +182,9 @@ pub async fn invoke_with_stop_point(
         .maybe_timeout(operation_timeout_config)
         .await
     }
-    .await
+    .await;
+    spans::end_operation_span(operation_span, &result);
+    result
 }
 
 /// Apply configuration is responsible for apply runtime plugins to the config bag, as well as running
@@ -337,6 +346,7 @@ async fn try_op(
         let attempt_timeout_config =
             MaybeTimeoutConfig::new(runtime_components, cfg, TimeoutKind::OperationAttempt);
         trace!(attempt_timeout_config = ?attempt_timeout_config);
+        let attempt_span = spans::start_attempt_span(cfg, i);
         let maybe_timeout = async {
             debug!("beginning attempt #{i}");
             try_attempt(ctx, cfg, runtime_components, stop_point)
@@ -353,6 +363,7 @@ async fn try_op(
 
         // We continue when encountering a timeout error. The retry classifier will decide what to do with it.
         continue_on_err!([ctx] => maybe_timeout);
+        spans::end_attempt_span(attempt_span, ctx);
 
         // If we got a retry strategy from the bag, ask it what to do.
         // If no strategy was set, we won't retry.
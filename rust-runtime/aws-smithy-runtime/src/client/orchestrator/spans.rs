@@ -0,0 +1,151 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Operation and attempt spans, emitted through the tracer provider of the global
+//! [`TelemetryProvider`](aws_smithy_observability::TelemetryProvider).
+//!
+//! These complement the `tracing` spans emitted by the orchestrator, which are unaffected.
+
+use crate::client::metrics::error_type;
+use aws_smithy_observability::global::get_telemetry_provider;
+use aws_smithy_observability::tracer::{Span, SpanKind, SpanStatus, Tracer};
+use aws_smithy_observability::{AttributeValue, Attributes, TelemetryProvider};
+use aws_smithy_runtime_api::client::interceptors::context::{Error, InterceptorContext};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use std::sync::Arc;
+
+const SCOPE: &str = "aws-smithy-runtime";
+
+/// The span covering an entire operation invocation, stored in the config bag so that attempt
+/// spans can be parented to it.
+#[derive(Debug, Clone)]
+pub(super) struct OperationSpan(Arc<dyn Span>);
+
+impl Storable for OperationSpan {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Ends its span if it's dropped before the outcome was recorded, e.g. because the operation
+/// timed out or the future driving it was cancelled, so that spans are never left open.
+#[derive(Debug)]
+pub(super) struct SpanGuard {
+    span: Arc<dyn Span>,
+    finished: bool,
+}
+
+impl SpanGuard {
+    fn new(span: Arc<dyn Span>) -> Self {
+        Self {
+            span,
+            finished: false,
+        }
+    }
+
+    /// The span of the operation, to be stored in the config bag.
+    pub(super) fn operation_span(&self) -> OperationSpan {
+        OperationSpan(self.span.clone())
+    }
+
+    fn finish(mut self, record: impl FnOnce(&dyn Span)) {
+        record(self.span.as_ref());
+        self.span.end();
+        self.finished = true;
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            set_error(self.span.as_ref(), "cancelled");
+            self.span.end();
+        }
+    }
+}
+
+fn tracer() -> Tracer {
+    get_telemetry_provider()
+        .unwrap_or_else(|_| Arc::new(TelemetryProvider::noop()))
+        .tracer_provider()
+        .get_tracer(SCOPE, None)
+}
+
+/// Starts the span for an operation invocation.
+pub(super) fn start_operation_span(service_name: &str, operation_name: &str) -> SpanGuard {
+    let mut attributes = Attributes::new();
+    attributes.set("rpc.service", AttributeValue::String(service_name.into()));
+    attributes.set("rpc.method", AttributeValue::String(operation_name.into()));
+
+    SpanGuard::new(
+        tracer()
+            .create_span(format!("{service_name}.{operation_name}"))
+            .set_kind(SpanKind::Client)
+            .set_attributes(attributes)
+            .build(),
+    )
+}
+
+/// Records the outcome of the operation on its span and ends it.
+pub(super) fn end_operation_span(
+    span: SpanGuard,
+    result: &Result<InterceptorContext, SdkError<Error, HttpResponse>>,
+) {
+    span.finish(|span| match result {
+        Ok(ctx) => {
+            set_status_code(span, ctx.response());
+            span.set_status(SpanStatus::Ok);
+        }
+        Err(err) => {
+            set_status_code(span, err.raw_response());
+            let category = match err {
+                SdkError::TimeoutError(_) => "timeout",
+                SdkError::DispatchFailure(_) => "connector",
+                SdkError::ResponseError(_) => "response",
+                SdkError::ServiceError(_) => "operation",
+                _ => "other",
+            };
+            set_error(span, category);
+        }
+    });
+}
+
+/// Starts the span for attempt number `attempt` of the operation whose span is in `cfg`.
+pub(super) fn start_attempt_span(cfg: &ConfigBag, attempt: u32) -> SpanGuard {
+    let tracer = tracer();
+    let mut builder = tracer.create_span("attempt");
+    if let Some(OperationSpan(parent)) = cfg.load::<OperationSpan>() {
+        builder = builder.set_parent(parent.clone());
+    }
+    let span = builder.build();
+    span.set_attribute("attempt".into(), AttributeValue::I64(attempt.into()));
+    SpanGuard::new(span)
+}
+
+/// Records the outcome of the attempt in `ctx` on its span and ends it.
+pub(super) fn end_attempt_span(span: SpanGuard, ctx: &InterceptorContext) {
+    span.finish(|span| {
+        set_status_code(span, ctx.response());
+        match ctx.output_or_error() {
+            Some(Err(err)) => set_error(span, error_type(err)),
+            Some(Ok(_)) => span.set_status(SpanStatus::Ok),
+            None => {}
+        }
+    });
+}
+
+fn set_status_code(span: &dyn Span, response: Option<&HttpResponse>) {
+    if let Some(response) = response {
+        span.set_attribute(
+            "http.status_code".into(),
+            AttributeValue::I64(response.status().as_u16().into()),
+        );
+    }
+}
+
+fn set_error(span: &dyn Span, category: &'static str) {
+    span.set_attribute("error.type".into(), AttributeValue::String(category.into()));
+    span.set_status(SpanStatus::Error(category.into()));
+}
@@ -11,6 +11,8 @@ pub enum SmithySdkFeature {
     Waiter,
     Paginator,
     GzipRequestCompression,
+    ZstdRequestCompression,
+    BrotliRequestCompression,
     ProtocolRpcV2Cbor,
     RetryModeStandard,
     RetryModeAdaptive,
@@ -0,0 +1,126 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+mod telemetry_common;
+use telemetry_common::Deserializer;
+
+use aws_smithy_observability::global::set_telemetry_provider;
+use aws_smithy_observability::test_util::InMemoryMeterProvider;
+use aws_smithy_observability::{AttributeValue, TelemetryProvider};
+use aws_smithy_runtime::client::http::test_util::infallible_client_fn;
+use aws_smithy_runtime::client::metrics::MetricsRuntimePlugin;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An operation whose first `failures` attempts get a 503 response
+fn operation(operation_name: &'static str, failures: usize) -> Operation<(), String, Infallible> {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let http_client = infallible_client_fn(move |_req| {
+        let status = if requests.fetch_add(1, Ordering::SeqCst) < failures {
+            503
+        } else {
+            200
+        };
+        http_02x::Response::builder()
+            .status(status)
+            .body("response body")
+            .unwrap()
+    });
+
+    Operation::builder()
+        .service_name("TestService")
+        .operation_name(operation_name)
+        .http_client(http_client)
+        .endpoint_url("http://localhost:1234/doesntmatter")
+        .no_auth()
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .standard_retry(
+            &RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_max_backoff(Duration::from_millis(1)),
+        )
+        .timeout_config(TimeoutConfig::disabled())
+        .serializer(|_body: ()| Ok(HttpRequest::new(SdkBody::empty())))
+        .deserializer_impl(Deserializer)
+        .runtime_plugin(
+            MetricsRuntimePlugin::builder()
+                .with_scope("test-scope")
+                .build()
+                .unwrap(),
+        )
+        .build()
+}
+
+#[tokio::test]
+async fn metrics_runtime_plugin_records_call_and_attempt_durations() {
+    let meter_provider = InMemoryMeterProvider::new();
+    set_telemetry_provider(
+        TelemetryProvider::builder()
+            .meter_provider(Arc::new(meter_provider.clone()))
+            .build(),
+    )
+    .unwrap();
+
+    operation("RetriedOperation", 1)
+        .invoke(())
+        .await
+        .expect("success after retry");
+    operation("OtherOperation", 0)
+        .invoke(())
+        .await
+        .expect("success");
+
+    let call_duration = meter_provider.query("smithy.client.call.duration");
+    assert_eq!(2, call_duration.count());
+    let retried = call_duration.clone().for_operation("RetriedOperation");
+    assert_eq!(1, retried.count());
+    assert!(retried.sum() > 0.0);
+    let measurement = &retried.measurements()[0];
+    assert_eq!("test-scope", measurement.scope());
+    assert_eq!(
+        Some(&AttributeValue::String("TestService".into())),
+        measurement.attributes().get("rpc.service")
+    );
+    assert_eq!(
+        Some(&AttributeValue::I64(200)),
+        measurement.attributes().get("http.status_code")
+    );
+
+    let attempt_duration = meter_provider.query("smithy.client.call.attempt.duration");
+    assert_eq!(
+        2,
+        attempt_duration
+            .clone()
+            .for_operation("RetriedOperation")
+            .count()
+    );
+    assert_eq!(
+        1,
+        attempt_duration
+            .clone()
+            .for_operation("RetriedOperation")
+            .with_attribute("attempt", AttributeValue::I64(2))
+            .count()
+    );
+    assert_eq!(1, attempt_duration.for_operation("OtherOperation").count());
+
+    assert_eq!(
+        "response body".len() as f64,
+        meter_provider
+            .query("smithy.client.call.response.size")
+            .for_operation("OtherOperation")
+            .sum()
+    );
+}
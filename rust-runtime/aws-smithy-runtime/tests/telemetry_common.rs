@@ -0,0 +1,31 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_runtime_api::client::interceptors::context::{Error, Output};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::client::ser_de::DeserializeResponse;
+
+/// Deserializes successful responses into the `"output"` string and fails every other response
+/// with a connector error, so that the operation's attempts can be retried
+#[derive(Debug)]
+pub struct Deserializer;
+
+impl DeserializeResponse for Deserializer {
+    fn deserialize_nonstreaming(
+        &self,
+        resp: &HttpResponse,
+    ) -> Result<Output, OrchestratorError<Error>> {
+        if resp.status().is_success() {
+            Ok(Output::erase("output".to_owned()))
+        } else {
+            Err(OrchestratorError::connector(ConnectorError::io(
+                "mock connector error".into(),
+            )))
+        }
+    }
+}
@@ -0,0 +1,275 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+mod telemetry_common;
+use telemetry_common::Deserializer;
+
+use aws_smithy_async::rt::sleep::TokioSleep;
+use aws_smithy_observability::global::set_telemetry_provider;
+use aws_smithy_observability::tracer::{
+    ProvideSpan, ProvideTracer, Span, SpanBuilder, SpanKind, SpanStatus, Tracer,
+};
+use aws_smithy_observability::{AttributeValue, Attributes, Scope, TelemetryProvider};
+use aws_smithy_runtime::client::http::test_util::{infallible_client_fn, NeverClient};
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::HttpStatusCodeClassifier;
+use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::any::Any;
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct RecordedSpan {
+    id: usize,
+    name: String,
+    kind: SpanKind,
+    parent: Option<usize>,
+    attributes: Mutex<Attributes>,
+    status: Mutex<SpanStatus>,
+    ended: AtomicBool,
+}
+
+impl RecordedSpan {
+    fn attribute(&self, key: &str) -> Option<AttributeValue> {
+        self.attributes.lock().unwrap().get(key).cloned()
+    }
+
+    fn status(&self) -> SpanStatus {
+        self.status.lock().unwrap().clone()
+    }
+}
+
+impl Scope for RecordedSpan {
+    fn end(&self) {
+        self.ended.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Span for RecordedSpan {
+    fn set_attribute(&self, key: Cow<'static, str>, value: AttributeValue) {
+        self.attributes.lock().unwrap().set(key, value);
+    }
+
+    fn add_event(&self, _name: Cow<'static, str>, _attributes: Option<&Attributes>) {}
+
+    fn set_status(&self, status: SpanStatus) {
+        *self.status.lock().unwrap() = status;
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordingTracer {
+    spans: Mutex<Vec<Arc<RecordedSpan>>>,
+}
+
+impl ProvideSpan for RecordingTracer {
+    fn create_span(&self, builder: SpanBuilder<'_>) -> Arc<dyn Span> {
+        let mut spans = self.spans.lock().unwrap();
+        let span = Arc::new(RecordedSpan {
+            id: spans.len(),
+            name: builder.get_name().to_string(),
+            kind: builder.get_kind(),
+            parent: builder
+                .get_parent()
+                .as_ref()
+                .and_then(|parent| (parent.as_ref() as &dyn Any).downcast_ref::<RecordedSpan>())
+                .map(|parent| parent.id),
+            attributes: Mutex::new(builder.get_attributes().clone().unwrap_or_default()),
+            status: Mutex::new(SpanStatus::Unset),
+            ended: AtomicBool::new(false),
+        });
+        spans.push(span.clone());
+        span
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordingTracerProvider {
+    tracer: Arc<RecordingTracer>,
+}
+
+impl ProvideTracer for RecordingTracerProvider {
+    fn get_tracer(&self, _scope: &'static str, _attributes: Option<&Attributes>) -> Tracer {
+        Tracer::new(self.tracer.clone())
+    }
+}
+
+impl RecordingTracerProvider {
+    /// The operation span with the name `name`, followed by its attempt spans.
+    fn spans_of(&self, name: &str) -> Vec<Arc<RecordedSpan>> {
+        let spans = self.tracer.spans.lock().unwrap();
+        let operation = spans
+            .iter()
+            .find(|span| span.name == name)
+            .expect("operation span was emitted");
+        let attempts = spans
+            .iter()
+            .filter(|span| span.parent == Some(operation.id));
+        std::iter::once(operation)
+            .chain(attempts)
+            .cloned()
+            .collect()
+    }
+}
+
+/// The global telemetry provider is shared by every test in this binary, so each test uses a
+/// distinct operation name to find its spans.
+fn recording_provider() -> &'static Arc<RecordingTracerProvider> {
+    static PROVIDER: OnceLock<Arc<RecordingTracerProvider>> = OnceLock::new();
+    PROVIDER.get_or_init(|| {
+        let provider = Arc::new(RecordingTracerProvider::default());
+        set_telemetry_provider(
+            TelemetryProvider::builder()
+                .tracer_provider(provider.clone())
+                .build(),
+        )
+        .unwrap();
+        provider
+    })
+}
+
+fn never_responding_operation(
+    operation_name: &'static str,
+    timeout_config: TimeoutConfig,
+) -> Operation<(), String, Infallible> {
+    Operation::builder()
+        .service_name("TestService")
+        .operation_name(operation_name)
+        .http_client(NeverClient::new())
+        .endpoint_url("http://localhost:1234/doesntmatter")
+        .no_auth()
+        .no_retry()
+        .timeout_config(timeout_config)
+        .sleep_impl(TokioSleep::new())
+        .serializer(|_body: ()| Ok(HttpRequest::new(SdkBody::empty())))
+        .deserializer_impl(Deserializer)
+        .build()
+}
+
+#[tokio::test]
+async fn operation_and_attempt_spans_are_emitted() {
+    let provider = recording_provider();
+
+    // Fail the first attempt so that the operation is retried
+    let requests = Arc::new(AtomicUsize::new(0));
+    let http_client = infallible_client_fn(move |_req| {
+        let status = match requests.fetch_add(1, Ordering::SeqCst) {
+            0 => 503,
+            _ => 200,
+        };
+        http_02x::Response::builder()
+            .status(status)
+            .body(SdkBody::empty())
+            .unwrap()
+    });
+    let op: Operation<(), String, Infallible> = Operation::builder()
+        .service_name("TestService")
+        .operation_name("TestOperation")
+        .http_client(http_client)
+        .endpoint_url("http://localhost:1234/doesntmatter")
+        .no_auth()
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .standard_retry(
+            &RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_max_backoff(Duration::from_millis(1)),
+        )
+        .timeout_config(TimeoutConfig::disabled())
+        .serializer(|_body: ()| Ok(HttpRequest::new(SdkBody::empty())))
+        .deserializer_impl(Deserializer)
+        .build();
+
+    assert_eq!("output", op.invoke(()).await.expect("success after retry"));
+
+    let spans = provider.spans_of("TestService.TestOperation");
+    assert_eq!(3, spans.len(), "{spans:#?}");
+    assert!(spans.iter().all(|span| span.ended.load(Ordering::SeqCst)));
+
+    let operation = &spans[0];
+    assert_eq!("TestService.TestOperation", operation.name);
+    assert_eq!(SpanKind::Client, operation.kind);
+    assert_eq!(None, operation.parent);
+    assert_eq!(
+        Some(AttributeValue::String("TestService".into())),
+        operation.attribute("rpc.service")
+    );
+    assert_eq!(
+        Some(AttributeValue::String("TestOperation".into())),
+        operation.attribute("rpc.method")
+    );
+    assert_eq!(
+        Some(AttributeValue::I64(200)),
+        operation.attribute("http.status_code")
+    );
+    assert_eq!(SpanStatus::Ok, operation.status());
+
+    let (failed, succeeded) = (&spans[1], &spans[2]);
+    for (attempt, span) in [failed, succeeded].into_iter().enumerate() {
+        assert_eq!("attempt", span.name);
+        assert_eq!(Some(operation.id), span.parent);
+        assert_eq!(
+            Some(AttributeValue::I64(attempt as i64 + 1)),
+            span.attribute("attempt")
+        );
+    }
+    assert_eq!(
+        Some(AttributeValue::I64(503)),
+        failed.attribute("http.status_code")
+    );
+    assert_eq!(
+        Some(AttributeValue::String("connector".into())),
+        failed.attribute("error.type")
+    );
+    assert_eq!(SpanStatus::Error("connector".into()), failed.status());
+    assert_eq!(SpanStatus::Ok, succeeded.status());
+    assert_eq!(None, succeeded.attribute("error.type"));
+}
+
+#[tokio::test]
+async fn spans_are_ended_when_the_operation_times_out() {
+    let provider = recording_provider();
+    let op = never_responding_operation(
+        "TimedOutOperation",
+        TimeoutConfig::builder()
+            .operation_timeout(Duration::from_millis(10))
+            .build(),
+    );
+
+    let err = op.invoke(()).await.expect_err("operation timed out");
+    assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+
+    let spans = provider.spans_of("TestService.TimedOutOperation");
+    assert_eq!(2, spans.len(), "{spans:#?}");
+    assert!(spans.iter().all(|span| span.ended.load(Ordering::SeqCst)));
+    let (operation, attempt) = (&spans[0], &spans[1]);
+    assert_eq!(SpanStatus::Error("timeout".into()), operation.status());
+    assert_eq!(SpanStatus::Error("cancelled".into()), attempt.status());
+}
+
+#[tokio::test]
+async fn spans_are_ended_when_the_operation_is_cancelled() {
+    let provider = recording_provider();
+    let op = never_responding_operation("CancelledOperation", TimeoutConfig::disabled());
+
+    tokio::time::timeout(Duration::from_millis(10), op.invoke(()))
+        .await
+        .expect_err("the operation never completes");
+
+    let spans = provider.spans_of("TestService.CancelledOperation");
+    assert_eq!(2, spans.len(), "{spans:#?}");
+    for span in &spans {
+        assert!(span.ended.load(Ordering::SeqCst));
+        assert_eq!(SpanStatus::Error("cancelled".into()), span.status());
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+#![cfg(all(feature = "client", feature = "test-util"))]
+
+use aws_smithy_http_client::test_util::{ReplayEvent, VirtualTimeHarness};
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::{
+    HttpStatusCodeClassifier, TransientErrorClassifier,
+};
+use aws_smithy_runtime_api::client::interceptors::context::{Error, Output};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
+use aws_smithy_runtime_api::client::ser_de::DeserializeResponse;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::convert::Infallible;
+use std::time::{Duration, SystemTime};
+
+fn event(status: u16, latency: Duration) -> ReplayEvent {
+    ReplayEvent::new(
+        http_1x::Request::builder()
+            .uri("http://localhost:1234/doesntmatter")
+            .body(SdkBody::empty())
+            .unwrap(),
+        http_1x::Response::builder()
+            .status(status)
+            .body(SdkBody::empty())
+            .unwrap(),
+    )
+    .with_latency(latency)
+}
+
+fn operation(
+    harness: &VirtualTimeHarness,
+    timeout_config: TimeoutConfig,
+) -> Operation<(), String, Infallible> {
+    #[derive(Debug)]
+    struct Deserializer;
+    impl DeserializeResponse for Deserializer {
+        fn deserialize_nonstreaming(
+            &self,
+            resp: &HttpResponse,
+        ) -> Result<Output, OrchestratorError<Error>> {
+            if resp.status().is_success() {
+                Ok(Output::erase("output".to_owned()))
+            } else {
+                Err(OrchestratorError::connector(ConnectorError::io(
+                    "mock connector error".into(),
+                )))
+            }
+        }
+    }
+
+    Operation::builder()
+        .service_name("test")
+        .operation_name("test")
+        .http_client(harness.http_client())
+        .sleep_impl(harness.sleep_impl())
+        .time_source(harness.time_source())
+        .endpoint_url("http://localhost:1234/doesntmatter")
+        .no_auth()
+        .retry_classifier(HttpStatusCodeClassifier::default())
+        .retry_classifier(TransientErrorClassifier::<Infallible>::new())
+        .standard_retry(
+            &RetryConfig::standard()
+                .with_max_attempts(3)
+                .with_initial_backoff(Duration::from_secs(10))
+                .with_max_backoff(Duration::from_secs(60)),
+        )
+        .timeout_config(timeout_config)
+        .serializer(|_body: ()| Ok(HttpRequest::new(SdkBody::empty())))
+        .deserializer_impl(Deserializer)
+        .build()
+}
+
+#[tokio::test]
+async fn retries_back_off_in_virtual_time() {
+    let harness = VirtualTimeHarness::new(vec![
+        event(503, Duration::from_secs(1)),
+        event(503, Duration::from_secs(1)),
+        event(200, Duration::from_secs(1)),
+    ]);
+    let op = operation(&harness, TimeoutConfig::disabled());
+
+    let output = harness.run(op.invoke(())).await.expect("success");
+    assert_eq!("output", output);
+    assert_eq!(3, harness.replay_client().actual_requests().count());
+    // three responses of a second, and two backoffs of up to `initial_backoff * 2^attempt`
+    let elapsed = harness
+        .now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap();
+    assert!(
+        elapsed >= Duration::from_secs(3) && elapsed <= Duration::from_secs(33),
+        "elapsed = {elapsed:?}"
+    );
+    assert!(harness.pending_sleeps().is_empty());
+}
+
+#[tokio::test]
+async fn attempt_timeouts_fire_in_virtual_time() {
+    let harness = VirtualTimeHarness::new(vec![
+        event(200, Duration::from_secs(3600)),
+        event(200, Duration::from_secs(1)),
+    ]);
+    let op = operation(
+        &harness,
+        TimeoutConfig::builder()
+            .operation_attempt_timeout(Duration::from_secs(5))
+            .build(),
+    );
+
+    let output = harness.run(op.invoke(())).await.expect("success");
+    assert_eq!("output", output);
+    assert_eq!(2, harness.replay_client().actual_requests().count());
+    let elapsed = harness
+        .now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap();
+    assert!(
+        elapsed >= Duration::from_secs(6) && elapsed < Duration::from_secs(3600),
+        "elapsed = {elapsed:?}"
+    );
+}
+
+#[tokio::test]
+async fn operation_timeouts_fire_in_virtual_time() {
+    let harness = VirtualTimeHarness::new(vec![event(200, Duration::from_secs(3600))]);
+    let op = operation(
+        &harness,
+        TimeoutConfig::builder()
+            .operation_timeout(Duration::from_secs(30))
+            .build(),
+    );
+
+    let err = harness.run(op.invoke(())).await.expect_err("timeout");
+    assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+    assert_eq!(
+        SystemTime::UNIX_EPOCH + Duration::from_secs(30),
+        harness.now()
+    );
+}